@@ -15,6 +15,18 @@ reboot-message = Please Reboot to apply pending updates...
 reboot-now = Reboot Now
 updating = Updating the System, please wait...
 updating-warning = Do not close this program
+gpg-valid = Valid
+gpg-invalid = Invalid
+gpg-unsigned = Unsigned
+gpg-unknown = Unknown
+gpg-key-id = Key ID:
+gpg-signer = Signer:
+gpg-date = Date:
+gpg-invalid-warning = Warning! The GPG signature of this commit could not be verified, it may have been tampered with.
+gpg-unsigned-warning = Warning! This commit is not signed, its origin cannot be verified.
+untrusted-update = Untrusted Update
+untrusted-update-confirmation = Are you sure you want to apply this update anyway?
+proceed-anyway = Proceed Anyway
 
 update-applications = Update Applications
 update-applications-description = Update Flatpak installed Applications
@@ -22,6 +34,48 @@ checking-updates = Checking for Updates...
 no-updates = No Updates Found
 application-updates = Application Updates
 update-all = Update All
+installation-user = User
+installation-system = System
+runtime-updates = Runtimes and Extensions
+update-runtimes = Update Runtimes
+used-by = Used by
+no-dependent-apps = Not used by any installed application
+no-release-notes = No release notes
+updated-refs = Updated
+selected = selected
+select-all = Select All
+select-none = Select None
+update-selected = Update Selected
+update-results = Update results:
+update-succeeded = Updated
+update-failed = Failed
+dismiss = Dismiss
+requests-new-permissions = The update requests new permissions
+new-permissions = New Permissions
+new-permissions-confirmation = These applications request permissions they didn't have, update them anyway?
+checking-permissions = Checking the permissions of the new versions...
+update-anyway = Update Anyway
+held-updates = Held Back
+hold-application = Hold this application on its current version
+ignore-version = Ignore this version
+held-masked = Held with flatpak mask
+ignored-version = Ignored version:
+release = Release
+end-of-life = End of life, it won't get any more updates
+migrate-to = Migrate to
+migrated = Application migrated, its data was moved to the new one
+migrated-no-data = Application migrated, it had no data to move
+migrated-data-kept = Application migrated, its data was left in place because the new one already has its own
+install-applications = Install Applications
+search-placeholder = Search applications
+search = Search
+searching = Searching...
+no-search-results = No applications found
+install = Install
+install-into = Install into
+not-available-in-installation = Not available on this installation
+installed = Installed
+application-installed = Application installed
 
 rollback = Rollback
 rollback-description = Rollback to a previous System Deployment (Requires Reboot)
@@ -36,7 +90,39 @@ no-deployments-error = Error, no deployments found!
 manage-layered-packages = Manage Layered Packages
 pin-deployment = Pin this deployment
 unpin-deployment = Unpin this deployment
+delete-deployment-tooltip = Delete this deployment
+delete-deployment = Delete Deployment
+delete-deployment-confirmation = Are you sure you want to permanently delete this deployment?
+delete = Delete
+cancel = Cancel
 layered-packages = Layered Packages:
+edit-deployment-note = Edit the note of this deployment
+note = Note:
+note-label-placeholder = Label (Ej: Known good before NVIDIA update)
+note-placeholder = Why are you keeping this deployment?
+save = Save
+
+update-history = Update History
+update-history-description = See the changes made to your system over time
+no-history = No changes have been recorded yet
+history-upgrade = Upgrade
+history-rollback = Rollback
+history-rebase = Rebase
+history-layering = Layered Packages Change
+history-not-recorded = The change was applied but could not be saved in the update history
+no-package-changes = No package changes
+upgraded = Upgraded
+downgraded = Downgraded
+removed = Removed
+from = From:
+to = To:
+
+what-changed = What Changed
+notable-changes = Notable Changes
+no-notable-changes = No kernel, graphics or desktop changes
+fixed-advisories = Fixed Advisories
+no-fixed-advisories = No advisories were fixed
+continue = Continue
 
 apply-changes = Apply Changes
 add-packages = Add Packages
@@ -49,8 +135,124 @@ packages-to-remove-footer = Packages to Remove
 remove = Remove
 applying-changes = Applying changes, please wait...
 
+etc-changes = /etc Changes
+no-etc-changes = No local changes found on /etc
+modified = Modified
+added = Added
+deleted = Deleted
+rpm-leftover = Needs Review
+rpm-leftovers-warning = .rpmnew/.rpmsave leftover files found, please review and merge them
+no-content-changes = Only the file metadata (permissions, ownership...) has changed
+etc-diff-permission-denied = This file can only be read by root, the diff can't be shown
+
+disk-usage = Disk Usage
+calculating-disk-usage = Calculating disk usage, this may take a while...
+cleaning-up = Cleaning up, please wait...
+no-disk-usage = Could not calculate the disk usage
+select-disk-usage-slice = Select a part of the chart to see how to free up its space
+unreferenced-objects = Unused Objects
+unreferenced-objects-description = Objects of the ostree repository no deployment references anymore, plus temporary files.
+deployment = Deployment
+deployment-usage-description = Space this deployment doesn't share with the booted deployment or the ones before it, deleting it frees up to this space.
+booted-deployment-description = Space used by the deployment you are currently running, including the content it shares with the other deployments. It cannot be deleted.
+pinned-deployment-description = Space this deployment doesn't share with the booted deployment or the ones before it, unpin it to be able to delete it.
+cached-metadata = Cached Metadata
+cached-metadata-description = Cached rpm repository metadata, it will be downloaded again when needed.
+cleanup-base = Clean Up Unused Objects
+cleanup-metadata = Clear Cached Metadata
+cleanup-confirmation = Are you sure you want to run this clean up?
+
+installed-flatpaks = Installed Applications
+installed-flatpaks-description = Manage your installed Flatpak applications and runtimes
+no-installed-flatpaks = Nothing installed
+applications = Applications
+runtimes = Runtimes
+installed-on = Installed on
+uninstall = Uninstall
+uninstall-confirmation = Are you sure you want to uninstall this?
+uninstalled = Uninstalled successfully
+delete-app-data = Also delete the application data
+remove-unused = Remove Unused
+remove-unused-confirmation = The following runtimes and extensions are not used by any application and will be removed
+nothing-unused = There is nothing unused to remove
+unused-removed = Unused runtimes removed
+permissions = Permissions
+reset-overrides = Reset Overrides
+permission-filesystems = Filesystems
+permission-devices = Devices
+permission-sockets = Sockets
+permission-talk-names = Session Bus Names
+permission-overridden = Overridden
+permission-global-override = Global override
+filesystem-placeholder = Filesystem to allow (Ej: ~/Games, /mnt/data:ro, xdg-config/MangoHud)
+talk-name-placeholder = Bus name to allow (Ej: org.freedesktop.secrets)
+commit-history = Version History
+no-commits = No previous versions found on the remote
+installed-commit = Installed
+downgrade = Downgrade
+downgrade-confirmation = Are you sure you want to go back to this version of
+hold-after-downgrade = Hold the application on this version
+version-changed = Version changed successfully
+repair = Repair
+repair-installations = Repair Installations
+repair-description = Checks the installed applications and runtimes for missing or corrupted files, removes them and downloads them again
+repairing = Repairing, please wait...
+repair-summary = Repair finished
+nothing-to-repair = No problems were found
+removed-objects = Corrupted objects removed
+redownloaded-refs = Downloaded again
+removed-refs = Leftovers removed
+repair-problems = Could not be fixed
+
+flatpak-remotes = Flatpak Remotes
+flatpak-remotes-description = Manage the repositories your Flatpak applications are installed from
+no-remotes = No remotes configured
+add-remote = Add Remote
+add-remote-description = Add a remote from a .flatpakrepo file or URL, or from the URL of a repository
+remote-location-placeholder = .flatpakrepo file or URL (Ej: https://dl.flathub.org/repo/flathub.flatpakrepo)
+remote-name-placeholder = Name (Optional for .flatpakrepo files)
+remove-remote = Remove Remote
+remove-remote-confirmation = Are you sure you want to remove this remote?
+remote-changes-applied = Remotes updated successfully
+priority = Priority
+raise-priority = Raise priority
+lower-priority = Lower priority
+remote-disabled = Disabled
+remote-filter = Filter
+remote-subset = Subset
+enable = Enable
+disable = Disable
+restricted-flathub-warning = Warning! Flathub is filtered or limited to verified applications, only part of its applications can be installed.
+
+containers = Containers
+containers-description = Manage your Distrobox and Toolbox containers
+no-containers = No containers found
+no-container-managers = Neither Distrobox nor Toolbox are installed
+upgrade = Upgrade
+upgrade-all = Upgrade All
+start = Start
+stop = Stop
+create-container = Create Container
+create-container-description = Create a new container from one of these images, it will be downloaded if needed
+container-name-placeholder = Name (Ej: fedora-dev)
+create = Create
+delete-container = Delete Container
+delete-container-confirmation = Are you sure you want to delete this container? Everything installed inside it will be lost
+container-changes-applied = Containers updated successfully
+
+firmware = Firmware
+firmware-description = Update the firmware of your devices with fwupd
+no-firmware-devices = No devices with updatable firmware found
+firmware-up-to-date = Up to date
+current-version = Installed version
+available-version = Available version
+urgency = Urgency
+updating-firmware = Updating the firmware of
+firmware-updated = Firmware updated successfully
+
 configuration = Application Configuration
 theme = Theme
+default-installation = Default installation for new applications
 
 about = About
 author = Author:
@@ -15,6 +15,18 @@ reboot-message = Por favor Reinicia para aplicar las actualizaciones...
 reboot-now = Reiniciar Ahora
 updating = Actualizando el Sistema, por favor espere...
 updating-warning = No cierre este programa
+gpg-valid = Válida
+gpg-invalid = Inválida
+gpg-unsigned = Sin Firmar
+gpg-unknown = Desconocida
+gpg-key-id = ID de Clave:
+gpg-signer = Firmante:
+gpg-date = Fecha:
+gpg-invalid-warning = ¡Atención! No se pudo verificar la firma GPG de este commit, podría haber sido manipulado.
+gpg-unsigned-warning = ¡Atención! Este commit no está firmado, no se puede verificar su origen.
+untrusted-update = Actualización No Verificada
+untrusted-update-confirmation = Esta seguro de que quiere aplicar esta actualización de todos modos?
+proceed-anyway = Continuar de Todos Modos
 
 update-applications = Actualizar Aplicaciones
 update-applications-description = Actualiza las aplicaciones instaladas vía Flatpak
@@ -22,6 +34,48 @@ checking-updates = Buscando Actualizaciones...
 no-updates = Sin Actualizaciones
 application-updates = Actualizaciones de Aplicaciones
 update-all = Actualizar Todo
+installation-user = Usuario
+installation-system = Sistema
+runtime-updates = Runtimes y Extensiones
+update-runtimes = Actualizar Runtimes
+used-by = Usado por
+no-dependent-apps = No lo usa ninguna aplicación instalada
+no-release-notes = Sin notas de versión
+updated-refs = Actualizado
+selected = seleccionadas
+select-all = Seleccionar Todo
+select-none = No Seleccionar Nada
+update-selected = Actualizar Seleccionadas
+update-results = Resultados de la actualización:
+update-succeeded = Actualizada
+update-failed = Fallida
+dismiss = Descartar
+requests-new-permissions = La actualización pide nuevos permisos
+new-permissions = Nuevos Permisos
+new-permissions-confirmation = Estas aplicaciones piden permisos que no tenían, ¿actualizarlas de todos modos?
+checking-permissions = Comprobando los permisos de las nuevas versiones...
+update-anyway = Actualizar de Todos Modos
+held-updates = Retenidas
+hold-application = Mantener esta aplicación en su versión actual
+ignore-version = Ignorar esta versión
+held-masked = Retenida con flatpak mask
+ignored-version = Versión ignorada:
+release = Liberar
+end-of-life = Fin de vida, no recibirá más actualizaciones
+migrate-to = Migrar a
+migrated = Aplicación migrada, sus datos se han movido a la nueva
+migrated-no-data = Aplicación migrada, no tenía datos que mover
+migrated-data-kept = Aplicación migrada, sus datos se han dejado donde estaban porque la nueva ya tiene los suyos
+install-applications = Instalar Aplicaciones
+search-placeholder = Buscar aplicaciones
+search = Buscar
+searching = Buscando...
+no-search-results = No se han encontrado aplicaciones
+install = Instalar
+install-into = Instalar en
+not-available-in-installation = No disponible en esta instalación
+installed = Instalada
+application-installed = Aplicación instalada
 
 rollback = Rollback
 rollback-description = Rollback a un despliegue del sistema anterior (Requiere Reinicio)
@@ -36,7 +90,39 @@ no-deployments-error = Error, no se encontraron despliegues!
 manage-layered-packages = Paquetes "Capa"
 pin-deployment = Fijar este despliegue
 unpin-deployment = Desfijar este despliegue
+delete-deployment-tooltip = Eliminar este despliegue
+delete-deployment = Eliminar Despliegue
+delete-deployment-confirmation = Esta seguro de que quiere eliminar este despliegue permanentemente?
+delete = Eliminar
+cancel = Cancelar
 layered-packages = Paquetes Capa:
+edit-deployment-note = Editar la nota de este despliegue
+note = Nota:
+note-label-placeholder = Etiqueta (Ej: Funcionaba antes de actualizar NVIDIA)
+note-placeholder = ¿Por qué mantienes este despliegue?
+save = Guardar
+
+update-history = Historial de Actualizaciones
+update-history-description = Consulta los cambios realizados en tu sistema a lo largo del tiempo
+no-history = Todavía no se ha registrado ningún cambio
+history-upgrade = Actualización
+history-rollback = Rollback
+history-rebase = Rebase
+history-layering = Cambio de Paquetes Capa
+history-not-recorded = El cambio se aplicó pero no se pudo guardar en el historial de actualizaciones
+no-package-changes = Sin cambios de paquetes
+upgraded = Actualizados
+downgraded = Revertidos
+removed = Eliminados
+from = Desde:
+to = Hasta:
+
+what-changed = Qué ha Cambiado
+notable-changes = Cambios Destacados
+no-notable-changes = Sin cambios en el kernel, los gráficos o el escritorio
+fixed-advisories = Avisos Solucionados
+no-fixed-advisories = No se ha solucionado ningún aviso
+continue = Continuar
 
 apply-changes = Aplicar Cambios
 add-packages = Añadir Paquetes
@@ -49,8 +135,124 @@ packages-to-remove-footer = Paquetes a Eliminar
 remove = Eliminar
 applying-changes = Aplicando cambios, porfavor espere...
 
+etc-changes = Cambios en /etc
+no-etc-changes = No se encontraron cambios locales en /etc
+modified = Modificados
+added = Añadidos
+deleted = Eliminados
+rpm-leftover = Requiere Revisión
+rpm-leftovers-warning = archivos .rpmnew/.rpmsave encontrados, por favor revíselos y combínelos
+no-content-changes = Solo han cambiado los metadatos del archivo (permisos, propietario...)
+etc-diff-permission-denied = Este archivo solo puede leerlo root, no se puede mostrar la diferencia
+
+disk-usage = Uso del Disco
+calculating-disk-usage = Calculando el uso del disco, esto puede tardar...
+cleaning-up = Limpiando, por favor espere...
+no-disk-usage = No se pudo calcular el uso del disco
+select-disk-usage-slice = Selecciona una parte del gráfico para ver cómo liberar su espacio
+unreferenced-objects = Objetos sin Usar
+unreferenced-objects-description = Objetos del repositorio ostree que ya no usa ningún despliegue, además de archivos temporales.
+deployment = Despliegue
+deployment-usage-description = Espacio que este despliegue no comparte con el despliegue actual ni con los anteriores, eliminarlo libera hasta este espacio.
+booted-deployment-description = Espacio usado por el despliegue que estás ejecutando, incluido el contenido que comparte con los demás despliegues. No se puede eliminar.
+pinned-deployment-description = Espacio que este despliegue no comparte con el despliegue actual ni con los anteriores, desfíjalo para poder eliminarlo.
+cached-metadata = Metadatos en Caché
+cached-metadata-description = Metadatos de los repositorios rpm en caché, se volverán a descargar cuando sea necesario.
+cleanup-base = Limpiar Objetos sin Usar
+cleanup-metadata = Limpiar Metadatos en Caché
+cleanup-confirmation = Esta seguro de que quiere realizar esta limpieza?
+
+installed-flatpaks = Aplicaciones Instaladas
+installed-flatpaks-description = Gestiona tus aplicaciones y runtimes Flatpak instalados
+no-installed-flatpaks = No hay nada instalado
+applications = Aplicaciones
+runtimes = Runtimes
+installed-on = Instalado el
+uninstall = Desinstalar
+uninstall-confirmation = ¿Seguro que quieres desinstalar esto?
+uninstalled = Desinstalado correctamente
+delete-app-data = Eliminar también los datos de la aplicación
+remove-unused = Eliminar No Usados
+remove-unused-confirmation = Los siguientes runtimes y extensiones no los usa ninguna aplicación y se eliminarán
+nothing-unused = No hay nada sin usar que eliminar
+unused-removed = Runtimes sin usar eliminados
+permissions = Permisos
+reset-overrides = Restablecer Cambios
+permission-filesystems = Sistemas de Archivos
+permission-devices = Dispositivos
+permission-sockets = Sockets
+permission-talk-names = Nombres del Bus de Sesión
+permission-overridden = Modificado
+permission-global-override = Modificado globalmente
+filesystem-placeholder = Sistema de archivos a permitir (Ej: ~/Games, /mnt/data:ro, xdg-config/MangoHud)
+talk-name-placeholder = Nombre del bus a permitir (Ej: org.freedesktop.secrets)
+commit-history = Historial de Versiones
+no-commits = No se encontraron versiones anteriores en el remoto
+installed-commit = Instalada
+downgrade = Volver a esta Versión
+downgrade-confirmation = ¿Seguro que quieres volver a esta versión de
+hold-after-downgrade = Mantener la aplicación en esta versión
+version-changed = Versión cambiada correctamente
+repair = Reparar
+repair-installations = Reparar Instalaciones
+repair-description = Comprueba si faltan archivos o están dañados en las aplicaciones y runtimes instalados, los elimina y los vuelve a descargar
+repairing = Reparando, por favor espera...
+repair-summary = Reparación terminada
+nothing-to-repair = No se encontraron problemas
+removed-objects = Objetos dañados eliminados
+redownloaded-refs = Descargados de nuevo
+removed-refs = Restos eliminados
+repair-problems = No se pudieron arreglar
+
+flatpak-remotes = Remotos Flatpak
+flatpak-remotes-description = Gestiona los repositorios desde los que se instalan tus aplicaciones Flatpak
+no-remotes = No hay remotos configurados
+add-remote = Añadir Remoto
+add-remote-description = Añade un remoto desde un archivo o URL .flatpakrepo, o desde la URL de un repositorio
+remote-location-placeholder = Archivo o URL .flatpakrepo (Ej: https://dl.flathub.org/repo/flathub.flatpakrepo)
+remote-name-placeholder = Nombre (Opcional para archivos .flatpakrepo)
+remove-remote = Eliminar Remoto
+remove-remote-confirmation = ¿Seguro que quieres eliminar este remoto?
+remote-changes-applied = Remotos actualizados correctamente
+priority = Prioridad
+raise-priority = Subir prioridad
+lower-priority = Bajar prioridad
+remote-disabled = Desactivado
+remote-filter = Filtro
+remote-subset = Subconjunto
+enable = Activar
+disable = Desactivar
+restricted-flathub-warning = ¡Atención! Flathub está filtrado o limitado a aplicaciones verificadas, solo se puede instalar parte de sus aplicaciones.
+
+containers = Contenedores
+containers-description = Gestiona tus contenedores de Distrobox y Toolbox
+no-containers = No se encontraron contenedores
+no-container-managers = No están instalados ni Distrobox ni Toolbox
+upgrade = Actualizar
+upgrade-all = Actualizar Todos
+start = Iniciar
+stop = Detener
+create-container = Crear Contenedor
+create-container-description = Crea un nuevo contenedor a partir de una de estas imágenes, se descargará si es necesario
+container-name-placeholder = Nombre (Ej: fedora-dev)
+create = Crear
+delete-container = Eliminar Contenedor
+delete-container-confirmation = ¿Seguro que quieres eliminar este contenedor? Se perderá todo lo instalado dentro de él
+container-changes-applied = Contenedores actualizados correctamente
+
+firmware = Firmware
+firmware-description = Actualiza el firmware de tus dispositivos con fwupd
+no-firmware-devices = No se encontraron dispositivos con firmware actualizable
+firmware-up-to-date = Actualizado
+current-version = Versión instalada
+available-version = Versión disponible
+urgency = Urgencia
+updating-firmware = Actualizando el firmware de
+firmware-updated = Firmware actualizado correctamente
+
 configuration = Configuración de la Aplicación
 theme = Tema
+default-installation = Instalación por defecto para nuevas aplicaciones
 
 about = Acerca de
 author = Autor:
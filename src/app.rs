@@ -6,8 +6,11 @@ use iced::{Alignment, Length, Subscription, Theme};
 use iced::{Task, widget::text};
 
 use crate::app::core::config::Config;
+use crate::app::core::what_changed::WhatChanged;
 use crate::app::screen::{
-    Screen, about, config, rollback, system_status, update_applications, update_system,
+    Screen, about, config, containers, firmware, flatpak_remotes, installed_flatpaks,
+    post_update_summary, rollback, system_status, update_applications, update_history,
+    update_system,
 };
 use crate::app::style::{AccordionButtonPosition, icon_button_style, icon_svg_style};
 use crate::app::utils::ui::{
@@ -32,25 +35,38 @@ pub struct FAManagement {
 
 enum State {
     Loading,
-    Ready { config: Config, screen: Screen },
+    Ready { config: Config, screen: Box<Screen> },
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     ConfigLoaded(Result<Config, anywho::Error>),
     ConfigSaved(Result<(), anywho::Error>),
+    /// Callback after comparing the booted deployment with the one booted on the last run
+    WhatChangedChecked(Result<(String, Option<WhatChanged>), anywho::Error>),
 
     UpdateSystem(update_system::Message),
     UpdateApplications(update_applications::Message),
+    InstalledFlatpaks(installed_flatpaks::Message),
+    FlatpakRemotes(flatpak_remotes::Message),
+    Containers(containers::Message),
+    Firmware(firmware::Message),
     Rollback(rollback::Message),
     SystemStatus(system_status::Message),
+    UpdateHistory(update_history::Message),
+    PostUpdateSummary(post_update_summary::Message),
     Config(config::Message),
     About(about::Message),
 
     OpenUpdateSystem,
     OpenUpdateApplications,
+    OpenInstalledFlatpaks,
+    OpenFlatpakRemotes,
+    OpenContainers,
+    OpenFirmware,
     OpenRollback,
     OpenSystemStatus,
+    OpenUpdateHistory,
     OpenConfig,
     OpenAbout,
 
@@ -76,7 +92,7 @@ impl FAManagement {
     pub fn view(&self) -> iced::Element<'_, Message> {
         let content = match &self.state {
             State::Loading => center(text("Loading...")).into(),
-            State::Ready { screen, .. } => match screen {
+            State::Ready { screen, .. } => match screen.as_ref() {
                 Screen::Welcome => self.welcome_view(),
                 Screen::UpdateSystem(update_system) => {
                     update_system.view(self.now).map(Message::UpdateSystem)
@@ -84,10 +100,26 @@ impl FAManagement {
                 Screen::UpdateApplications(update_applications) => update_applications
                     .view(self.now)
                     .map(Message::UpdateApplications),
+                Screen::InstalledFlatpaks(installed_flatpaks) => installed_flatpaks
+                    .view(self.now)
+                    .map(Message::InstalledFlatpaks),
+                Screen::FlatpakRemotes(flatpak_remotes) => {
+                    flatpak_remotes.view(self.now).map(Message::FlatpakRemotes)
+                }
+                Screen::Containers(containers) => {
+                    containers.view(self.now).map(Message::Containers)
+                }
+                Screen::Firmware(firmware) => firmware.view(self.now).map(Message::Firmware),
                 Screen::Rollback(rollback) => rollback.view(self.now).map(Message::Rollback),
                 Screen::SystemStatus(system_status) => {
                     system_status.view(self.now).map(Message::SystemStatus)
                 }
+                Screen::UpdateHistory(update_history) => {
+                    update_history.view(self.now).map(Message::UpdateHistory)
+                }
+                Screen::PostUpdateSummary(post_update_summary) => post_update_summary
+                    .view(self.now)
+                    .map(Message::PostUpdateSummary),
                 Screen::Config(config) => config.view(self.now).map(Message::Config),
                 Screen::About(about) => about.view(self.now).map(Message::About),
             },
@@ -103,10 +135,15 @@ impl FAManagement {
             Message::ConfigLoaded(res) => {
                 match res {
                     Ok(config) => {
+                        let last_booted_checksum = config.last_booted_checksum.clone();
                         self.state = State::Ready {
                             config,
-                            screen: Screen::Welcome,
+                            screen: Box::new(Screen::Welcome),
                         };
+                        return Task::perform(
+                            WhatChanged::check(last_booted_checksum),
+                            Message::WhatChangedChecked,
+                        );
                     }
                     Err(err) => {
                         eprintln!("Error loading config: {err}");
@@ -120,13 +157,44 @@ impl FAManagement {
                 }
                 return Task::none();
             }
+            Message::WhatChangedChecked(res) => {
+                let State::Ready { screen, config } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let (booted_checksum, summary) = match res {
+                    Ok(result) => result,
+                    Err(err) => {
+                        eprintln!("Error checking what changed: {err}");
+                        return Task::none();
+                    }
+                };
+
+                // only interrupt the user if they are still on the welcome screen
+                if let Some(summary) = summary
+                    && matches!(**screen, Screen::Welcome)
+                {
+                    let (post_update_summary, _) = screen::PostUpdateSummary::new(summary);
+                    **screen = Screen::PostUpdateSummary(post_update_summary);
+                }
+
+                if booted_checksum.is_empty()
+                    || config.last_booted_checksum.as_ref() == Some(&booted_checksum)
+                {
+                    return Task::none();
+                }
+
+                config.last_booted_checksum = Some(booted_checksum);
+                let new_config = config.clone();
+                return Task::perform(new_config.save(APP_ID), Message::ConfigSaved);
+            }
 
             Message::UpdateSystem(message) => {
                 let State::Ready { screen, .. } = &mut self.state else {
                     return Task::none();
                 };
 
-                let Screen::UpdateSystem(update_system) = screen else {
+                let Screen::UpdateSystem(update_system) = screen.as_mut() else {
                     return Task::none();
                 };
 
@@ -134,7 +202,7 @@ impl FAManagement {
                     update_system::Action::None => Task::none(),
                     update_system::Action::Run(task) => task.map(Message::UpdateSystem),
                     update_system::Action::Back => {
-                        *screen = Screen::Welcome;
+                        **screen = Screen::Welcome;
                         return Task::none();
                     }
                     update_system::Action::AddToast(toast) => {
@@ -152,16 +220,16 @@ impl FAManagement {
                 };
 
                 let (update_system, task) = screen::UpdateSystem::new();
-                *screen = Screen::UpdateSystem(update_system);
+                **screen = Screen::UpdateSystem(update_system);
                 return task.map(Message::UpdateSystem);
             }
 
             Message::UpdateApplications(message) => {
-                let State::Ready { screen, .. } = &mut self.state else {
+                let State::Ready { screen, config } = &mut self.state else {
                     return Task::none();
                 };
 
-                let Screen::UpdateApplications(update_applications) = screen else {
+                let Screen::UpdateApplications(update_applications) = screen.as_mut() else {
                     return Task::none();
                 };
 
@@ -169,7 +237,7 @@ impl FAManagement {
                     update_applications::Action::None => Task::none(),
                     update_applications::Action::Run(task) => task.map(Message::UpdateApplications),
                     update_applications::Action::Back => {
-                        *screen = Screen::Welcome;
+                        **screen = Screen::Welcome;
                         return Task::none();
                     }
                     update_applications::Action::AddToast(toast) => {
@@ -181,24 +249,176 @@ impl FAManagement {
                         }
                         return Task::none();
                     }
+                    update_applications::Action::AddToastAndRun((toast, task)) => {
+                        self.toasts.push(toast);
+                        task.map(Message::UpdateApplications)
+                    }
+                    update_applications::Action::UpdateIgnoredUpdates(ignored_updates) => {
+                        config.ignored_updates = ignored_updates;
+                        let new_config = config.clone();
+                        Task::perform(new_config.save(APP_ID), Message::ConfigSaved)
+                    }
                 };
             }
             Message::OpenUpdateApplications => {
-                let State::Ready { screen, .. } = &mut self.state else {
+                let State::Ready { screen, config } = &mut self.state else {
                     return Task::none();
                 };
 
-                let (update_applications, task) = screen::UpdateApplications::new();
-                *screen = Screen::UpdateApplications(update_applications);
+                let (update_applications, task) = screen::UpdateApplications::new(
+                    config.ignored_updates.clone(),
+                    config.default_installation,
+                );
+                **screen = Screen::UpdateApplications(update_applications);
                 return task.map(Message::UpdateApplications);
             }
 
+            Message::InstalledFlatpaks(message) => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let Screen::InstalledFlatpaks(installed_flatpaks) = screen.as_mut() else {
+                    return Task::none();
+                };
+
+                return match installed_flatpaks.update(message, self.now) {
+                    installed_flatpaks::Action::None => Task::none(),
+                    installed_flatpaks::Action::Run(task) => task.map(Message::InstalledFlatpaks),
+                    installed_flatpaks::Action::Back => {
+                        **screen = Screen::Welcome;
+                        return Task::none();
+                    }
+                    installed_flatpaks::Action::AddToast(toast) => {
+                        return self.update(Message::AddToast(toast), now);
+                    }
+                    installed_flatpaks::Action::AddToastAndRun((toast, task)) => {
+                        self.toasts.push(toast);
+                        task.map(Message::InstalledFlatpaks)
+                    }
+                };
+            }
+            Message::OpenInstalledFlatpaks => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let (installed_flatpaks, task) = screen::InstalledFlatpaks::new();
+                **screen = Screen::InstalledFlatpaks(installed_flatpaks);
+                return task.map(Message::InstalledFlatpaks);
+            }
+
+            Message::FlatpakRemotes(message) => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let Screen::FlatpakRemotes(flatpak_remotes) = screen.as_mut() else {
+                    return Task::none();
+                };
+
+                return match flatpak_remotes.update(message, self.now) {
+                    flatpak_remotes::Action::None => Task::none(),
+                    flatpak_remotes::Action::Run(task) => task.map(Message::FlatpakRemotes),
+                    flatpak_remotes::Action::Back => {
+                        **screen = Screen::Welcome;
+                        return Task::none();
+                    }
+                    flatpak_remotes::Action::AddToast(toast) => {
+                        return self.update(Message::AddToast(toast), now);
+                    }
+                    flatpak_remotes::Action::AddToastAndRun((toast, task)) => {
+                        self.toasts.push(toast);
+                        task.map(Message::FlatpakRemotes)
+                    }
+                };
+            }
+            Message::OpenFlatpakRemotes => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let (flatpak_remotes, task) = screen::FlatpakRemotes::new();
+                **screen = Screen::FlatpakRemotes(flatpak_remotes);
+                return task.map(Message::FlatpakRemotes);
+            }
+
+            Message::Containers(message) => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let Screen::Containers(containers) = screen.as_mut() else {
+                    return Task::none();
+                };
+
+                return match containers.update(message, self.now) {
+                    containers::Action::None => Task::none(),
+                    containers::Action::Run(task) => task.map(Message::Containers),
+                    containers::Action::Back => {
+                        **screen = Screen::Welcome;
+                        return Task::none();
+                    }
+                    containers::Action::AddToast(toast) => {
+                        return self.update(Message::AddToast(toast), now);
+                    }
+                    containers::Action::AddToastAndRun((toast, task)) => {
+                        self.toasts.push(toast);
+                        task.map(Message::Containers)
+                    }
+                };
+            }
+            Message::OpenContainers => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let (containers, task) = screen::Containers::new();
+                **screen = Screen::Containers(containers);
+                return task.map(Message::Containers);
+            }
+
+            Message::Firmware(message) => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let Screen::Firmware(firmware) = screen.as_mut() else {
+                    return Task::none();
+                };
+
+                return match firmware.update(message, self.now) {
+                    firmware::Action::None => Task::none(),
+                    firmware::Action::Run(task) => task.map(Message::Firmware),
+                    firmware::Action::Back => {
+                        **screen = Screen::Welcome;
+                        return Task::none();
+                    }
+                    firmware::Action::AddToast(toast) => {
+                        return self.update(Message::AddToast(toast), now);
+                    }
+                    firmware::Action::AddToastAndRun((toast, task)) => {
+                        self.toasts.push(toast);
+                        task.map(Message::Firmware)
+                    }
+                };
+            }
+            Message::OpenFirmware => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let (firmware, task) = screen::Firmware::new();
+                **screen = Screen::Firmware(firmware);
+                return task.map(Message::Firmware);
+            }
+
             Message::Rollback(message) => {
                 let State::Ready { screen, .. } = &mut self.state else {
                     return Task::none();
                 };
 
-                let Screen::Rollback(rollback) = screen else {
+                let Screen::Rollback(rollback) = screen.as_mut() else {
                     return Task::none();
                 };
 
@@ -206,7 +426,7 @@ impl FAManagement {
                     rollback::Action::None => Task::none(),
                     rollback::Action::Run(task) => task.map(Message::Rollback),
                     rollback::Action::Back => {
-                        *screen = Screen::Welcome;
+                        **screen = Screen::Welcome;
                         return Task::none();
                     }
                     rollback::Action::AddToast(toast) => {
@@ -224,16 +444,16 @@ impl FAManagement {
                 };
 
                 let (rollback, task) = screen::Rollback::new();
-                *screen = Screen::Rollback(rollback);
+                **screen = Screen::Rollback(rollback);
                 return task.map(Message::Rollback);
             }
 
             Message::SystemStatus(message) => {
-                let State::Ready { screen, .. } = &mut self.state else {
+                let State::Ready { screen, config, .. } = &mut self.state else {
                     return Task::none();
                 };
 
-                let Screen::SystemStatus(system_status) = screen else {
+                let Screen::SystemStatus(system_status) = screen.as_mut() else {
                     return Task::none();
                 };
 
@@ -241,7 +461,7 @@ impl FAManagement {
                     system_status::Action::None => Task::none(),
                     system_status::Action::Run(task) => task.map(Message::SystemStatus),
                     system_status::Action::Back => {
-                        *screen = Screen::Welcome;
+                        **screen = Screen::Welcome;
                         return Task::none();
                     }
                     system_status::Action::AddToast(toast) => {
@@ -251,24 +471,78 @@ impl FAManagement {
                         self.toasts.push(toast);
                         task.map(Message::SystemStatus)
                     }
+                    system_status::Action::UpdateDeploymentNotes(deployment_notes) => {
+                        config.deployment_notes = deployment_notes;
+                        let new_config = config.clone();
+                        Task::perform(new_config.save(APP_ID), Message::ConfigSaved)
+                    }
                 };
             }
             Message::OpenSystemStatus => {
-                let State::Ready { screen, .. } = &mut self.state else {
+                let State::Ready { screen, config, .. } = &mut self.state else {
                     return Task::none();
                 };
 
-                let (system_status, task) = screen::SystemStatus::new();
-                *screen = Screen::SystemStatus(system_status);
+                let (system_status, task) =
+                    screen::SystemStatus::new(config.deployment_notes.clone());
+                **screen = Screen::SystemStatus(system_status);
                 return task.map(Message::SystemStatus);
             }
 
+            Message::UpdateHistory(message) => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let Screen::UpdateHistory(update_history) = screen.as_mut() else {
+                    return Task::none();
+                };
+
+                return match update_history.update(message, self.now) {
+                    update_history::Action::None => Task::none(),
+                    update_history::Action::Run(task) => task.map(Message::UpdateHistory),
+                    update_history::Action::Back => {
+                        **screen = Screen::Welcome;
+                        return Task::none();
+                    }
+                    update_history::Action::AddToast(toast) => {
+                        return self.update(Message::AddToast(toast), now);
+                    }
+                };
+            }
+            Message::OpenUpdateHistory => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let (update_history, task) = screen::UpdateHistory::new();
+                **screen = Screen::UpdateHistory(update_history);
+                return task.map(Message::UpdateHistory);
+            }
+
+            Message::PostUpdateSummary(message) => {
+                let State::Ready { screen, .. } = &mut self.state else {
+                    return Task::none();
+                };
+
+                let Screen::PostUpdateSummary(post_update_summary) = screen.as_mut() else {
+                    return Task::none();
+                };
+
+                match post_update_summary.update(message, self.now) {
+                    post_update_summary::Action::Back => {
+                        **screen = Screen::Welcome;
+                        return Task::none();
+                    }
+                }
+            }
+
             Message::Config(message) => {
                 let State::Ready { screen, config, .. } = &mut self.state else {
                     return Task::none();
                 };
 
-                let Screen::Config(config_screen) = screen else {
+                let Screen::Config(config_screen) = screen.as_mut() else {
                     return Task::none();
                 };
 
@@ -278,8 +552,13 @@ impl FAManagement {
                         let new_config = config.clone();
                         Task::perform(new_config.save(APP_ID), Message::ConfigSaved)
                     }
+                    config::Action::ChangedDefaultInstallation(installation) => {
+                        config.default_installation = installation;
+                        let new_config = config.clone();
+                        Task::perform(new_config.save(APP_ID), Message::ConfigSaved)
+                    }
                     config::Action::Back => {
-                        *screen = Screen::Welcome;
+                        **screen = Screen::Welcome;
                         return Task::none();
                     }
                 };
@@ -290,7 +569,7 @@ impl FAManagement {
                 };
 
                 let (config, task) = screen::Config::new(config.clone());
-                *screen = Screen::Config(config);
+                **screen = Screen::Config(config);
                 return task.map(Message::Config);
             }
             Message::About(message) => {
@@ -298,13 +577,13 @@ impl FAManagement {
                     return Task::none();
                 };
 
-                let Screen::About(about_screen) = screen else {
+                let Screen::About(about_screen) = screen.as_mut() else {
                     return Task::none();
                 };
 
                 match about_screen.update(message, self.now) {
                     about::Action::Back => {
-                        *screen = Screen::Welcome;
+                        **screen = Screen::Welcome;
                         return Task::none();
                     }
                     about::Action::None => return Task::none(),
@@ -316,7 +595,7 @@ impl FAManagement {
                 };
 
                 let (about, task) = screen::About::new();
-                *screen = Screen::About(about);
+                **screen = Screen::About(about);
                 return task.map(Message::About);
             }
 
@@ -336,7 +615,7 @@ impl FAManagement {
             return Subscription::none();
         };
 
-        match screen {
+        match screen.as_ref() {
             Screen::Welcome => Subscription::none(),
             Screen::UpdateSystem(update_system) => update_system
                 .subscription(self.now)
@@ -344,10 +623,26 @@ impl FAManagement {
             Screen::UpdateApplications(update_applications) => update_applications
                 .subscription(self.now)
                 .map(Message::UpdateApplications),
+            Screen::InstalledFlatpaks(installed_flatpaks) => installed_flatpaks
+                .subscription(self.now)
+                .map(Message::InstalledFlatpaks),
+            Screen::FlatpakRemotes(flatpak_remotes) => flatpak_remotes
+                .subscription(self.now)
+                .map(Message::FlatpakRemotes),
+            Screen::Containers(containers) => {
+                containers.subscription(self.now).map(Message::Containers)
+            }
+            Screen::Firmware(firmware) => firmware.subscription(self.now).map(Message::Firmware),
             Screen::Rollback(rollback) => rollback.subscription(self.now).map(Message::Rollback),
             Screen::SystemStatus(system_status) => system_status
                 .subscription(self.now)
                 .map(Message::SystemStatus),
+            Screen::UpdateHistory(update_history) => update_history
+                .subscription(self.now)
+                .map(Message::UpdateHistory),
+            Screen::PostUpdateSummary(post_update_summary) => post_update_summary
+                .subscription(self.now)
+                .map(Message::PostUpdateSummary),
             Screen::Config(config) => config.subscription(self.now).map(Message::Config),
             Screen::About(about) => about.subscription(self.now).map(Message::About),
         }
@@ -395,6 +690,50 @@ impl FAManagement {
                 AccordionButtonStatus::Enabled,
                 false,
             ),
+            accordion_button(
+                AccordionButtonPosition::Middle,
+                fl!("installed-flatpaks"),
+                fl!("installed-flatpaks-description"),
+                AccordionIcon::BundledSvg {
+                    svg: PossibleBundledSVGs::InstalledFlatpaks
+                },
+                Message::OpenInstalledFlatpaks,
+                AccordionButtonStatus::Enabled,
+                false,
+            ),
+            accordion_button(
+                AccordionButtonPosition::Middle,
+                fl!("flatpak-remotes"),
+                fl!("flatpak-remotes-description"),
+                AccordionIcon::BundledSvg {
+                    svg: PossibleBundledSVGs::FlatpakRemotes
+                },
+                Message::OpenFlatpakRemotes,
+                AccordionButtonStatus::Enabled,
+                false,
+            ),
+            accordion_button(
+                AccordionButtonPosition::Middle,
+                fl!("containers"),
+                fl!("containers-description"),
+                AccordionIcon::BundledSvg {
+                    svg: PossibleBundledSVGs::Containers
+                },
+                Message::OpenContainers,
+                AccordionButtonStatus::Enabled,
+                false,
+            ),
+            accordion_button(
+                AccordionButtonPosition::Middle,
+                fl!("firmware"),
+                fl!("firmware-description"),
+                AccordionIcon::BundledSvg {
+                    svg: PossibleBundledSVGs::Firmware
+                },
+                Message::OpenFirmware,
+                AccordionButtonStatus::Enabled,
+                false,
+            ),
             accordion_button(
                 AccordionButtonPosition::Middle,
                 fl!("rollback"),
@@ -407,7 +746,7 @@ impl FAManagement {
                 false,
             ),
             accordion_button(
-                AccordionButtonPosition::Bottom,
+                AccordionButtonPosition::Middle,
                 fl!("system-status"),
                 fl!("system-status-description"),
                 AccordionIcon::BundledSvg {
@@ -416,6 +755,17 @@ impl FAManagement {
                 Message::OpenSystemStatus,
                 AccordionButtonStatus::Enabled,
                 false,
+            ),
+            accordion_button(
+                AccordionButtonPosition::Bottom,
+                fl!("update-history"),
+                fl!("update-history-description"),
+                AccordionIcon::BundledSvg {
+                    svg: PossibleBundledSVGs::UpdateHistory
+                },
+                Message::OpenUpdateHistory,
+                AccordionButtonStatus::Enabled,
+                false,
             )
         ]
         .spacing(0.)
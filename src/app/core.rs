@@ -2,12 +2,27 @@
 
 use anywho::anywho;
 
+pub mod appstream;
 pub mod config;
+pub mod config_drift;
+pub mod containers;
+pub mod disk_usage;
+pub mod end_of_life;
+pub mod firmware;
+pub mod flatpak_commits;
+pub mod flatpak_permissions;
+pub mod flatpak_remotes;
+pub mod flatpak_repair;
+pub mod flatpak_search;
+pub mod gpg_signature;
+pub mod history;
+pub mod installed_flatpaks;
 pub mod layered_packages;
 pub mod rollback;
 pub mod system_status;
 pub mod update_applications;
 pub mod update_system;
+pub mod what_changed;
 
 /// Runs the given command correctly for each possible context (Flatpak/Distrobox/System)
 pub async fn run_command(
@@ -16,25 +31,86 @@ pub async fn run_command(
 ) -> Result<std::process::Output, std::io::Error> {
     use tokio::process::Command;
 
+    let (program, args) = command_line(main_command, args);
+    Command::new(program).args(args).output().await
+}
+
+/// Spawns the given command correctly for each possible context (Flatpak/Distrobox/System)
+/// with piped stdout and stderr, so its output can be read while it runs
+pub fn spawn_command(
+    main_command: &str,
+    args: &[&str],
+) -> Result<tokio::process::Child, std::io::Error> {
+    use std::process::Stdio;
+    use tokio::process::Command;
+
+    let (program, args) = command_line(main_command, args);
+    Command::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+}
+
+/// Reads the stdout and stderr of a spawned command at the same time, yielding every segment of output.
+/// Progress is redrawn with \r, so both \r and \n end a segment
+pub fn read_output_segments(
+    child: &mut tokio::process::Child,
+) -> impl iced::futures::Stream<Item = String> + Send + use<> {
+    use iced::futures::stream::{self, StreamExt};
+
+    let stdout = stream::iter(child.stdout.take().map(output_segments)).flatten();
+    let stderr = stream::iter(child.stderr.take().map(output_segments)).flatten();
+
+    stream::select(stdout, stderr)
+}
+
+fn output_segments(
+    reader: impl tokio::io::AsyncRead + Unpin + Send + 'static,
+) -> impl iced::futures::Stream<Item = String> + Send + 'static {
+    use iced::futures::stream::{self, StreamExt};
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    stream::unfold(
+        BufReader::new(reader).split(b'\r'),
+        |mut segments| async move {
+            let segment = segments.next_segment().await.ok().flatten()?;
+            Some((segment, segments))
+        },
+    )
+    .flat_map(|segment| {
+        let lines: Vec<String> = String::from_utf8_lossy(&segment)
+            .split('\n')
+            .map(str::to_string)
+            .collect();
+        stream::iter(lines)
+    })
+}
+
+/// Returns the program and arguments needed to run the given command on the host
+fn command_line<'a>(main_command: &'a str, args: &[&'a str]) -> (&'a str, Vec<&'a str>) {
     if is_flatpak() {
         // If is flatpak we need to add flatpak-spawn --host
-        Command::new("flatpak-spawn")
-            .args(
-                vec!["--host", main_command]
-                    .into_iter()
-                    .chain(args.iter().cloned()),
-            )
-            .output()
-            .await
+        (
+            "flatpak-spawn",
+            vec!["--host", main_command]
+                .into_iter()
+                .chain(args.iter().cloned())
+                .collect(),
+        )
     } else if is_running_in_distrobox() {
         // If is distrobox we need to add distrobox-host-exec
-        Command::new("distrobox-host-exec")
-            .args(vec![main_command].into_iter().chain(args.iter().cloned()))
-            .output()
-            .await
+        (
+            "distrobox-host-exec",
+            vec![main_command]
+                .into_iter()
+                .chain(args.iter().cloned())
+                .collect(),
+        )
     } else {
         // Add nothing (Ej: main_command: "pkexec", args: ["rpm-ostree", "rollback"])
-        Command::new(main_command).args(args).output().await
+        (main_command, args.to_vec())
     }
 }
 
@@ -0,0 +1,765 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::reader::Reader;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::app::core::run_command;
+use crate::app::core::update_applications::Installation;
+use crate::app::utils::date::format_date;
+
+const CACHE_VERSION: i32 = 1;
+
+/// Release of an application as described on its appstream metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Release {
+    pub version: String,
+    /// Date of the release (Ej: 2025-05-01), may be empty
+    pub date: String,
+    /// Paragraphs and list items of the release description
+    pub notes: Vec<String>,
+}
+
+/// Name, summary, icon and releases of an application as described on its appstream metadata
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AppMetadata {
+    pub name: String,
+    pub summary: String,
+    /// Copy of the icon of the application in our cache directory, so it can be read from the sandbox
+    pub icon: Option<PathBuf>,
+    /// Every release of the application, newest first
+    pub releases: Vec<Release>,
+}
+
+/// Metadata of the applications read from the appstream caches, kept on disk
+/// so each appstream cache is only parsed again after flatpak refreshes it
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MetadataCache {
+    /// Keyed by the appstream cache it was read from (Ej: system/flathub/x86_64)
+    sources: HashMap<String, CachedSource>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CachedSource {
+    /// Commit the active appstream of the remote points to, changes every time flatpak refreshes it
+    revision: String,
+    /// Keyed by application ID
+    apps: HashMap<String, AppMetadata>,
+}
+
+impl MetadataCache {
+    /// Loads the cache from disk, starting from an empty one if it can't be read
+    pub async fn load(app_id: &str) -> Self {
+        let app_id = app_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let content = std::fs::read_to_string(cache_path(&app_id).ok()?).ok()?;
+            ron::from_str(&content).ok()
+        })
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+    }
+
+    pub async fn save(self, app_id: &str) -> Result<(), anywho::Error> {
+        let app_id = app_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let content = ron::to_string(&self)
+                .map_err(|e| anywho!("Failed to serialize the appstream cache: {}", e))?;
+
+            std::fs::write(cache_path(&app_id)?, content)
+                .map_err(|e| anywho!("Failed to write the appstream cache: {}", e))
+        })
+        .await
+        .map_err(|e| anywho!("Task join error: {}", e))?
+    }
+
+    /// Returns the cached metadata of an application if the appstream cache didn't change since
+    pub fn get(&self, source: &str, revision: &str, app_id: &str) -> Option<&AppMetadata> {
+        // without a revision there's no way to tell if the cached metadata is outdated
+        if revision.is_empty() {
+            return None;
+        }
+
+        self.sources
+            .get(source)
+            .filter(|cached| cached.revision == revision)
+            .and_then(|cached| cached.apps.get(app_id))
+    }
+
+    /// Stores the metadata of an application, dropping the outdated ones of the same appstream cache
+    pub fn insert(&mut self, source: &str, revision: &str, app_id: String, metadata: AppMetadata) {
+        let cached = self.sources.entry(source.to_string()).or_default();
+        if cached.revision != revision {
+            cached.revision = revision.to_string();
+            cached.apps.clear();
+        }
+
+        cached.apps.insert(app_id, metadata);
+    }
+}
+
+/// Key of the appstream cache of a remote on the metadata cache (Ej: system/flathub/x86_64)
+pub fn source_key(installation: Installation, origin: &str, arch: &str) -> String {
+    format!("{}/{}/{}", installation, origin, arch)
+}
+
+/// Returns the appstream XML of the given remote from the local flatpak appstream cache
+pub async fn load(
+    installation: Installation,
+    origin: &str,
+    arch: &str,
+) -> Result<String, anywho::Error> {
+    let active_dir = active_dir(installation, origin, arch);
+
+    // the cache lives on the host, so we read it through run_command
+    let output = run_command(
+        "gzip",
+        &[
+            "-dc",
+            &active_dir.join("appstream.xml.gz").to_string_lossy(),
+        ],
+    )
+    .await?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+    }
+
+    // older flatpak versions only keep the uncompressed file
+    let output = run_command(
+        "cat",
+        &[&active_dir.join("appstream.xml").to_string_lossy()],
+    )
+    .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!(
+            "Could not read the {} appstream cache: {}",
+            origin,
+            stderr.trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Returns the commit the active appstream cache of the given remote points to, empty if unknown
+pub async fn revision(installation: Installation, origin: &str, arch: &str) -> String {
+    let active_dir = active_dir(installation, origin, arch);
+
+    match run_command("readlink", &[&active_dir.to_string_lossy()]).await {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Copies the icon of an application to our cache directory and returns the copy,
+/// prefers the icons exported by the installed application over the appstream cached icon
+pub async fn cache_icon(
+    app_id: &str,
+    installation: Installation,
+    origin: &str,
+    arch: &str,
+    application_id: &str,
+    cached_icon: Option<String>,
+) -> Option<PathBuf> {
+    let exported = installation.path().join("exports/share/icons/hicolor");
+    let mut candidates = vec![
+        exported.join(format!("scalable/apps/{}.svg", application_id)),
+        exported.join(format!("128x128/apps/{}.png", application_id)),
+        exported.join(format!("64x64/apps/{}.png", application_id)),
+    ];
+    if let Some(cached_icon) = cached_icon {
+        candidates.push(
+            active_dir(installation, origin, arch)
+                .join("icons")
+                .join(cached_icon),
+        );
+    }
+
+    copy_first_icon(
+        app_id,
+        &format!("{}-{}", installation, application_id),
+        candidates,
+    )
+    .await
+}
+
+/// Copies the icon flatpak downloaded with the appstream data of the given remote to our cache directory,
+/// for applications that may not be installed, and returns the copy
+pub async fn cache_remote_icon(
+    app_id: &str,
+    remote: &str,
+    application_id: &str,
+) -> Option<PathBuf> {
+    let candidates = Installation::ALL
+        .into_iter()
+        .flat_map(|installation| {
+            ["128x128", "64x64"].map(|size| {
+                active_dir(installation, remote, std::env::consts::ARCH)
+                    .join("icons")
+                    .join(size)
+                    .join(format!("{}.png", application_id))
+            })
+        })
+        .collect();
+
+    copy_first_icon(
+        app_id,
+        &format!("{}-{}", remote, application_id),
+        candidates,
+    )
+    .await
+}
+
+/// Copies the first of the candidates that exists on the host to our icons cache directory,
+/// named after the given stem
+async fn copy_first_icon(app_id: &str, stem: &str, candidates: Vec<PathBuf>) -> Option<PathBuf> {
+    let icons_dir = cache_path(app_id).ok()?.parent()?.join("icons");
+    std::fs::create_dir_all(&icons_dir).ok()?;
+
+    for candidate in candidates {
+        let extension = candidate.extension()?.to_string_lossy().to_string();
+        let destination = icons_dir.join(format!("{}.{}", stem, extension));
+
+        // the icons live on the host, the cache directory is the same path inside and outside the sandbox
+        let copied = run_command(
+            "cp",
+            &[&candidate.to_string_lossy(), &destination.to_string_lossy()],
+        )
+        .await
+        .is_ok_and(|output| output.status.success());
+
+        if copied {
+            return Some(destination);
+        }
+    }
+
+    None
+}
+
+/// Reads the metadata and cached icon of the given applications from an appstream XML
+/// on a blocking thread, see [`parse_components`]
+pub async fn read_components(
+    appstream: String,
+    app_ids: Vec<String>,
+) -> HashMap<String, (AppMetadata, Option<String>)> {
+    tokio::task::spawn_blocking(move || parse_components(&appstream, &app_ids))
+        .await
+        .unwrap_or_default()
+}
+
+/// Parses the appstream XML once, returning the metadata (without its icon) and the cached icon
+/// of each of the given applications that is on it, keyed by application ID.
+/// Some remotes still use the .desktop suffix on the IDs, an exact match is preferred
+fn parse_components(
+    appstream: &str,
+    app_ids: &[String],
+) -> HashMap<String, (AppMetadata, Option<String>)> {
+    let wanted: HashSet<&str> = app_ids.iter().map(String::as_str).collect();
+    let mut components = HashMap::new();
+    let mut exact_matches = HashSet::new();
+    let mut reader = Reader::from_str(appstream);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) if start.name().as_ref() == b"component" => {
+                let component = read_element(&mut reader, Element::from_start(&start));
+                let id = component
+                    .child("id")
+                    .map(|id| id.text().trim().to_string())
+                    .unwrap_or_default();
+
+                let (app_id, is_exact) = if wanted.contains(id.as_str()) {
+                    (id, true)
+                } else {
+                    match id.strip_suffix(".desktop") {
+                        Some(app_id) if wanted.contains(app_id) => (app_id.to_string(), false),
+                        _ => continue,
+                    }
+                };
+
+                // the first exact match wins, a .desktop one is only kept until there's an exact one
+                if exact_matches.contains(&app_id)
+                    || (!is_exact && components.contains_key(&app_id))
+                {
+                    continue;
+                }
+                if is_exact {
+                    exact_matches.insert(app_id.clone());
+                }
+
+                components.insert(
+                    app_id,
+                    (component_metadata(&component), cached_icon(&component)),
+                );
+            }
+            Ok(Event::Eof) | Err(_) => return components,
+            _ => {}
+        }
+    }
+}
+
+/// Returns the name, summary and releases of a component, without its icon
+fn component_metadata(component: &Element) -> AppMetadata {
+    AppMetadata {
+        name: component
+            .localized_child("name")
+            .map(|name| name.text().trim().to_string())
+            .unwrap_or_default(),
+        summary: component
+            .localized_child("summary")
+            .map(|summary| summary.text().trim().to_string())
+            .unwrap_or_default(),
+        icon: None,
+        releases: component
+            .child("releases")
+            .map(parse_releases)
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns the biggest icon flatpak downloaded with the appstream data of a component,
+/// relative to the icons directory of the appstream cache (Ej: 128x128/org.gnome.Calculator.png)
+fn cached_icon(component: &Element) -> Option<String> {
+    component
+        .children("icon")
+        .filter(|icon| icon.attribute("type") == Some("cached"))
+        .filter_map(|icon| {
+            let file = icon.text().trim().to_string();
+            if file.is_empty() {
+                return None;
+            }
+
+            let width = icon.attribute("width").unwrap_or("64");
+            let height = icon.attribute("height").unwrap_or("64");
+            let size = width.parse::<u32>().unwrap_or_default();
+
+            Some((size, format!("{}x{}/{}", width, height, file)))
+        })
+        // keeps the first of the biggest icons
+        .rev()
+        .max_by_key(|(size, _)| *size)
+        .map(|(_, icon)| icon)
+}
+
+/// Returns the releases newer than the installed version, newest first.
+/// If the installed version isn't listed we can't tell which ones are newer, so only the latest is returned
+pub fn newer_releases(releases: &[Release], current_version: &str) -> Vec<Release> {
+    match releases
+        .iter()
+        .position(|release| release.version == current_version)
+    {
+        Some(position) => releases[..position].to_vec(),
+        None => releases.iter().take(1).cloned().collect(),
+    }
+}
+
+/// Parses the release elements of a releases element, keeping their order
+fn parse_releases(releases: &Element) -> Vec<Release> {
+    releases
+        .children("release")
+        .map(|release| {
+            let date = match (release.attribute("date"), release.attribute("timestamp")) {
+                (Some(date), _) => date.chars().take(10).collect(),
+                (None, Some(timestamp)) => timestamp
+                    .parse::<u64>()
+                    .map(format_date)
+                    .unwrap_or_default(),
+                (None, None) => String::new(),
+            };
+
+            Release {
+                version: release.attribute("version").unwrap_or_default().to_string(),
+                date,
+                notes: release
+                    .child("description")
+                    .map(|description| markup_lines(&description.nodes))
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Directory of the active appstream cache of a remote (Ej: /var/lib/flatpak/appstream/flathub/x86_64/active)
+fn active_dir(installation: Installation, origin: &str, arch: &str) -> PathBuf {
+    installation
+        .path()
+        .join("appstream")
+        .join(origin)
+        .join(arch)
+        .join("active")
+}
+
+fn cache_path(app_id: &str) -> Result<PathBuf, anywho::Error> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anywho!("Could not determine cache directory"))?
+        .join(app_id);
+
+    if !cache_dir.exists() {
+        std::fs::create_dir_all(&cache_dir)
+            .map_err(|e| anywho!("Failed to create cache directory: {}", e))?;
+    }
+
+    Ok(cache_dir.join(format!("appstream_v{}.ron", CACHE_VERSION)))
+}
+
+/// Element of an appstream document, with its children in document order
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Element(Element),
+    Text(String),
+}
+
+impl Element {
+    fn from_start(start: &BytesStart) -> Self {
+        Element {
+            name: String::from_utf8_lossy(start.name().as_ref()).to_string(),
+            attributes: start
+                .attributes()
+                .filter_map(Result::ok)
+                .map(|attribute| {
+                    let value = attribute
+                        .unescape_value()
+                        .map(|value| value.to_string())
+                        .unwrap_or_else(|_| String::from_utf8_lossy(&attribute.value).to_string());
+
+                    (
+                        String::from_utf8_lossy(attribute.key.as_ref()).to_string(),
+                        value,
+                    )
+                })
+                .collect(),
+            nodes: Vec::new(),
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn children<'a>(&'a self, name: &str) -> impl DoubleEndedIterator<Item = &'a Element> {
+        self.nodes.iter().filter_map(move |node| match node {
+            Node::Element(element) if element.name == name => Some(element),
+            _ => None,
+        })
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children(name).next()
+    }
+
+    /// Returns the first child with the given name that isn't a translation
+    fn localized_child(&self, name: &str) -> Option<&Element> {
+        self.children(name)
+            .find(|element| !element.is_translation())
+    }
+
+    /// Translated elements have a xml:lang attribute other than the default one
+    fn is_translation(&self) -> bool {
+        self.attribute("xml:lang")
+            .is_some_and(|lang| !matches!(lang, "C" | "en"))
+    }
+
+    /// Returns the text of the element and its descendants
+    fn text(&self) -> String {
+        self.nodes
+            .iter()
+            .map(|node| match node {
+                Node::Element(element) => element.text(),
+                Node::Text(text) => text.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Reads the children of an element until its closing tag
+fn read_element(reader: &mut Reader<&[u8]>, mut element: Element) -> Element {
+    element.nodes = read_nodes(reader);
+    element
+}
+
+/// Reads elements and text until the closing tag of the current element or the end of the document
+fn read_nodes(reader: &mut Reader<&[u8]>) -> Vec<Node> {
+    let mut nodes = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(start)) => {
+                let element = read_element(reader, Element::from_start(&start));
+                nodes.push(Node::Element(element));
+            }
+            Ok(Event::Empty(start)) => nodes.push(Node::Element(Element::from_start(&start))),
+            Ok(Event::Text(text)) => {
+                let text = text
+                    .unescape()
+                    .map(|text| text.to_string())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&text).to_string());
+                nodes.push(Node::Text(text));
+            }
+            Ok(Event::CData(data)) => {
+                nodes.push(Node::Text(String::from_utf8_lossy(&data).to_string()));
+            }
+            Ok(Event::End(_)) | Ok(Event::Eof) | Err(_) => return nodes,
+            _ => {}
+        }
+    }
+}
+
+/// Converts the markup of an appstream description into lines, list items are prefixed with •
+pub fn description_lines(description: &str) -> Vec<String> {
+    let mut reader = Reader::from_str(description);
+
+    markup_lines(&read_nodes(&mut reader))
+}
+
+fn markup_lines(nodes: &[Node]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for node in nodes {
+        let Node::Element(element) = node else {
+            continue;
+        };
+        if element.is_translation() {
+            continue;
+        }
+
+        let prefix = match element.name.as_str() {
+            "p" => "",
+            "li" => "• ",
+            _ => {
+                lines.extend(markup_lines(&element.nodes));
+                continue;
+            }
+        };
+
+        let line = element
+            .text()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        if !line.is_empty() {
+            lines.push(format!("{}{}", prefix, line));
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_metadata(appstream: &str, app_id: &str) -> Option<AppMetadata> {
+        parse_components(appstream, &[app_id.to_string()])
+            .remove(app_id)
+            .map(|(metadata, _)| metadata)
+    }
+
+    fn release_notes(appstream: &str, app_id: &str, current_version: &str) -> Vec<Release> {
+        app_metadata(appstream, app_id)
+            .map(|metadata| newer_releases(&metadata.releases, current_version))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn parses_release_notes_newer_than_installed() {
+        let releases = release_notes(
+            include_str!("fixtures/appstream.xml"),
+            "org.gnome.Calculator",
+            "47.1",
+        );
+
+        assert_eq!(
+            releases,
+            vec![
+                Release {
+                    version: "48.1".to_string(),
+                    date: "2025-04-12".to_string(),
+                    notes: vec![
+                        "Bug fixes & translations".to_string(),
+                        "• Fixed the currency conversion".to_string(),
+                        "• Updated <b>translations</b>".to_string(),
+                    ],
+                },
+                Release {
+                    version: "48.0".to_string(),
+                    date: "2025-03-15".to_string(),
+                    notes: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn only_returns_the_latest_release_for_unknown_versions() {
+        let releases = release_notes(
+            include_str!("fixtures/appstream.xml"),
+            "org.gnome.Calculator",
+            "46.0-custom",
+        );
+
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].version, "48.1");
+    }
+
+    #[test]
+    fn finds_desktop_suffixed_components() {
+        let releases = release_notes(
+            include_str!("fixtures/appstream.xml"),
+            "org.mozilla.firefox",
+            "",
+        );
+
+        assert_eq!(releases.len(), 1);
+        assert_eq!(releases[0].version, "139.0.4");
+        assert_eq!(releases[0].notes, vec!["Stability fixes".to_string()]);
+    }
+
+    #[test]
+    fn parses_translations_entities_and_cdata() {
+        let metadata = app_metadata(
+            include_str!("fixtures/appstream.xml"),
+            "io.gitlab.librewolf-community",
+        )
+        .unwrap();
+
+        assert_eq!(metadata.name, "LibreWolf");
+        assert_eq!(metadata.summary, "Privacy & security focused browser");
+        assert_eq!(
+            metadata.releases,
+            vec![Release {
+                version: "139.0.4-1".to_string(),
+                date: "2025-06-11".to_string(),
+                notes: vec!["Based on Firefox 139".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_components() {
+        assert!(
+            release_notes(
+                include_str!("fixtures/appstream.xml"),
+                "org.kde.kdenlive",
+                ""
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn parses_app_metadata() {
+        let metadata = app_metadata(
+            include_str!("fixtures/appstream.xml"),
+            "org.gnome.Calculator",
+        )
+        .unwrap();
+
+        assert_eq!(metadata.name, "Calculator");
+        assert_eq!(
+            metadata.summary,
+            "Perform arithmetic, scientific or financial calculations"
+        );
+        assert_eq!(metadata.releases.len(), 3);
+
+        let firefox = app_metadata(
+            include_str!("fixtures/appstream.xml"),
+            "org.mozilla.firefox",
+        )
+        .unwrap();
+        assert_eq!(firefox.summary, "Fast, Private & Safe Web Browser");
+    }
+
+    #[test]
+    fn parses_every_requested_component_at_once() {
+        let app_ids = [
+            "org.gnome.Calculator",
+            "org.mozilla.firefox",
+            "org.kde.kdenlive",
+        ]
+        .map(String::from);
+        let components = parse_components(include_str!("fixtures/appstream.xml"), &app_ids);
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(components["org.gnome.Calculator"].0.name, "Calculator");
+        assert_eq!(
+            components["org.mozilla.firefox"].0.summary,
+            "Fast, Private & Safe Web Browser"
+        );
+    }
+
+    #[test]
+    fn picks_the_biggest_cached_icon() {
+        let app_ids = [
+            "org.gnome.Calculator",
+            "org.mozilla.firefox",
+            "org.kde.kdenlive",
+        ]
+        .map(String::from);
+        let components = parse_components(include_str!("fixtures/appstream.xml"), &app_ids);
+
+        assert_eq!(
+            components["org.gnome.Calculator"].1.as_deref(),
+            Some("128x128/org.gnome.Calculator.png")
+        );
+        assert_eq!(
+            components["org.mozilla.firefox"].1.as_deref(),
+            Some("64x64/org.mozilla.firefox.png")
+        );
+        assert!(!components.contains_key("org.kde.kdenlive"));
+    }
+
+    #[test]
+    fn caches_metadata_per_revision() {
+        let mut cache = MetadataCache::default();
+        let metadata = AppMetadata {
+            name: "Calculator".to_string(),
+            ..Default::default()
+        };
+
+        cache.insert(
+            "system/flathub/x86_64",
+            "abc",
+            "org.gnome.Calculator".into(),
+            metadata,
+        );
+        assert!(
+            cache
+                .get("system/flathub/x86_64", "abc", "org.gnome.Calculator")
+                .is_some()
+        );
+
+        // a refreshed appstream cache makes the stored metadata outdated
+        assert!(
+            cache
+                .get("system/flathub/x86_64", "def", "org.gnome.Calculator")
+                .is_none()
+        );
+        assert!(
+            cache
+                .get("system/flathub/x86_64", "", "org.gnome.Calculator")
+                .is_none()
+        );
+    }
+}
@@ -1,12 +1,41 @@
 use anywho::anywho;
 use iced::Theme;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::app::core::update_applications::Installation;
 
 const CONFIG_VERSION: i32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     pub theme: ApplicationTheme,
+    /// User notes attached to deployments, keyed by the deployment checksum
+    #[serde(default)]
+    pub deployment_notes: HashMap<String, DeploymentNote>,
+    /// Checksum of the deployment that was booted the last time the app ran
+    #[serde(default)]
+    pub last_booted_checksum: Option<String>,
+    /// Application updates the user chose to skip, keyed by the installation and application ref
+    /// with the skipped version
+    #[serde(default)]
+    pub ignored_updates: HashMap<(Installation, String), String>,
+    /// Installation new applications are installed into
+    #[serde(default)]
+    pub default_installation: Installation,
+}
+
+/// A short label and a free-text note the user attached to a deployment
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct DeploymentNote {
+    pub label: String,
+    pub note: String,
+}
+
+impl DeploymentNote {
+    pub fn is_empty(&self) -> bool {
+        self.label.trim().is_empty() && self.note.trim().is_empty()
+    }
 }
 
 impl Config {
@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::run_command;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigChangeKind {
+    Modified,
+    Added,
+    Deleted,
+}
+
+/// The diff of a changed file
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileDiff {
+    /// Output of diff -u, empty when only the file metadata changed
+    Text(String),
+    /// One of the files can only be read by root
+    PermissionDenied,
+}
+
+/// A file on /etc that differs from the deployment defaults (/usr/etc)
+#[derive(Debug, Clone)]
+pub struct ConfigChange {
+    pub kind: ConfigChangeKind,
+    /// Path relative to /etc
+    pub path: String,
+}
+
+impl ConfigChange {
+    /// Get's all the locally changed files on /etc using: ostree admin config-diff
+    pub async fn get_all() -> Result<Vec<ConfigChange>, anywho::Error> {
+        let output = run_command("ostree", &["admin", "config-diff"]).await;
+
+        let output = match output {
+            Ok(output) => output,
+            Err(err) => return Err(anywho!("Error fetching /etc changes: {}", err)),
+        };
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!("Error fetching /etc changes: {}", stderr.trim()));
+        }
+
+        Ok(parse_config_diff(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Checks if this file is an .rpmnew/.rpmsave leftover that should be reviewed
+    pub fn is_rpm_leftover(&self) -> bool {
+        self.path.ends_with(".rpmnew") || self.path.ends_with(".rpmsave")
+    }
+
+    /// Checks if there is something we can show a diff for
+    pub fn has_diff(&self) -> bool {
+        self.kind == ConfigChangeKind::Modified
+            || (self.kind == ConfigChangeKind::Added && self.is_rpm_leftover())
+    }
+
+    /// Returns a text diff (diff -u) for this change, or [`FileDiff::PermissionDenied`] if
+    /// one of the files can't be read without root.
+    ///
+    /// Modified files are compared against /usr/etc and .rpmnew/.rpmsave leftovers
+    /// are compared against the file they were created for.
+    pub async fn diff(self) -> Result<FileDiff, anywho::Error> {
+        let (original, changed) = if self.is_rpm_leftover() {
+            let base_path = self
+                .path
+                .trim_end_matches(".rpmnew")
+                .trim_end_matches(".rpmsave");
+            (format!("/etc/{}", base_path), format!("/etc/{}", self.path))
+        } else {
+            (
+                format!("/usr/etc/{}", self.path),
+                format!("/etc/{}", self.path),
+            )
+        };
+
+        for path in [&original, &changed] {
+            if !is_readable(path).await? {
+                return Ok(FileDiff::PermissionDenied);
+            }
+        }
+
+        let output = run_command("diff", &["-u", &original, &changed]).await;
+
+        let output = match output {
+            Ok(output) => output,
+            Err(err) => return Err(anywho!("{}", err)),
+        };
+
+        // diff exits with 0 when the files are the same, 1 when they differ and 2 on error
+        match output.status.code() {
+            Some(0) => Ok(FileDiff::Text(String::new())),
+            Some(1) => Ok(FileDiff::Text(
+                String::from_utf8_lossy(&output.stdout).to_string(),
+            )),
+            _ => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(anywho!("Failed to diff {}: {}", self.path, stderr.trim()))
+            }
+        }
+    }
+}
+
+/// Checks if the given host file can be read by the current user
+async fn is_readable(path: &str) -> Result<bool, anywho::Error> {
+    match run_command("test", &["-r", path]).await {
+        Ok(output) => Ok(output.status.success()),
+        Err(err) => Err(anywho!("{}", err)),
+    }
+}
+
+/// Parses the output of: ostree admin config-diff
+fn parse_config_diff(output: &str) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+
+    // parse lines like: "M    selinux/targeted/active/commit_num"
+    for line in output.lines() {
+        let line = line.trim();
+
+        let Some((kind, path)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+
+        let kind = match kind {
+            "M" => ConfigChangeKind::Modified,
+            "A" => ConfigChangeKind::Added,
+            "D" => ConfigChangeKind::Deleted,
+            _ => continue,
+        };
+
+        changes.push(ConfigChange {
+            kind,
+            path: path.trim().to_string(),
+        });
+    }
+
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config_diff() {
+        let changes = parse_config_diff(include_str!("fixtures/ostree_admin_config_diff.txt"));
+
+        let summary: Vec<(ConfigChangeKind, &str)> = changes
+            .iter()
+            .map(|change| (change.kind.clone(), change.path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ConfigChangeKind::Added, "dnf/dnf.conf.rpmnew"),
+                (ConfigChangeKind::Modified, "hostname"),
+                (ConfigChangeKind::Deleted, "localtime"),
+                (
+                    ConfigChangeKind::Modified,
+                    "selinux/targeted/active/commit_num"
+                ),
+                (
+                    ConfigChangeKind::Added,
+                    "systemd/system/multi-user.target.wants/sshd.service"
+                ),
+            ]
+        );
+        assert!(changes[0].is_rpm_leftover());
+        assert!(changes[0].has_diff());
+        assert!(!changes[2].has_diff());
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::run_command;
+
+/// Images offered when creating a new container (name shown, image)
+pub const IMAGES: [(&str, &str); 7] = [
+    ("Fedora", "registry.fedoraproject.org/fedora-toolbox:latest"),
+    ("Ubuntu 24.04", "quay.io/toolbx/ubuntu-toolbox:24.04"),
+    ("Debian 12", "quay.io/toolbx-images/debian-toolbox:12"),
+    ("Arch Linux", "quay.io/toolbx/arch-toolbox:latest"),
+    (
+        "openSUSE Tumbleweed",
+        "registry.opensuse.org/opensuse/distrobox:latest",
+    ),
+    ("AlmaLinux 9", "quay.io/toolbx-images/almalinux-toolbox:9"),
+    ("Alpine", "quay.io/toolbx-images/alpine-toolbox:latest"),
+];
+
+/// Tool that created and manages a container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerManager {
+    Distrobox,
+    Toolbox,
+}
+
+impl ContainerManager {
+    pub const ALL: [ContainerManager; 2] = [ContainerManager::Distrobox, ContainerManager::Toolbox];
+
+    /// Name of the command of the tool
+    pub fn command(&self) -> &'static str {
+        match self {
+            ContainerManager::Distrobox => "distrobox",
+            ContainerManager::Toolbox => "toolbox",
+        }
+    }
+
+    /// Returns the tools that are installed on the host
+    pub async fn available() -> Vec<ContainerManager> {
+        let mut available = Vec::new();
+
+        for manager in ContainerManager::ALL {
+            let installed = run_command(manager.command(), &["--version"])
+                .await
+                .is_ok_and(|output| output.status.success());
+            if installed {
+                available.push(manager);
+            }
+        }
+
+        available
+    }
+}
+
+impl std::fmt::Display for ContainerManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContainerManager::Distrobox => write!(f, "Distrobox"),
+            ContainerManager::Toolbox => write!(f, "Toolbox"),
+        }
+    }
+}
+
+/// Distrobox or Toolbox container on the host, as listed by podman
+#[derive(Debug, Clone, PartialEq)]
+pub struct Container {
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// Ej: Up 2 hours, Exited (0) 3 days ago
+    pub status: String,
+    pub running: bool,
+    /// Size of the writable layer and of the image (Ej: 1.2GB (virtual 2.3GB))
+    pub size: String,
+    pub manager: ContainerManager,
+}
+
+impl Container {
+    /// Returns the distrobox and toolbox containers using: podman ps --all --size,
+    /// the containers of each tool are told apart by the labels they set
+    pub async fn get_all() -> Result<Vec<Container>, anywho::Error> {
+        let output = run_command(
+            "podman",
+            &[
+                "ps",
+                "--all",
+                "--size",
+                "--format",
+                "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.State}}\t{{.Status}}\t{{.Size}}\t{{index .Labels \"manager\"}}\t{{index .Labels \"com.github.containers.toolbox\"}}",
+            ],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!("Error listing the containers: {}", stderr.trim()));
+        }
+
+        Ok(parse_containers(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Starts the container using: podman start
+    pub async fn start(name: String) -> Result<(), anywho::Error> {
+        run_checked("podman", &["start", &name], &format!("start {}", name)).await
+    }
+
+    /// Stops the container using: podman stop
+    pub async fn stop(name: String) -> Result<(), anywho::Error> {
+        run_checked("podman", &["stop", &name], &format!("stop {}", name)).await
+    }
+
+    /// Deletes the container with the tool that created it, so it can clean up after it
+    /// (Ej: the applications distrobox exported to the host)
+    pub async fn delete(self) -> Result<(), anywho::Error> {
+        let args: &[&str] = match self.manager {
+            ContainerManager::Distrobox => &["rm", "--force", "--yes", &self.name],
+            ContainerManager::Toolbox => &["rm", "--force", &self.name],
+        };
+
+        run_checked(
+            self.manager.command(),
+            args,
+            &format!("delete {}", self.name),
+        )
+        .await
+    }
+
+    /// Upgrades the packages of a distrobox container using: distrobox upgrade
+    pub async fn upgrade(name: String) -> Result<(), anywho::Error> {
+        run_checked(
+            "distrobox",
+            &["upgrade", &name],
+            &format!("upgrade {}", name),
+        )
+        .await
+    }
+
+    /// Upgrades the packages of every distrobox container using: distrobox upgrade --all
+    pub async fn upgrade_all() -> Result<(), anywho::Error> {
+        run_checked("distrobox", &["upgrade", "--all"], "upgrade the containers").await
+    }
+
+    /// Creates a new container with the given tool, pulling the image if needed
+    pub async fn create(
+        manager: ContainerManager,
+        name: String,
+        image: String,
+    ) -> Result<(), anywho::Error> {
+        let args: &[&str] = match manager {
+            ContainerManager::Distrobox => &["create", "--yes", "--name", &name, "--image", &image],
+            ContainerManager::Toolbox => &["create", "--assumeyes", "--image", &image, &name],
+        };
+
+        run_checked(manager.command(), args, &format!("create {}", name)).await
+    }
+}
+
+/// Runs the given command, failing with its stderr if it doesn't succeed
+async fn run_checked(command: &str, args: &[&str], what: &str) -> Result<(), anywho::Error> {
+    let output = run_command(command, args).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!("Failed to {}: {}", what, stderr.trim()));
+    }
+
+    Ok(())
+}
+
+/// Parses the output of podman ps with the tab separated format of get_all,
+/// skipping the containers that weren't created by distrobox or toolbox
+fn parse_containers(output: &str) -> Vec<Container> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [id, name, image, state, status, size, manager, toolbox] = columns.as_slice()
+            else {
+                return None;
+            };
+
+            // distrobox containers made from toolbox images inherit its label, so distrobox goes first
+            let manager = if *manager == "distrobox" {
+                ContainerManager::Distrobox
+            } else if *toolbox == "true" {
+                ContainerManager::Toolbox
+            } else {
+                return None;
+            };
+
+            Some(Container {
+                id: id.to_string(),
+                name: name.to_string(),
+                image: image.to_string(),
+                status: status.to_string(),
+                running: *state == "running",
+                size: size.to_string(),
+                manager,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_containers() {
+        let containers = parse_containers(include_str!("fixtures/podman_ps.txt"));
+
+        assert_eq!(containers.len(), 3);
+        assert_eq!(
+            containers[0],
+            Container {
+                id: "3f2a1b0c9d8e".to_string(),
+                name: "fedora-dev".to_string(),
+                image: "registry.fedoraproject.org/fedora-toolbox:latest".to_string(),
+                status: "Up 2 hours".to_string(),
+                running: true,
+                size: "412MB (virtual 1.31GB)".to_string(),
+                manager: ContainerManager::Distrobox,
+            }
+        );
+        assert_eq!(containers[1].manager, ContainerManager::Toolbox);
+        assert!(!containers[1].running);
+        // the plain podman container is not listed
+        assert_eq!(containers[2].name, "arch");
+    }
+}
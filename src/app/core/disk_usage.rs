@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+use std::collections::HashMap;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
+
+use crate::app::core::run_command;
+use crate::app::core::system_status::Deployment;
+
+const OSTREE_REPO_PATH: &str = "/sysroot/ostree/repo";
+const OSTREE_DEPLOY_PATH: &str = "/sysroot/ostree/deploy";
+const RPM_OSTREE_CACHE_PATH: &str = "/var/cache/rpm-ostree";
+
+/// Disk usage of the ostree repo, each deployment and the cached metadata (in bytes)
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    /// Size of the repo objects no deployment uses, plus temporary files
+    pub unreferenced_size: u64,
+    pub metadata_size: u64,
+    pub deployments: Vec<DeploymentUsage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeploymentUsage {
+    pub index: i32,
+    pub checksum: String,
+    pub version: String,
+    /// Size of the content this deployment doesn't share with the booted deployment
+    /// or the deployments before it, the booted deployment gets its full size
+    pub unique_size: u64,
+    pub is_booted: bool,
+    pub is_pinned: bool,
+}
+
+/// Possible actions to free up the space used by each part of the [`DiskUsage`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CleanupAction {
+    /// Clear temporary files and unused objects: rpm-ostree cleanup --base
+    Base,
+    /// Clear the cached rpm metadata: rpm-ostree cleanup --repomd
+    Metadata,
+    /// Delete the deployment with the given index: ostree admin undeploy <index>
+    Undeploy(i32),
+}
+
+impl DiskUsage {
+    /// Computes the disk usage of the ostree repo, deployments and cached metadata using du
+    pub async fn get() -> Result<DiskUsage, anywho::Error> {
+        let output = run_command("ostree", &["admin", "status"]).await;
+
+        let output = match output {
+            Ok(output) => output,
+            Err(err) => return Err(anywho!("Error fetching deployments: {}", err)),
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let deployment_dirs = Self::parse_deployment_dirs(&stdout);
+
+        if deployment_dirs.is_empty() {
+            return Err(anywho!(
+                "No deployments found in ostree admin status output"
+            ));
+        }
+
+        // du only counts hardlinked files the first time it sees them, so with the booted
+        // deployment first each path only reports what the ones before it don't use,
+        // and the repo (listed last) only the objects no deployment references
+        let mut paths: Vec<&str> = deployment_dirs
+            .iter()
+            .filter(|(_, usage)| usage.is_booted)
+            .chain(deployment_dirs.iter().filter(|(_, usage)| !usage.is_booted))
+            .map(|(path, _)| path.as_str())
+            .collect();
+        paths.push(OSTREE_REPO_PATH);
+
+        let (sizes, metadata_size) =
+            iced::futures::future::join(Self::du(&paths), Self::du(&[RPM_OSTREE_CACHE_PATH])).await;
+        let sizes = sizes?;
+        let metadata_size = metadata_size
+            .ok()
+            .and_then(|sizes| sizes.get(RPM_OSTREE_CACHE_PATH).copied())
+            .unwrap_or(0);
+
+        let Some(unreferenced_size) = sizes.get(OSTREE_REPO_PATH).copied() else {
+            return Err(anywho!(
+                "Failed to compute the disk usage of the ostree repo"
+            ));
+        };
+
+        let deployments = deployment_dirs
+            .into_iter()
+            .map(|(path, usage)| DeploymentUsage {
+                unique_size: sizes.get(&path).copied().unwrap_or(0),
+                ..usage
+            })
+            .collect();
+
+        Ok(DiskUsage {
+            unreferenced_size,
+            metadata_size,
+            deployments,
+        })
+    }
+
+    /// Parses the output of ostree admin status into (deployment path, usage) pairs
+    fn parse_deployment_dirs(output: &str) -> Vec<(String, DeploymentUsage)> {
+        let mut deployments: Vec<(String, DeploymentUsage)> = Vec::new();
+
+        for line in output.lines() {
+            // parse lines like: "* fedora 7b0c2d8e...5f1a.0 (rollback)"
+            if !line.starts_with("    ") && !line.trim().is_empty() {
+                let is_booted = line.starts_with('*');
+                let mut parts = line.trim_start_matches('*').split_whitespace();
+
+                let (Some(os_name), Some(deployment)) = (parts.next(), parts.next()) else {
+                    continue;
+                };
+
+                let Some((checksum, _serial)) = deployment.rsplit_once('.') else {
+                    continue;
+                };
+
+                if checksum.len() != 64 || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+                    continue;
+                }
+
+                deployments.push((
+                    format!("{}/{}/deploy/{}", OSTREE_DEPLOY_PATH, os_name, deployment),
+                    DeploymentUsage {
+                        index: deployments.len() as i32,
+                        checksum: checksum.to_string(),
+                        version: String::new(),
+                        unique_size: 0,
+                        is_booted,
+                        is_pinned: false,
+                    },
+                ));
+            } else if let Some((_, usage)) = deployments.last_mut() {
+                let line = line.trim();
+
+                if line.starts_with("Version:") {
+                    usage.version = line
+                        .trim_start_matches("Version:")
+                        .split_whitespace()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string();
+                } else if line.starts_with("Pinned:") {
+                    let pinned_value = line.trim_start_matches("Pinned:").trim();
+                    usage.is_pinned = pinned_value.eq_ignore_ascii_case("yes");
+                }
+            }
+        }
+
+        deployments
+    }
+
+    /// Returns the size (in bytes) du reports for each of the given paths
+    async fn du(paths: &[&str]) -> Result<HashMap<String, u64>, anywho::Error> {
+        let mut args = vec!["-s", "--block-size=1"];
+        args.extend_from_slice(paths);
+
+        let output = run_command("du", &args)
+            .await
+            .map_err(|e| anywho!("{}", e))?;
+
+        // du may exit with an error if some files can't be read, but still reports what it could
+        let sizes = Self::parse_du(&String::from_utf8_lossy(&output.stdout));
+        if sizes.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!("Failed to compute disk usage: {}", stderr.trim()));
+        }
+
+        Ok(sizes)
+    }
+
+    /// Parses the output of du -s into a map of path to size
+    fn parse_du(output: &str) -> HashMap<String, u64> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let (size, path) = line.split_once('\t')?;
+                Some((path.to_string(), size.trim().parse::<u64>().ok()?))
+            })
+            .collect()
+    }
+}
+
+/// Runs the given [`CleanupAction`]
+pub async fn cleanup(action: CleanupAction) -> Result<(), anywho::Error> {
+    let flag = match action {
+        CleanupAction::Base => "--base",
+        CleanupAction::Metadata => "--repomd",
+        CleanupAction::Undeploy(index) => return Deployment::undeploy_deployment(index).await,
+    };
+
+    let output = run_command("pkexec", &["rpm-ostree", "cleanup", flag]).await;
+
+    match output {
+        Ok(output) => {
+            if output.status.success() {
+                Ok(())
+            } else {
+                if output.status == ExitStatus::from_raw(32256) {
+                    return Err(anywho!("Permision denied"));
+                }
+
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let stdout = String::from_utf8_lossy(&output.stdout);
+
+                Err(anywho!(
+                    "{}",
+                    if !stderr.is_empty() {
+                        stderr.trim()
+                    } else {
+                        stdout.trim()
+                    }
+                ))
+            }
+        }
+        Err(err) => Err(anywho!("{}", err)),
+    }
+}
+
+/// Formats a size in bytes into a human readable string (Ej: 1.5 GB)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000. && unit < UNITS.len() - 1 {
+        size /= 1000.;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_deployment_dirs() {
+        let deployments =
+            DiskUsage::parse_deployment_dirs(include_str!("fixtures/ostree_admin_status.txt"));
+
+        let summary: Vec<(&str, i32, &str, bool, bool)> = deployments
+            .iter()
+            .map(|(path, usage)| {
+                (
+                    path.as_str(),
+                    usage.index,
+                    usage.version.as_str(),
+                    usage.is_booted,
+                    usage.is_pinned,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "/sysroot/ostree/deploy/fedora/deploy/e7a4a1b0f6b5cd8b1e0c3e9a8e3e4fb6b8d1f1d0c2a6e6b4b7a2e9c1d3f5a7b9.0",
+                    0,
+                    "42.20250601.0",
+                    true,
+                    false
+                ),
+                (
+                    "/sysroot/ostree/deploy/fedora/deploy/3b9d2e1f0a8c7b6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d.0",
+                    1,
+                    "42.20250525.0",
+                    false,
+                    true
+                ),
+                (
+                    "/sysroot/ostree/deploy/fedora/deploy/5c1f0e7d9b3a2c4e6f8a0b1d3c5e7f9a1b2c4d6e8f0a1b3c5d7e9f1a2b4c6d8e.1",
+                    2,
+                    "42.20250518.0",
+                    false,
+                    false
+                ),
+            ]
+        );
+        assert_eq!(
+            deployments[0].1.checksum,
+            "e7a4a1b0f6b5cd8b1e0c3e9a8e3e4fb6b8d1f1d0c2a6e6b4b7a2e9c1d3f5a7b9"
+        );
+    }
+}
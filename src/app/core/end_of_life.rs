@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::run_command;
+use crate::app::core::update_applications::{Installation, RefKind};
+
+/// Installed application or runtime marked as end-of-life, it won't get any more updates
+#[derive(Debug, Clone)]
+pub struct EndOfLife {
+    pub name: String,
+    pub id: String,
+    pub kind: RefKind,
+    pub installation: Installation,
+    pub origin: String,
+    pub branch: String,
+    pub arch: String,
+    /// Reason given by the publisher, may be empty
+    pub reason: String,
+    /// Ref that replaces this one (Ej: app/org.gnome.NewName/x86_64/stable), older refs may only give its ID
+    pub rebase: Option<String>,
+}
+
+/// What happened to the data of an application in ~/.var/app when it was migrated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataMigration {
+    /// The data was moved to the new ID, with a link left behind on the old one
+    Moved,
+    /// There was no data to move
+    NoData,
+    /// The new ID already had its own data, so the old data was left where it was
+    Kept,
+}
+
+impl EndOfLife {
+    /// Full flatpak ref (Ej: app/org.gnome.Calculator/x86_64/stable)
+    pub fn flatpak_ref(&self) -> String {
+        format!(
+            "{}/{}/{}/{}",
+            self.kind.prefix(),
+            self.id,
+            self.arch,
+            self.branch
+        )
+    }
+
+    /// ID of the ref that replaces this one (Ej: org.gnome.NewName)
+    pub fn rebase_id(&self) -> Option<&str> {
+        let rebase = self.rebase.as_deref()?;
+
+        match rebase.split('/').collect::<Vec<&str>>()[..] {
+            [_, id, _, _] => Some(id),
+            _ => Some(rebase),
+        }
+    }
+
+    /// Full ref that replaces this one, built from the arch and branch of this ref if only the ID is known
+    pub fn rebase_ref(&self) -> Option<String> {
+        let rebase = self.rebase.as_deref()?;
+
+        if rebase.split('/').count() == 4 {
+            return Some(rebase.to_string());
+        }
+
+        Some(format!(
+            "{}/{}/{}/{}",
+            self.kind.prefix(),
+            rebase,
+            self.arch,
+            self.branch
+        ))
+    }
+
+    /// Returns the installed refs of both installations marked as end-of-life,
+    /// using: flatpak list --columns=name,ref,origin,options
+    pub async fn get_all() -> Result<Vec<EndOfLife>, anywho::Error> {
+        let mut end_of_life = Vec::new();
+        let mut errors = Vec::new();
+
+        for installation in Installation::ALL {
+            let output = match run_command(
+                "flatpak",
+                &[
+                    "list",
+                    installation.flag(),
+                    "--columns=name,ref,origin,options",
+                ],
+            )
+            .await
+            {
+                Ok(output) => output,
+                Err(err) => {
+                    errors.push(format!("{}: {}", installation, err));
+                    continue;
+                }
+            };
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                errors.push(format!("{}: {}", installation, stderr.trim()));
+                continue;
+            }
+
+            end_of_life.extend(parse_end_of_life(
+                &String::from_utf8_lossy(&output.stdout),
+                installation,
+            ));
+        }
+
+        // only fail if we couldn't check any installation
+        if errors.len() == Installation::ALL.len() {
+            return Err(anywho!(
+                "Error checking end-of-life refs: {}",
+                errors.join(", ")
+            ));
+        }
+
+        Ok(end_of_life)
+    }
+
+    /// Replaces an end-of-life application with the one it was rebased to:
+    /// installs the new ID, moves the data from ~/.var/app and uninstalls the old ID
+    pub async fn migrate(self) -> Result<DataMigration, anywho::Error> {
+        let (Some(new_id), Some(new_ref)) = (self.rebase_id(), self.rebase_ref()) else {
+            return Err(anywho!("{} has no replacement to migrate to", self.id));
+        };
+
+        let output = run_command(
+            "flatpak",
+            &[
+                "install",
+                "-y",
+                "--noninteractive",
+                self.installation.flag(),
+                &self.origin,
+                &new_ref,
+            ],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!("Failed to install {}: {}", new_id, stderr.trim()));
+        }
+
+        // the same thing flatpak does for renamed apps: move the data and leave a link behind
+        let mut data_migration = DataMigration::NoData;
+        if self.kind == RefKind::App {
+            let data_dir = dirs::home_dir().unwrap_or_default().join(".var/app");
+            let old_data = data_dir.join(&self.id).to_string_lossy().to_string();
+            let new_data = data_dir.join(new_id).to_string_lossy().to_string();
+
+            // the data lives on the host, so it's checked there
+            let has_old_data = host_test("-d", &old_data).await?;
+            let has_new_data = host_test("-e", &new_data).await?;
+
+            if has_old_data && has_new_data {
+                data_migration = DataMigration::Kept;
+            } else if has_old_data {
+                let output = run_command("mv", &[&old_data, &new_data]).await?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(anywho!(
+                        "Failed to move the data of {}: {}",
+                        self.id,
+                        stderr.trim()
+                    ));
+                }
+
+                let output = run_command("ln", &["-s", &new_data, &old_data]).await?;
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(anywho!(
+                        "Moved the data of {} but failed to link it back: {}",
+                        self.id,
+                        stderr.trim()
+                    ));
+                }
+                data_migration = DataMigration::Moved;
+            }
+        }
+
+        let flatpak_ref = self.flatpak_ref();
+        let output = run_command(
+            "flatpak",
+            &[
+                "uninstall",
+                "-y",
+                "--noninteractive",
+                self.installation.flag(),
+                &flatpak_ref,
+            ],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Failed to uninstall {}: {}",
+                flatpak_ref,
+                stderr.trim()
+            ));
+        }
+
+        Ok(data_migration)
+    }
+}
+
+/// Checks a path on the host using: test <flag> <path>
+async fn host_test(flag: &str, path: &str) -> Result<bool, anywho::Error> {
+    let output = run_command("test", &[flag, path]).await?;
+
+    Ok(output.status.success())
+}
+
+/// Parses the output of: flatpak list --columns=name,ref,origin,options,
+/// keeping only the refs with eol=<reason> or eol-rebase=<new ref> options
+fn parse_end_of_life(output: &str, installation: Installation) -> Vec<EndOfLife> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [name, flatpak_ref, origin, options] = columns.as_slice() else {
+                return None;
+            };
+
+            let (reason, rebase) = parse_eol_options(options)?;
+
+            let [prefix, id, arch, branch] = flatpak_ref.split('/').collect::<Vec<&str>>()[..]
+            else {
+                return None;
+            };
+            let kind = match prefix {
+                "app" => RefKind::App,
+                "runtime" => RefKind::Runtime,
+                _ => return None,
+            };
+
+            Some(EndOfLife {
+                name: if name.is_empty() { id } else { name }.to_string(),
+                id: id.to_string(),
+                kind,
+                installation,
+                origin: origin.to_string(),
+                branch: branch.to_string(),
+                arch: arch.to_string(),
+                reason,
+                rebase,
+            })
+        })
+        .collect()
+}
+
+/// Returns the end-of-life reason and rebase target of an options column (Ej: current,eol=Unmaintained),
+/// None if the ref is not end-of-life
+fn parse_eol_options(options: &str) -> Option<(String, Option<String>)> {
+    let mut reason: Option<String> = None;
+    let mut rebase = None;
+    let mut reading_reason = false;
+
+    for option in options.split(',') {
+        if let Some(value) = option.strip_prefix("eol=") {
+            reason = Some(value.to_string());
+            reading_reason = true;
+        } else if let Some(value) = option.strip_prefix("eol-rebase=") {
+            rebase = Some(value.to_string());
+            reading_reason = false;
+        } else if reading_reason && option.contains(char::is_whitespace) {
+            // the reason may contain commas
+            if let Some(reason) = &mut reason {
+                reason.push(',');
+                reason.push_str(option);
+            }
+        } else {
+            reading_reason = false;
+        }
+    }
+
+    if reason.is_none() && rebase.is_none() {
+        return None;
+    }
+
+    Some((reason.unwrap_or_default(), rebase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_end_of_life_refs() {
+        let end_of_life = parse_end_of_life(
+            include_str!("fixtures/flatpak_list_options.txt"),
+            Installation::System,
+        );
+
+        assert_eq!(end_of_life.len(), 4);
+
+        assert_eq!(end_of_life[0].id, "com.example.OldName");
+        assert_eq!(end_of_life[0].kind, RefKind::App);
+        assert_eq!(end_of_life[0].rebase_id(), Some("com.example.NewName"));
+        assert_eq!(
+            end_of_life[0].rebase_ref().as_deref(),
+            Some("app/com.example.NewName/x86_64/stable")
+        );
+        assert_eq!(
+            end_of_life[0].reason,
+            "The application has been renamed, please install the new one"
+        );
+
+        assert_eq!(end_of_life[1].name, "Old Editor");
+        assert_eq!(
+            end_of_life[1].reason,
+            "No longer maintained, use something else"
+        );
+        assert_eq!(end_of_life[1].rebase, None);
+
+        assert_eq!(end_of_life[2].id, "org.example.OldViewer");
+        assert_eq!(end_of_life[2].rebase_id(), Some("org.example.NewViewer"));
+        assert_eq!(
+            end_of_life[2].rebase_ref().as_deref(),
+            Some("app/org.example.NewViewer/x86_64/beta")
+        );
+
+        assert_eq!(
+            end_of_life[3].flatpak_ref(),
+            "runtime/org.gnome.Platform/x86_64/44"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+use iced::futures::{SinkExt, StreamExt};
+use serde::Deserialize;
+
+use crate::app::core::appstream::description_lines;
+use crate::app::core::{read_output_segments, run_command, spawn_command};
+use crate::app::utils::date::format_date;
+
+/// Device managed by fwupd, with the firmware releases it can be updated to
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirmwareDevice {
+    pub id: String,
+    pub name: String,
+    pub vendor: String,
+    /// Ej: UEFI revocation database, may be empty
+    pub summary: String,
+    /// Installed firmware version
+    pub version: String,
+    /// The installed firmware is only applied after rebooting
+    pub needs_reboot: bool,
+    /// Releases newer than the installed version, newest first
+    pub releases: Vec<FirmwareRelease>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirmwareRelease {
+    pub version: String,
+    /// Ej: 2025-05-01, may be empty
+    pub date: String,
+    /// Ej: low, medium, high, critical, may be empty
+    pub urgency: String,
+    /// Paragraphs and list items of the release description
+    pub notes: Vec<String>,
+}
+
+/// Progress of a firmware update
+#[derive(Debug, Clone)]
+pub enum FirmwareEvent {
+    /// What fwupd is doing (Ej: Writing…), with the completion percentage (0-100) if it's known
+    Status(String, Option<f32>),
+    /// The update has finished
+    Done(Result<(), anywho::Error>),
+}
+
+impl FirmwareDevice {
+    /// Returns the updatable devices with their available releases using:
+    /// fwupdmgr get-devices --json and fwupdmgr get-updates --json
+    pub async fn get_all() -> Result<Vec<FirmwareDevice>, anywho::Error> {
+        let (devices, updates) = iced::futures::future::join(
+            run_command("fwupdmgr", &["get-devices", "--json"]),
+            run_command(
+                "fwupdmgr",
+                &[
+                    "get-updates",
+                    "--json",
+                    "--no-unreported-check",
+                    "--no-metadata-check",
+                ],
+            ),
+        )
+        .await;
+
+        let devices = devices?;
+        if !devices.status.success() {
+            let stderr = String::from_utf8_lossy(&devices.stderr);
+            return Err(anywho!(
+                "Error listing the firmware devices: {}",
+                stderr.trim()
+            ));
+        }
+        let mut devices = parse_devices(&String::from_utf8_lossy(&devices.stdout))?;
+
+        // fwupdmgr exits with 2 and prints a message when there's nothing to update
+        let updates = updates?;
+        let updates = if updates.status.success() {
+            parse_updates(&String::from_utf8_lossy(&updates.stdout))?
+        } else if updates.status.code() == Some(2) {
+            Vec::new()
+        } else {
+            let stderr = String::from_utf8_lossy(&updates.stderr);
+            return Err(anywho!(
+                "Error checking the firmware updates: {}",
+                stderr.trim()
+            ));
+        };
+
+        for (device_id, releases) in updates {
+            if let Some(device) = devices.iter_mut().find(|device| device.id == device_id) {
+                device.releases = releases;
+            }
+        }
+
+        Ok(devices)
+    }
+
+    /// Downloads the latest firmware metadata from the LVFS using: fwupdmgr refresh
+    pub async fn refresh_metadata() -> Result<(), anywho::Error> {
+        let output = run_command("fwupdmgr", &["refresh"]).await?;
+
+        // 2 means the metadata is already up to date
+        if !output.status.success() && output.status.code() != Some(2) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Error refreshing the firmware metadata: {}",
+                stderr.trim()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Updates the firmware of the given device using: fwupdmgr update <device id>, streaming its progress.
+/// fwupdmgr doesn't reboot by itself, devices that need it are flagged with needs-reboot afterwards
+pub fn update(
+    device_id: String,
+) -> impl iced::futures::Stream<Item = FirmwareEvent> + Send + 'static {
+    iced::stream::channel(100, async move |mut sender| {
+        let mut child = match spawn_command(
+            "fwupdmgr",
+            &["update", &device_id, "--assume-yes", "--no-reboot-check"],
+        ) {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = sender
+                    .send(FirmwareEvent::Done(Err(anywho!(
+                        "Command execution failed: {}",
+                        e
+                    ))))
+                    .await;
+                return;
+            }
+        };
+
+        let mut last_line = String::new();
+        let mut segments = std::pin::pin!(read_output_segments(&mut child));
+        while let Some(segment) = segments.next().await {
+            if let Some((status, progress)) = parse_progress(&segment) {
+                last_line = status.clone();
+                let _ = sender.send(FirmwareEvent::Status(status, progress)).await;
+            }
+        }
+
+        let result = match child.wait().await {
+            Ok(status) if status.success() => Ok(()),
+            Ok(_) => Err(anywho!("Failed to update the firmware: {}", last_line)),
+            Err(e) => Err(anywho!("Failed to update the firmware: {}", e)),
+        };
+
+        let _ = sender.send(FirmwareEvent::Done(result)).await;
+    })
+}
+
+#[derive(Deserialize)]
+struct DevicesJson {
+    #[serde(rename = "Devices", default)]
+    devices: Vec<DeviceJson>,
+}
+
+#[derive(Deserialize)]
+struct DeviceJson {
+    #[serde(rename = "DeviceId", default)]
+    device_id: String,
+    #[serde(rename = "Name", default)]
+    name: String,
+    #[serde(rename = "Vendor", default)]
+    vendor: String,
+    #[serde(rename = "Summary", default)]
+    summary: String,
+    #[serde(rename = "Version", default)]
+    version: String,
+    #[serde(rename = "Flags", default)]
+    flags: Vec<String>,
+    /// Only on the output of get-updates
+    #[serde(rename = "Releases", default)]
+    releases: Vec<ReleaseJson>,
+}
+
+#[derive(Deserialize)]
+struct ReleaseJson {
+    #[serde(rename = "Version", default)]
+    version: String,
+    /// Unix timestamp of the release
+    #[serde(rename = "Created", default)]
+    created: Option<u64>,
+    #[serde(rename = "Urgency", default)]
+    urgency: String,
+    /// Appstream description markup
+    #[serde(rename = "Description", default)]
+    description: String,
+}
+
+/// Parses the devices of: fwupdmgr get-devices --json, keeping the updatable ones
+/// and the ones waiting for a reboot to apply their update
+fn parse_devices(output: &str) -> Result<Vec<FirmwareDevice>, anywho::Error> {
+    let json: DevicesJson = serde_json::from_str(output)
+        .map_err(|e| anywho!("Error parsing the firmware devices: {}", e))?;
+
+    Ok(json
+        .devices
+        .into_iter()
+        .filter_map(|device| {
+            let has_flag = |flag: &str| device.flags.iter().any(|f| f == flag);
+
+            let needs_reboot = has_flag("needs-reboot");
+            if !has_flag("updatable") && !needs_reboot {
+                return None;
+            }
+
+            Some(FirmwareDevice {
+                id: device.device_id,
+                name: device.name,
+                vendor: device.vendor,
+                summary: device.summary,
+                version: device.version,
+                needs_reboot,
+                releases: Vec::new(),
+            })
+        })
+        .collect())
+}
+
+/// Parses the releases of every device of: fwupdmgr get-updates --json
+fn parse_updates(output: &str) -> Result<Vec<(String, Vec<FirmwareRelease>)>, anywho::Error> {
+    let json: DevicesJson = serde_json::from_str(output)
+        .map_err(|e| anywho!("Error parsing the firmware updates: {}", e))?;
+
+    Ok(json
+        .devices
+        .into_iter()
+        .map(|device| {
+            let releases = device
+                .releases
+                .into_iter()
+                .map(|release| FirmwareRelease {
+                    version: release.version,
+                    date: release.created.map(format_date).unwrap_or_default(),
+                    urgency: release.urgency,
+                    notes: description_lines(&release.description),
+                })
+                .collect();
+
+            (device.device_id, releases)
+        })
+        .collect())
+}
+
+/// Parses a segment of the output of fwupdmgr update, the progress is drawn as a bar like:
+/// "Writing…                 [*********                    ]"
+fn parse_progress(segment: &str) -> Option<(String, Option<f32>)> {
+    let segment = segment.trim();
+    if segment.is_empty() {
+        return None;
+    }
+
+    let Some((status, bar)) = segment
+        .strip_suffix(']')
+        .and_then(|segment| segment.rsplit_once('['))
+    else {
+        return Some((segment.to_string(), None));
+    };
+
+    let total = bar.chars().count();
+    let done = bar.chars().filter(|c| *c == '*').count();
+    // the bar of a step without a known percentage is a moving block (Ej: [   -   ])
+    let progress = (total > 0 && done > 0).then(|| done as f32 * 100. / total as f32);
+
+    Some((status.trim().to_string(), progress))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_updatable_devices() {
+        let devices = parse_devices(include_str!("fixtures/fwupdmgr_get_devices.json")).unwrap();
+
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "UEFI dbx");
+        assert_eq!(devices[0].id, "362301da643102b9f38477387e2193e57abaa590");
+        assert_eq!(devices[0].version, "371");
+        assert!(!devices[0].needs_reboot);
+        assert_eq!(devices[1].vendor, "Lenovo");
+        assert!(devices[1].needs_reboot);
+    }
+
+    #[test]
+    fn parses_available_updates() {
+        let updates = parse_updates(include_str!("fixtures/fwupdmgr_get_updates.json")).unwrap();
+
+        assert_eq!(updates.len(), 1);
+        let (device_id, releases) = &updates[0];
+        assert_eq!(device_id, "362301da643102b9f38477387e2193e57abaa590");
+        assert_eq!(
+            releases[0],
+            FirmwareRelease {
+                version: "377".to_string(),
+                date: "2025-02-25".to_string(),
+                urgency: "high".to_string(),
+                notes: vec![
+                    "This updates the dbx to the latest release from Microsoft.".to_string(),
+                    "• Revokes vulnerable bootloaders".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_update_progress() {
+        assert_eq!(
+            parse_progress("Writing…                 [**********          ]"),
+            Some(("Writing…".to_string(), Some(50.)))
+        );
+        assert_eq!(
+            parse_progress("Decompressing…           [     -              ]"),
+            Some(("Decompressing…".to_string(), None))
+        );
+        assert_eq!(
+            parse_progress("Successfully installed firmware"),
+            Some(("Successfully installed firmware".to_string(), None))
+        );
+        assert_eq!(parse_progress("   "), None);
+    }
+}
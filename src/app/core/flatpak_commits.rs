@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::run_command;
+use crate::app::core::update_applications::Installation;
+
+/// Commit of a ref on its remote, as listed by flatpak remote-info --log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    pub commit: String,
+    pub subject: String,
+    /// Ej: 2024-11-05 14:09:11 +0000, may be empty
+    pub date: String,
+}
+
+impl Commit {
+    /// Abbreviated checksum of the commit (Ej: 5ddc1a2b3c4d)
+    pub fn short(&self) -> &str {
+        &self.commit[..self.commit.len().min(12)]
+    }
+
+    /// Returns the commits of a ref on its remote, newest first, together with the installed commit
+    pub async fn get_all(
+        installation: Installation,
+        origin: String,
+        flatpak_ref: String,
+    ) -> Result<(Vec<Commit>, String), anywho::Error> {
+        let (log, installed) = iced::futures::future::join(
+            run_command(
+                "flatpak",
+                &[
+                    "remote-info",
+                    installation.flag(),
+                    "--log",
+                    &origin,
+                    &flatpak_ref,
+                ],
+            ),
+            run_command(
+                "flatpak",
+                &["info", installation.flag(), "--show-commit", &flatpak_ref],
+            ),
+        )
+        .await;
+
+        let log = log?;
+        if !log.status.success() {
+            let stderr = String::from_utf8_lossy(&log.stderr);
+            return Err(anywho!(
+                "Error loading the history of {}: {}",
+                flatpak_ref,
+                stderr.trim()
+            ));
+        }
+
+        let installed_commit = installed
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_default();
+
+        Ok((
+            parse_commit_log(&String::from_utf8_lossy(&log.stdout)),
+            installed_commit,
+        ))
+    }
+
+    /// Deploys the given commit of a ref using: flatpak update --commit=<commit>,
+    /// optionally masking the application so the next update doesn't undo it
+    pub async fn downgrade(
+        installation: Installation,
+        app_id: String,
+        flatpak_ref: String,
+        commit: String,
+        mask: bool,
+    ) -> Result<(), anywho::Error> {
+        let commit_flag = format!("--commit={}", commit);
+        let output = run_command(
+            "flatpak",
+            &[
+                "update",
+                "-y",
+                "--noninteractive",
+                installation.flag(),
+                &commit_flag,
+                &flatpak_ref,
+            ],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Failed to deploy {} of {}: {}",
+                commit,
+                flatpak_ref,
+                stderr.trim()
+            ));
+        }
+
+        if mask {
+            let output = run_command("flatpak", &["mask", installation.flag(), &app_id]).await?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(anywho!("Failed to hold {}: {}", app_id, stderr.trim()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the commits of: flatpak remote-info --log, blocks of lines like:
+/// "    Commit: 5ddc1a...", "   Subject: Export org.gnome.Calculator", "      Date: 2024-11-05 14:09:11 +0000"
+fn parse_commit_log(output: &str) -> Vec<Commit> {
+    let mut commits: Vec<Commit> = Vec::new();
+
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key {
+            "Commit" => commits.push(Commit {
+                commit: value.to_string(),
+                subject: String::new(),
+                date: String::new(),
+            }),
+            "Subject" => {
+                if let Some(commit) = commits.last_mut() {
+                    commit.subject = value.to_string();
+                }
+            }
+            "Date" => {
+                if let Some(commit) = commits.last_mut() {
+                    commit.date = value.to_string();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    commits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commit_log() {
+        let commits = parse_commit_log(include_str!("fixtures/flatpak_remote_info_log.txt"));
+
+        assert_eq!(commits.len(), 3);
+        assert_eq!(
+            commits[0],
+            Commit {
+                commit: "5ddc1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7"
+                    .to_string(),
+                subject: "Update to 47.1 (a1b2c3d4)".to_string(),
+                date: "2024-11-05 14:09:11 +0000".to_string(),
+            }
+        );
+        assert_eq!(commits[0].short(), "5ddc1a2b3c4d");
+        // the parent of the latest commit is not an entry of the history
+        assert_eq!(commits[1].subject, "Update to 47.0 (e5f6a7b8)");
+        assert_eq!(commits[2].date, "2024-08-20 09:15:02 +0000");
+    }
+}
@@ -0,0 +1,434 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::run_command;
+use crate::app::core::update_applications::Installation;
+
+/// Kind of permission that can be toggled with flatpak override
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionKind {
+    Filesystem,
+    Device,
+    Socket,
+    /// Session bus name the application can talk to (Ej: org.freedesktop.Notifications)
+    TalkName,
+}
+
+impl PermissionKind {
+    pub const ALL: [PermissionKind; 4] = [
+        PermissionKind::Filesystem,
+        PermissionKind::Device,
+        PermissionKind::Socket,
+        PermissionKind::TalkName,
+    ];
+
+    /// Flatpak override flags to grant and revoke this kind of permission
+    fn flags(&self) -> (&'static str, &'static str) {
+        match self {
+            PermissionKind::Filesystem => ("--filesystem", "--nofilesystem"),
+            PermissionKind::Device => ("--device", "--nodevice"),
+            PermissionKind::Socket => ("--socket", "--nosocket"),
+            PermissionKind::TalkName => ("--talk-name", "--no-talk-name"),
+        }
+    }
+
+    /// Permissions always shown, even if neither the application nor the overrides mention them
+    fn common(&self) -> &'static [&'static str] {
+        match self {
+            PermissionKind::Filesystem => &[
+                "host",
+                "host-os",
+                "host-etc",
+                "home",
+                "xdg-desktop",
+                "xdg-documents",
+                "xdg-download",
+                "xdg-music",
+                "xdg-pictures",
+                "xdg-videos",
+            ],
+            PermissionKind::Device => &["dri", "input", "usb", "kvm", "shm", "all"],
+            PermissionKind::Socket => &[
+                "wayland",
+                "x11",
+                "fallback-x11",
+                "pulseaudio",
+                "cups",
+                "pcsc",
+                "ssh-auth",
+                "gpg-agent",
+                "session-bus",
+                "system-bus",
+            ],
+            PermissionKind::TalkName => &[],
+        }
+    }
+}
+
+/// Permission of an application combining its metadata with the overrides
+#[derive(Debug, Clone)]
+pub struct Permission {
+    pub kind: PermissionKind,
+    /// Ej: home, xdg-download, x11, org.freedesktop.Notifications
+    pub name: String,
+    /// Access mode of filesystems (Ej: ro), may be empty
+    pub mode: String,
+    /// Granted by the metadata of the application
+    pub default: bool,
+    /// Set by the overrides that apply to every application
+    pub global_override: Option<bool>,
+    /// Set by the overrides of this application
+    pub app_override: Option<bool>,
+}
+
+impl Permission {
+    /// Returns true if the application ends up having this permission
+    pub fn granted(&self) -> bool {
+        self.app_override
+            .or(self.global_override)
+            .unwrap_or(self.default)
+    }
+
+    /// Value to pass to flatpak override (Ej: home:ro)
+    pub fn value(&self) -> String {
+        if self.mode.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}:{}", self.name, self.mode)
+        }
+    }
+
+    /// Returns the permissions of an application, combining its metadata (flatpak info -m)
+    /// with the global and application overrides of its installation (flatpak override --show)
+    pub async fn get_all(
+        installation: Installation,
+        app_id: String,
+        flatpak_ref: String,
+    ) -> Result<Vec<Permission>, anywho::Error> {
+        let (metadata, global_overrides, app_overrides) = iced::futures::future::join3(
+            flatpak_output(&["info", installation.flag(), "-m", &flatpak_ref]),
+            flatpak_output(&["override", installation.flag(), "--show"]),
+            flatpak_output(&["override", installation.flag(), "--show", &app_id]),
+        )
+        .await;
+
+        Ok(combine_permissions(
+            &metadata?,
+            &global_overrides?,
+            &app_overrides?,
+        ))
+    }
+
+    /// Grants or revokes a permission of an application using: flatpak override
+    pub async fn set(
+        installation: Installation,
+        app_id: String,
+        kind: PermissionKind,
+        value: String,
+        granted: bool,
+    ) -> Result<(), anywho::Error> {
+        let (grant, revoke) = kind.flags();
+        let flag = if granted {
+            format!("{}={}", grant, value)
+        } else {
+            // revoking doesn't take the access mode into account
+            let name = value.split(':').next().unwrap_or_default();
+            format!("{}={}", revoke, name)
+        };
+
+        run_override(installation, &[&flag, &app_id]).await
+    }
+
+    /// Removes every override of an application: flatpak override --reset
+    pub async fn reset(installation: Installation, app_id: String) -> Result<(), anywho::Error> {
+        run_override(installation, &["--reset", &app_id]).await
+    }
+
+    /// Returns the permissions the latest commit of a ref on its remote grants and the installed one doesn't,
+    /// comparing the metadata of: flatpak info -m and flatpak remote-info -m
+    pub async fn new_permissions(
+        installation: Installation,
+        origin: String,
+        flatpak_ref: String,
+    ) -> Result<Vec<(PermissionKind, String)>, anywho::Error> {
+        let (installed, incoming) = iced::futures::future::join(
+            flatpak_output(&["info", installation.flag(), "-m", &flatpak_ref]),
+            flatpak_output(&[
+                "remote-info",
+                installation.flag(),
+                "-m",
+                &origin,
+                &flatpak_ref,
+            ]),
+        )
+        .await;
+
+        Ok(permission_growth(&installed?, &incoming?))
+    }
+}
+
+/// Runs flatpak and returns its stdout, fails if flatpak does
+async fn flatpak_output(args: &[&str]) -> Result<String, anywho::Error> {
+    let output = run_command("flatpak", args).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!("Error reading the permissions: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Runs flatpak override on the given installation,
+/// the overrides of the system installation can only be written as root
+async fn run_override(installation: Installation, args: &[&str]) -> Result<(), anywho::Error> {
+    let mut command_args = vec!["override", installation.flag()];
+    command_args.extend(args);
+
+    let output = match installation {
+        Installation::User => run_command("flatpak", &command_args).await?,
+        Installation::System => {
+            command_args.insert(0, "flatpak");
+            run_command("pkexec", &command_args).await?
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!(
+            "Failed to change the permissions: {}",
+            stderr.trim()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Parses a flatpak metadata or overrides keyfile into (kind, value, granted) entries,
+/// where value may contain the access mode (Ej: home:ro)
+fn parse_keyfile(keyfile: &str) -> Vec<(PermissionKind, String, bool)> {
+    let mut entries = Vec::new();
+    let mut section = "";
+
+    for line in keyfile.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name;
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+
+        match section {
+            "Context" => {
+                let kind = match key {
+                    "filesystems" => PermissionKind::Filesystem,
+                    "devices" => PermissionKind::Device,
+                    "sockets" => PermissionKind::Socket,
+                    _ => continue,
+                };
+
+                for value in value.split(';').filter(|value| !value.is_empty()) {
+                    match value.strip_prefix('!') {
+                        Some(value) => entries.push((kind, value.to_string(), false)),
+                        None => entries.push((kind, value.to_string(), true)),
+                    }
+                }
+            }
+            "Session Bus Policy" => {
+                entries.push((PermissionKind::TalkName, key.to_string(), value != "none"));
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// Returns the (kind, value) permissions granted by the incoming metadata and not by the installed one,
+/// read-only access to a filesystem is covered by read-write access to it
+fn permission_growth(installed: &str, incoming: &str) -> Vec<(PermissionKind, String)> {
+    let installed = parse_keyfile(installed);
+
+    parse_keyfile(incoming)
+        .into_iter()
+        .filter(|(_, _, granted)| *granted)
+        .filter(|(kind, value, _)| {
+            let (name, mode) = value.split_once(':').unwrap_or((value, ""));
+
+            !installed
+                .iter()
+                .any(|(installed_kind, installed_value, granted)| {
+                    let (installed_name, installed_mode) = installed_value
+                        .split_once(':')
+                        .unwrap_or((installed_value, ""));
+
+                    *granted
+                        && installed_kind == kind
+                        && installed_name == name
+                        && (installed_mode == mode || installed_mode != "ro")
+                })
+        })
+        .map(|(kind, value, _)| (kind, value))
+        .collect()
+}
+
+/// Combines the permissions of the metadata with the global and the application overrides
+fn combine_permissions(
+    metadata: &str,
+    global_overrides: &str,
+    app_overrides: &str,
+) -> Vec<Permission> {
+    let mut permissions: Vec<Permission> = PermissionKind::ALL
+        .iter()
+        .flat_map(|kind| {
+            kind.common().iter().map(|name| Permission {
+                kind: *kind,
+                name: name.to_string(),
+                mode: String::new(),
+                default: false,
+                global_override: None,
+                app_override: None,
+            })
+        })
+        .collect();
+
+    apply_keyfile(&mut permissions, metadata, |permission, granted| {
+        permission.default = granted
+    });
+    apply_keyfile(&mut permissions, global_overrides, |permission, granted| {
+        permission.global_override = Some(granted)
+    });
+    apply_keyfile(&mut permissions, app_overrides, |permission, granted| {
+        permission.app_override = Some(granted)
+    });
+
+    permissions
+}
+
+/// Applies the entries of a keyfile to the permissions, adding the ones not listed yet
+fn apply_keyfile(
+    permissions: &mut Vec<Permission>,
+    keyfile: &str,
+    apply: impl Fn(&mut Permission, bool),
+) {
+    for (kind, value, granted) in parse_keyfile(keyfile) {
+        let (name, mode) = match kind {
+            PermissionKind::Filesystem => value.split_once(':').unwrap_or((&value, "")),
+            _ => (value.as_str(), ""),
+        };
+
+        let index = match permissions
+            .iter()
+            .position(|permission| permission.kind == kind && permission.name == name)
+        {
+            Some(index) => index,
+            None => {
+                permissions.push(Permission {
+                    kind,
+                    name: name.to_string(),
+                    mode: String::new(),
+                    default: false,
+                    global_override: None,
+                    app_override: None,
+                });
+                permissions.len() - 1
+            }
+        };
+
+        let permission = &mut permissions[index];
+        if granted && !mode.is_empty() {
+            permission.mode = mode.to_string();
+        }
+        apply(permission, granted);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(permissions: &'a [Permission], kind: PermissionKind, name: &str) -> &'a Permission {
+        permissions
+            .iter()
+            .find(|permission| permission.kind == kind && permission.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn combines_metadata_and_overrides() {
+        let permissions = combine_permissions(
+            include_str!("fixtures/flatpak_info_metadata.txt"),
+            "[Context]\nsockets=!x11;\n",
+            include_str!("fixtures/flatpak_override_show.txt"),
+        );
+
+        // granted by the metadata with its access mode
+        let downloads = find(&permissions, PermissionKind::Filesystem, "xdg-download");
+        assert!(downloads.default);
+        assert_eq!(downloads.value(), "xdg-download:ro");
+
+        // granted by the application override
+        let home = find(&permissions, PermissionKind::Filesystem, "home");
+        assert!(!home.default);
+        assert!(home.granted());
+
+        // revoked by the global override
+        let x11 = find(&permissions, PermissionKind::Socket, "x11");
+        assert!(x11.default);
+        assert_eq!(x11.global_override, Some(false));
+        assert!(!x11.granted());
+
+        // revoked by the application override
+        let dri = find(&permissions, PermissionKind::Device, "dri");
+        assert!(dri.default);
+        assert!(!dri.granted());
+
+        let notifications = find(
+            &permissions,
+            PermissionKind::TalkName,
+            "org.freedesktop.Notifications",
+        );
+        assert!(notifications.granted());
+        let tracker = find(
+            &permissions,
+            PermissionKind::TalkName,
+            "org.freedesktop.Tracker3.Miner.Files",
+        );
+        assert!(!tracker.granted());
+
+        // permissions nobody mentions are still shown, not granted
+        assert!(!find(&permissions, PermissionKind::Filesystem, "host").granted());
+    }
+
+    #[test]
+    fn detects_permission_growth() {
+        let growth = permission_growth(
+            include_str!("fixtures/flatpak_info_metadata.txt"),
+            include_str!("fixtures/flatpak_remote_info_metadata.txt"),
+        );
+
+        // dropped permissions and read-only access to a read-write filesystem are not growth
+        assert_eq!(
+            growth,
+            vec![
+                (PermissionKind::Filesystem, "xdg-download".to_string()),
+                (PermissionKind::Filesystem, "home".to_string()),
+                (
+                    PermissionKind::TalkName,
+                    "org.freedesktop.secrets".to_string()
+                ),
+            ]
+        );
+
+        assert!(
+            permission_growth(
+                include_str!("fixtures/flatpak_info_metadata.txt"),
+                include_str!("fixtures/flatpak_info_metadata.txt"),
+            )
+            .is_empty()
+        );
+    }
+}
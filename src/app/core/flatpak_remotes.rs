@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::run_command;
+use crate::app::core::update_applications::Installation;
+
+/// Flatpak remote configured on one of the installations
+#[derive(Debug, Clone)]
+pub struct Remote {
+    pub name: String,
+    pub title: String,
+    pub url: String,
+    pub installation: Installation,
+    /// Higher priority remotes are preferred when a ref is available on more than one
+    pub priority: i32,
+    pub enabled: bool,
+    /// Path of the filter file applied to the remote, if any
+    pub filter: Option<String>,
+    /// Subset of the remote that is used (Ej: verified), if any
+    pub subset: Option<String>,
+}
+
+impl Remote {
+    /// Returns true if the remote points to Flathub
+    pub fn is_flathub(&self) -> bool {
+        self.name == "flathub" || self.url.contains("flathub.org")
+    }
+
+    /// Returns true if only part of Flathub can be installed from this remote,
+    /// like the filtered remote configured on stock Fedora
+    pub fn is_restricted_flathub(&self) -> bool {
+        self.is_flathub() && (self.filter.is_some() || self.subset.is_some())
+    }
+
+    /// Returns the remotes (enabled or not) of both installations, an installation that
+    /// can't be listed doesn't hide the remotes of the other one and its error is returned with them
+    pub async fn get_all() -> (Vec<Remote>, Option<anywho::Error>) {
+        let mut remotes = Vec::new();
+        let mut errors = Vec::new();
+
+        for installation in Installation::ALL {
+            match Self::get_installation_remotes(installation).await {
+                Ok(installation_remotes) => remotes.extend(installation_remotes),
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+
+        let error = (!errors.is_empty()).then(|| anywho!("{}", errors.join(", ")));
+        (remotes, error)
+    }
+
+    /// Returns the remotes of an installation using: flatpak remotes --show-disabled
+    async fn get_installation_remotes(
+        installation: Installation,
+    ) -> Result<Vec<Remote>, anywho::Error> {
+        let output = run_command(
+            "flatpak",
+            &[
+                "remotes",
+                installation.flag(),
+                "--show-disabled",
+                "--columns=name,title,url,priority,filter,subset,options",
+            ],
+        )
+        .await
+        .map_err(|e| anywho!("Error listing the {} remotes: {}", installation, e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Error listing the {} remotes: {}",
+                installation,
+                stderr.trim()
+            ));
+        }
+
+        Ok(parse_remotes(
+            &String::from_utf8_lossy(&output.stdout),
+            installation,
+        ))
+    }
+
+    /// Adds a remote from a .flatpakrepo file or URL, or from the URL of a repository.
+    /// If no name is given the name of the .flatpakrepo file is used (Ej: flathub.flatpakrepo -> flathub)
+    pub async fn add(
+        installation: Installation,
+        name: String,
+        location: String,
+    ) -> Result<(), anywho::Error> {
+        let location = location.trim();
+        let name = match name.trim() {
+            "" => remote_name_from_location(location)
+                .ok_or_else(|| anywho!("A name is needed to add the remote {}", location))?,
+            name => name.to_string(),
+        };
+
+        let mut args = vec!["remote-add", "--if-not-exists", installation.flag()];
+        if location.ends_with(".flatpakrepo") {
+            args.push("--from");
+        }
+        args.push(&name);
+        args.push(location);
+
+        run_flatpak(&args, &format!("Failed to add the remote {}", name)).await
+    }
+
+    /// Enables or disables a remote: flatpak remote-modify --enable/--disable
+    pub async fn set_enabled(
+        installation: Installation,
+        name: String,
+        enabled: bool,
+    ) -> Result<(), anywho::Error> {
+        let flag = if enabled { "--enable" } else { "--disable" };
+        run_flatpak(
+            &["remote-modify", installation.flag(), flag, &name],
+            &format!("Failed to modify the remote {}", name),
+        )
+        .await
+    }
+
+    /// Changes the priority of a remote: flatpak remote-modify --prio=<priority>
+    pub async fn set_priority(
+        installation: Installation,
+        name: String,
+        priority: i32,
+    ) -> Result<(), anywho::Error> {
+        run_flatpak(
+            &[
+                "remote-modify",
+                installation.flag(),
+                &format!("--prio={}", priority),
+                &name,
+            ],
+            &format!("Failed to change the priority of the remote {}", name),
+        )
+        .await
+    }
+
+    /// Removes a remote, fails if there are still refs installed from it
+    pub async fn delete(installation: Installation, name: String) -> Result<(), anywho::Error> {
+        run_flatpak(
+            &["remote-delete", installation.flag(), &name],
+            &format!("Failed to remove the remote {}", name),
+        )
+        .await
+    }
+}
+
+/// Runs a flatpak command that only needs to succeed
+async fn run_flatpak(args: &[&str], error_message: &str) -> Result<(), anywho::Error> {
+    let output = run_command("flatpak", args).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!("{}: {}", error_message, stderr.trim()));
+    }
+
+    Ok(())
+}
+
+/// Name of the .flatpakrepo file of the location, without the extension
+fn remote_name_from_location(location: &str) -> Option<String> {
+    let file_name = location.trim_end_matches('/').rsplit('/').next()?;
+    let name = file_name.strip_suffix(".flatpakrepo")?;
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Parses the output of: flatpak remotes --columns=name,title,url,priority,filter,subset,options
+fn parse_remotes(output: &str, installation: Installation) -> Vec<Remote> {
+    // flatpak prints a dash on the columns without value
+    let optional = |value: &str| match value {
+        "" | "-" => None,
+        value => Some(value.to_string()),
+    };
+
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [name, title, url, priority, filter, subset, options] = columns.as_slice() else {
+                return None;
+            };
+
+            if name.is_empty() {
+                return None;
+            }
+
+            Some(Remote {
+                name: name.to_string(),
+                title: if title.is_empty() { name } else { title }.to_string(),
+                url: url.to_string(),
+                installation,
+                priority: priority.parse().unwrap_or(1),
+                enabled: !options.split(',').any(|option| option == "disabled"),
+                filter: optional(filter),
+                subset: optional(subset),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_remotes() {
+        let remotes = parse_remotes(
+            include_str!("fixtures/flatpak_remotes.txt"),
+            Installation::System,
+        );
+
+        assert_eq!(remotes.len(), 3);
+        assert_eq!(remotes[0].name, "fedora");
+        assert!(remotes[0].enabled);
+        assert!(!remotes[0].is_flathub());
+        assert!(!remotes[1].enabled);
+
+        // stock Fedora filtered Flathub
+        assert_eq!(remotes[2].title, "Flathub");
+        assert_eq!(remotes[2].priority, 2);
+        assert_eq!(
+            remotes[2].filter.as_deref(),
+            Some("/usr/share/flatpak/fedora-flathub.filter")
+        );
+        assert_eq!(remotes[2].subset, None);
+        assert!(remotes[2].is_restricted_flathub());
+    }
+
+    #[test]
+    fn names_remotes_from_flatpakrepo() {
+        assert_eq!(
+            remote_name_from_location("https://dl.flathub.org/repo/flathub.flatpakrepo"),
+            Some("flathub".to_string())
+        );
+        assert_eq!(
+            remote_name_from_location("/home/user/Downloads/gnome-nightly.flatpakrepo"),
+            Some("gnome-nightly".to_string())
+        );
+        assert_eq!(
+            remote_name_from_location("https://dl.flathub.org/repo/"),
+            None
+        );
+    }
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+use iced::futures::{SinkExt, StreamExt};
+
+use crate::app::core::update_applications::Installation;
+use crate::app::core::{read_output_segments, spawn_command};
+
+/// What flatpak repair fixed on an installation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepairSummary {
+    /// Missing or invalid objects that were removed from the repository
+    pub removed_objects: usize,
+    /// Refs deleted because of missing or invalid objects, flatpak downloads them again
+    pub redownloaded_refs: Vec<String>,
+    /// Refs left behind by interrupted installations that were removed
+    pub removed_refs: Vec<String>,
+    /// Problems flatpak couldn't fix (Ej: the remote of a ref is missing)
+    pub problems: Vec<String>,
+}
+
+/// Progress of a flatpak repair
+#[derive(Debug, Clone)]
+pub enum RepairEvent {
+    /// Line printed by flatpak repair
+    Output(String),
+    /// The repair has finished, with the summary of what was fixed
+    Done(Result<RepairSummary, anywho::Error>),
+}
+
+/// Repairs the given installation using: flatpak repair, streaming its output.
+/// The system installation needs privileges, so it runs through pkexec
+pub fn repair(
+    installation: Installation,
+) -> impl iced::futures::Stream<Item = RepairEvent> + Send + 'static {
+    iced::stream::channel(100, async move |mut sender| {
+        let spawned = match installation {
+            Installation::User => spawn_command("flatpak", &["repair", installation.flag()]),
+            Installation::System => {
+                spawn_command("pkexec", &["flatpak", "repair", installation.flag()])
+            }
+        };
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                let _ = sender
+                    .send(RepairEvent::Done(Err(anywho!(
+                        "Command execution failed: {}",
+                        e
+                    ))))
+                    .await;
+                return;
+            }
+        };
+
+        let mut lines = Vec::new();
+        let mut segments = std::pin::pin!(read_output_segments(&mut child));
+        while let Some(segment) = segments.next().await {
+            let line = segment.trim_end().to_string();
+            if line.is_empty() {
+                continue;
+            }
+
+            let _ = sender.send(RepairEvent::Output(line.clone())).await;
+            lines.push(line);
+        }
+
+        let result = match child.wait().await {
+            Ok(status) if status.success() => Ok(parse_repair_output(&lines)),
+            Ok(_) => Err(anywho!(
+                "Failed to repair the {} installation: {}",
+                installation,
+                lines.last().map(String::as_str).unwrap_or_default()
+            )),
+            Err(e) => Err(anywho!(
+                "Failed to repair the {} installation: {}",
+                installation,
+                e
+            )),
+        };
+
+        let _ = sender.send(RepairEvent::Done(result)).await;
+    })
+}
+
+/// Summarizes the output of flatpak repair, lines like:
+/// "Object missing: 1a2b....dirtree", "Deleting ref app/org.gnome.Calculator/x86_64/stable due to missing objects",
+/// "Removing non-deployed ref runtime/org.gnome.Platform/x86_64/46…"
+fn parse_repair_output(lines: &[String]) -> RepairSummary {
+    let mut summary = RepairSummary::default();
+    let mut failed_refs = Vec::new();
+
+    for line in lines.iter().map(|line| line.trim()) {
+        if line.starts_with("Object missing:") || line.starts_with("Object invalid:") {
+            summary.removed_objects += 1;
+        } else if let Some(rest) = line.strip_prefix("Deleting ref ") {
+            if let Some((flatpak_ref, _)) = rest.split_once(" due to") {
+                summary.redownloaded_refs.push(flatpak_ref.to_string());
+            }
+        } else if let Some(rest) = line.strip_prefix("Removing non-deployed ref ") {
+            summary
+                .removed_refs
+                .push(rest.trim_end_matches(['…', '.']).to_string());
+        } else if let Some(rest) = line.strip_prefix("Error reinstalling ") {
+            if let Some((flatpak_ref, _)) = rest.split_once(": ") {
+                failed_refs.push(flatpak_ref.to_string());
+            }
+            summary.problems.push(line.to_string());
+        } else if (line.starts_with("Remote ")
+            && (line.ends_with(" is missing") || line.ends_with(" is disabled")))
+            || line.starts_with("Problems loading data for ")
+            || line.starts_with("Can't load object ")
+        {
+            summary.problems.push(line.to_string());
+        }
+    }
+
+    // refs that couldn't be installed again are only a problem, not redownloaded
+    summary
+        .redownloaded_refs
+        .retain(|flatpak_ref| !failed_refs.contains(flatpak_ref));
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_repair_output() {
+        let lines: Vec<String> = include_str!("fixtures/flatpak_repair.txt")
+            .lines()
+            .map(String::from)
+            .collect();
+        let summary = parse_repair_output(&lines);
+
+        assert_eq!(summary.removed_objects, 3);
+        assert_eq!(
+            summary.redownloaded_refs,
+            vec!["app/org.gnome.Calculator/x86_64/stable"]
+        );
+        assert_eq!(
+            summary.removed_refs,
+            vec!["runtime/org.gnome.Platform/x86_64/46"]
+        );
+        assert_eq!(summary.problems.len(), 2);
+        assert_eq!(
+            summary.problems[0],
+            "Remote gnome-nightly for ref app/org.gnome.TextEditor.Devel/x86_64/master is missing"
+        );
+    }
+
+    #[test]
+    fn parses_clean_repair() {
+        let lines = vec![
+            "Working on the user installation at /home/user/.local/share/flatpak".to_string(),
+            "[1/2] Verifying app/org.mozilla.firefox/x86_64/stable…".to_string(),
+            "[2/2] Verifying runtime/org.freedesktop.Platform/x86_64/24.08…".to_string(),
+            "Checking remotes...".to_string(),
+            "Pruning objects".to_string(),
+            "Erasing .removed".to_string(),
+        ];
+
+        assert_eq!(parse_repair_output(&lines), RepairSummary::default());
+    }
+}
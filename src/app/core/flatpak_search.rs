@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::flatpak_remotes::Remote;
+use crate::app::core::update_applications::{AppIcon, Installation};
+use crate::app::core::{appstream, run_command};
+
+/// Application found on the appstream data of the configured remotes
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub name: String,
+    pub summary: String,
+    pub app_id: String,
+    pub version: String,
+    pub branch: String,
+    /// Remotes that provide the application (Ej: flathub) with the installation they are configured on,
+    /// a remote configured on both installations is listed once for each
+    pub remotes: Vec<(Installation, String)>,
+    pub icon: Option<AppIcon>,
+    /// Installations the application is already installed in
+    pub installed: Vec<Installation>,
+}
+
+impl SearchResult {
+    /// Names of the remotes that provide the application, without repeating them
+    pub fn remote_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (_, remote) in &self.remotes {
+            if !names.contains(&remote.as_str()) {
+                names.push(remote);
+            }
+        }
+        names
+    }
+
+    /// First remote of the given installation that provides the application
+    pub fn remote_of(&self, installation: Installation) -> Option<&str> {
+        self.remotes
+            .iter()
+            .find(|(remote_installation, _)| *remote_installation == installation)
+            .map(|(_, remote)| remote.as_str())
+    }
+
+    /// Searches the local appstream data of every remote using: flatpak search
+    pub async fn search(app_id: &str, query: String) -> Result<Vec<SearchResult>, anywho::Error> {
+        let (search, installed, (remotes, _)) = iced::futures::future::join3(
+            run_command(
+                "flatpak",
+                &[
+                    "search",
+                    "--columns=name,description,application,version,branch,remotes",
+                    &query,
+                ],
+            ),
+            run_command(
+                "flatpak",
+                &["list", "--app", "--columns=application,installation"],
+            ),
+            Remote::get_all(),
+        )
+        .await;
+
+        let search = search?;
+        if !search.status.success() {
+            let stderr = String::from_utf8_lossy(&search.stderr);
+            return Err(anywho!("Error searching {}: {}", query, stderr.trim()));
+        }
+
+        let installed = installed
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| parse_installed(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default();
+
+        // flatpak search only lists the remote names, so we look up which installations have them
+        let installation_remotes: Vec<(Installation, String)> = remotes
+            .into_iter()
+            .filter(|remote| remote.enabled)
+            .map(|remote| (remote.installation, remote.name))
+            .collect();
+
+        let mut results = parse_search_results(
+            &String::from_utf8_lossy(&search.stdout),
+            &installation_remotes,
+        );
+
+        let icons = iced::futures::future::join_all(
+            results
+                .iter()
+                .map(|result| appstream_icon(app_id, &result.app_id, result.remote_names())),
+        )
+        .await;
+
+        for (result, icon) in results.iter_mut().zip(icons) {
+            result.icon = icon;
+            result.installed = installed
+                .iter()
+                .filter(|(app_id, _)| *app_id == result.app_id)
+                .map(|(_, installation)| *installation)
+                .collect();
+        }
+
+        Ok(results)
+    }
+
+    /// Installs the application from the given remote into the given installation
+    pub async fn install(
+        installation: Installation,
+        remote: String,
+        app_id: String,
+        branch: String,
+    ) -> Result<(), anywho::Error> {
+        let app_ref = if branch.is_empty() {
+            app_id.clone()
+        } else {
+            format!("{}//{}", app_id, branch)
+        };
+
+        let output = run_command(
+            "flatpak",
+            &[
+                "install",
+                "-y",
+                "--noninteractive",
+                installation.flag(),
+                &remote,
+                &app_ref,
+            ],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Failed to install {} from {}: {}",
+                app_id,
+                remote,
+                stderr.trim()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Looks for the icon of an application on the appstream cache of its remotes,
+/// where flatpak keeps the icons it downloads with the appstream data
+async fn appstream_icon(app_id: &str, application_id: &str, remotes: Vec<&str>) -> Option<AppIcon> {
+    for remote in remotes {
+        // the cache lives on the host, so the icon is copied to our cache directory
+        if let Some(path) = appstream::cache_remote_icon(app_id, remote, application_id).await {
+            return Some(AppIcon::Image {
+                path: path.to_string_lossy().into_owned(),
+            });
+        }
+    }
+
+    None
+}
+
+/// Parses the output of: flatpak search --columns=name,description,application,version,branch,remotes,
+/// pairing each remote with the installations (from the given installation remotes) it's configured on
+fn parse_search_results(
+    output: &str,
+    installation_remotes: &[(Installation, String)],
+) -> Vec<SearchResult> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            // "No matches found" and other messages don't have columns
+            let [name, summary, app_id, version, branch, remotes] = columns.as_slice() else {
+                return None;
+            };
+
+            Some(SearchResult {
+                name: if name.is_empty() { app_id } else { name }.to_string(),
+                summary: summary.to_string(),
+                app_id: app_id.to_string(),
+                version: version.to_string(),
+                branch: branch.to_string(),
+                remotes: remotes
+                    .split(',')
+                    .map(str::trim)
+                    .flat_map(|remote| {
+                        installation_remotes
+                            .iter()
+                            .filter(move |(_, name)| name == remote)
+                            .cloned()
+                    })
+                    .collect(),
+                icon: None,
+                installed: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Parses the output of: flatpak list --app --columns=application,installation
+fn parse_installed(output: &str) -> Vec<(String, Installation)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (app_id, installation) = line.split_once('\t')?;
+            let installation = match installation.trim() {
+                "user" => Installation::User,
+                "system" => Installation::System,
+                _ => return None,
+            };
+
+            Some((app_id.trim().to_string(), installation))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_search_results() {
+        let installation_remotes = vec![
+            (Installation::User, "gnome-nightly".to_string()),
+            (Installation::System, "flathub".to_string()),
+            (Installation::User, "flathub".to_string()),
+        ];
+        let results = parse_search_results(
+            include_str!("fixtures/flatpak_search.txt"),
+            &installation_remotes,
+        );
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].name, "Calculator");
+        assert_eq!(
+            results[0].summary,
+            "Perform arithmetic, scientific or financial calculations"
+        );
+        assert_eq!(results[0].app_id, "org.gnome.Calculator");
+        assert_eq!(results[0].version, "48.1");
+        assert_eq!(
+            results[0].remotes,
+            vec![
+                (Installation::System, "flathub".to_string()),
+                (Installation::User, "flathub".to_string()),
+                (Installation::User, "gnome-nightly".to_string()),
+            ]
+        );
+        assert_eq!(results[0].remote_names(), vec!["flathub", "gnome-nightly"]);
+        assert_eq!(results[0].remote_of(Installation::User), Some("flathub"));
+        assert_eq!(results[2].version, "");
+        assert_eq!(results[2].branch, "stable");
+    }
+
+    #[test]
+    fn parses_no_matches() {
+        assert!(parse_search_results("No matches found\n", &[]).is_empty());
+    }
+
+    #[test]
+    fn parses_installed_applications() {
+        let installed =
+            parse_installed("org.gnome.Calculator\tsystem\norg.mozilla.firefox\tuser\n");
+
+        assert_eq!(
+            installed,
+            vec![
+                ("org.gnome.Calculator".to_string(), Installation::System),
+                ("org.mozilla.firefox".to_string(), Installation::User),
+            ]
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum GpgSignatureStatus {
+    Valid,
+    Invalid,
+    #[default]
+    Unsigned,
+    /// rpm-ostree printed something we couldn't make sense of
+    Unknown,
+}
+
+/// Origins of deployments that come from container images (Ej: ostree-image-signed:docker://...),
+/// those are verified by the container policy instead of GPG
+const CONTAINER_IMAGE_ORIGINS: [&str; 5] = [
+    "ostree-image-signed",
+    "ostree-unverified-registry",
+    "ostree-unverified-image",
+    "ostree-remote-image",
+    "ostree-remote-registry",
+];
+
+/// Checks if the given origin (the part of the ref before ':') is a container image
+pub fn is_container_image_origin(origin: &str) -> bool {
+    CONTAINER_IMAGE_ORIGINS.contains(&origin)
+}
+
+/// Parsed GPGSignature field of rpm-ostree (status/upgrade --check)
+#[derive(Debug, Clone, Default)]
+pub struct GpgSignature {
+    pub status: GpgSignatureStatus,
+    pub key_id: Option<String>,
+    pub signer: Option<String>,
+    pub date: Option<String>,
+    /// The text rpm-ostree printed, kept to show it when we can't parse it
+    pub raw: String,
+}
+
+impl GpgSignature {
+    /// Parses the content of a GPGSignature field, it supports both the brief format
+    /// (Ej: "Valid signature by 115DF9AEF857853EE8445D0A0727707EA15B79CC") and the
+    /// verbose one (Ej: "Signature made ... using RSA key ID ...", "Good signature from ...")
+    pub fn parse(raw: &str) -> GpgSignature {
+        let raw = raw.trim();
+        let lowercase = raw.to_lowercase();
+
+        let status = if raw.is_empty()
+            || lowercase.contains("unsigned")
+            || lowercase.contains("no signature")
+        {
+            GpgSignatureStatus::Unsigned
+        } else if lowercase.contains("bad signature")
+            || lowercase.contains("invalid")
+            || lowercase.contains("can't check")
+            || lowercase.contains("expired")
+            || lowercase.contains("revoked")
+            || lowercase.contains("not found")
+        {
+            GpgSignatureStatus::Invalid
+        } else if lowercase.contains("valid signature") || lowercase.contains("good signature") {
+            GpgSignatureStatus::Valid
+        } else {
+            // we don't know what this is, it's shown as is instead of blocking the user
+            GpgSignatureStatus::Unknown
+        };
+
+        let key_id = Self::word_after(raw, "key ID ").or_else(|| Self::word_after(raw, " by "));
+
+        let signer = raw.split_once(" from \"").and_then(|(_, rest)| {
+            rest.split_once('"')
+                .map(|(signer, _)| signer.trim().to_string())
+        });
+
+        let date = raw.split_once("Signature made ").map(|(_, rest)| {
+            rest.split_once(" using ")
+                .map(|(date, _)| date)
+                .unwrap_or(rest)
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        });
+
+        GpgSignature {
+            status,
+            key_id,
+            signer,
+            date,
+            raw: raw.to_string(),
+        }
+    }
+
+    /// The signature is known to be bad or missing
+    pub fn is_untrusted(&self) -> bool {
+        matches!(
+            self.status,
+            GpgSignatureStatus::Invalid | GpgSignatureStatus::Unsigned
+        )
+    }
+
+    /// Returns the first word after the given pattern, if any
+    fn word_after(raw: &str, pattern: &str) -> Option<String> {
+        raw.split_once(pattern)
+            .and_then(|(_, rest)| rest.split_whitespace().next())
+            .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+    }
+}
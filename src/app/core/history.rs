@@ -0,0 +1,241 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+use serde::{Deserialize, Serialize};
+
+use crate::app::core::run_command;
+use crate::app::core::system_status::Deployment;
+use crate::app::utils::date::format_date;
+
+const HISTORY_VERSION: i32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum HistoryKind {
+    Upgrade,
+    Rollback,
+    Rebase,
+    Layering,
+}
+
+/// A successful change to the system deployments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub kind: HistoryKind,
+    /// Seconds since the UNIX epoch
+    pub timestamp: u64,
+    pub from_version: String,
+    /// Checksum of the deployment (entries recorded by older versions have the base commit)
+    pub from_checksum: String,
+    pub to_version: String,
+    pub to_checksum: String,
+    /// Output of rpm-ostree db diff between both deployments
+    pub package_diff: String,
+}
+
+/// Packages that changed between two deployments (parsed from rpm-ostree db diff)
+#[derive(Debug, Clone, Default)]
+pub struct PackageDiff {
+    pub upgraded: Vec<String>,
+    pub downgraded: Vec<String>,
+    pub removed: Vec<String>,
+    pub added: Vec<String>,
+}
+
+impl HistoryEntry {
+    /// Loads all the recorded entries (oldest first)
+    pub async fn load_all(app_id: &str) -> Result<Vec<HistoryEntry>, anywho::Error> {
+        use std::fs;
+        use tokio::task;
+
+        let app_id = app_id.to_string();
+
+        task::spawn_blocking(move || {
+            let history_path = history_path(&app_id)?;
+
+            if !history_path.exists() {
+                return Ok(Vec::new());
+            }
+
+            let history_content = fs::read_to_string(&history_path)
+                .map_err(|e| anywho!("Failed to read history file: {}", e))?;
+
+            ron::from_str(&history_content)
+                .map_err(|e| anywho!("Failed to parse history file: {}", e))
+        })
+        .await
+        .map_err(|e| anywho!("Task join error: {}", e))?
+    }
+
+    /// Records the change between the booted deployment and the one that will be booted next,
+    /// it's meant to be called right after a successful upgrade, rollback or layering change
+    pub async fn record(app_id: &'static str, kind: HistoryKind) -> Result<(), anywho::Error> {
+        use std::fs;
+        use std::time::{SystemTime, UNIX_EPOCH};
+        use tokio::task;
+
+        let deployments = Deployment::get_all().await?;
+
+        let from = deployments
+            .iter()
+            .find(|d| d.is_booted)
+            .ok_or_else(|| anywho!("Could not find the booted deployment"))?;
+
+        // the first deployment is the one that will be booted next
+        let to = deployments
+            .first()
+            .ok_or_else(|| anywho!("Could not find the pending deployment"))?;
+
+        if to.is_booted {
+            return Ok(());
+        }
+
+        // an upgrade that changes the origin of the deployment is a rebase,
+        // the whole refspec is compared since a rebase can keep the remote (Ej: 41 to 42)
+        let kind = if kind == HistoryKind::Upgrade && from.origin != to.origin {
+            HistoryKind::Rebase
+        } else {
+            kind
+        };
+
+        // compares the booted and pending deployments, we keep the entry even if this fails
+        let package_diff = match run_command("rpm-ostree", &["db", "diff"]).await {
+            Ok(output) if output.status.success() => {
+                String::from_utf8_lossy(&output.stdout).trim().to_string()
+            }
+            _ => String::new(),
+        };
+
+        let entry = HistoryEntry {
+            kind,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            from_version: from.display_version(),
+            from_checksum: from.checksum.clone(),
+            to_version: to.display_version(),
+            to_checksum: to.checksum.clone(),
+            package_diff,
+        };
+
+        task::spawn_blocking(move || {
+            let history_path = history_path(app_id)?;
+
+            let mut entries: Vec<HistoryEntry> = if history_path.exists() {
+                let history_content = fs::read_to_string(&history_path)
+                    .map_err(|e| anywho!("Failed to read history file: {}", e))?;
+
+                // a history we can't parse is kept as a backup instead of being overwritten
+                ron::from_str(&history_content).or_else(|_| {
+                    fs::rename(&history_path, history_path.with_extension("ron.bak"))
+                        .map(|_| Vec::new())
+                        .map_err(|e| anywho!("Failed to back up the history file: {}", e))
+                })?
+            } else {
+                Vec::new()
+            };
+            entries.push(entry);
+
+            let history_content =
+                ron::ser::to_string_pretty(&entries, ron::ser::PrettyConfig::default())
+                    .map_err(|e| anywho!("Failed to serialize history: {}", e))?;
+
+            // write to a temporary file first so the history is never left half written
+            let temp_path = history_path.with_extension("ron.tmp");
+            fs::write(&temp_path, history_content)
+                .and_then(|_| fs::rename(&temp_path, &history_path))
+                .map_err(|e| anywho!("Failed to write history file: {}", e))
+        })
+        .await
+        .map_err(|e| anywho!("Task join error: {}", e))?
+    }
+
+    pub fn package_changes(&self) -> PackageDiff {
+        PackageDiff::parse(&self.package_diff)
+    }
+
+    /// Formats the timestamp of the entry (Ej: 2025-01-31 18:05 UTC)
+    pub fn formatted_date(&self) -> String {
+        let seconds_of_day = self.timestamp % 86400;
+
+        format!(
+            "{} {:02}:{:02} UTC",
+            format_date(self.timestamp),
+            seconds_of_day / 3600,
+            (seconds_of_day % 3600) / 60
+        )
+    }
+}
+
+impl PackageDiff {
+    /// Parses the output of rpm-ostree db diff, lines like:
+    /// "Upgraded:" followed by "  mesa-dri-drivers 24.1.1-1.fc40 -> 24.1.2-1.fc40"
+    pub fn parse(output: &str) -> PackageDiff {
+        let mut diff = PackageDiff::default();
+        let mut current_section: Option<&mut Vec<String>> = None;
+
+        for line in output.lines() {
+            if !line.starts_with(' ') {
+                current_section = match line.trim() {
+                    "Upgraded:" => Some(&mut diff.upgraded),
+                    "Downgraded:" => Some(&mut diff.downgraded),
+                    "Removed:" => Some(&mut diff.removed),
+                    "Added:" => Some(&mut diff.added),
+                    _ => None,
+                };
+            } else if let Some(section) = current_section.as_mut()
+                && !line.trim().is_empty()
+            {
+                section.push(line.trim().to_string());
+            }
+        }
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.upgraded.is_empty()
+            && self.downgraded.is_empty()
+            && self.removed.is_empty()
+            && self.added.is_empty()
+    }
+}
+
+fn history_path(app_id: &str) -> Result<std::path::PathBuf, anywho::Error> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anywho!("Could not determine config directory"))?
+        .join(app_id);
+
+    if !config_dir.exists() {
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| anywho!("Failed to create config directory: {}", e))?;
+    }
+
+    Ok(config_dir.join(format!("history_v{}.ron", HISTORY_VERSION)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_package_diff() {
+        let diff = PackageDiff::parse(include_str!("fixtures/rpm_ostree_db_diff.txt"));
+
+        assert_eq!(
+            diff.upgraded,
+            vec![
+                "mesa-dri-drivers 24.1.1-1.fc40 -> 24.1.2-1.fc40",
+                "mesa-libEGL 24.1.1-1.fc40 -> 24.1.2-1.fc40",
+            ]
+        );
+        assert_eq!(
+            diff.downgraded,
+            vec!["firefox 128.0-1.fc40 -> 127.0.2-1.fc40"]
+        );
+        assert_eq!(diff.removed, vec!["toolbox-0.0.99.5-1.fc40.x86_64"]);
+        assert_eq!(diff.added, vec!["distrobox-1.7.2.1-1.fc40.noarch"]);
+        assert!(!diff.is_empty());
+        assert!(PackageDiff::parse("").is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+use std::collections::HashMap;
+
+use crate::app::core::run_command;
+use crate::app::core::update_applications::{
+    ExtensionPoint, Installation, RefKind, depends_on, get_extension_points,
+    get_installed_app_runtimes,
+};
+use crate::app::utils::date::format_date;
+
+/// Application or runtime installed on one of the flatpak installations
+#[derive(Debug, Clone)]
+pub struct InstalledFlatpak {
+    pub name: String,
+    pub id: String,
+    pub kind: RefKind,
+    pub installation: Installation,
+    pub origin: String,
+    pub version: String,
+    pub branch: String,
+    pub arch: String,
+    /// Installed size as reported by flatpak (Ej: 12.3 MB)
+    pub size: String,
+    /// Date the current version was deployed (Ej: 2025-05-01), may be empty
+    pub installed_on: String,
+}
+
+impl InstalledFlatpak {
+    /// Full flatpak ref (Ej: app/org.gnome.Calculator/x86_64/stable)
+    pub fn flatpak_ref(&self) -> String {
+        format!(
+            "{}/{}/{}/{}",
+            self.kind.prefix(),
+            self.id,
+            self.arch,
+            self.branch
+        )
+    }
+
+    /// Returns every installed application and runtime of both installations
+    pub async fn get_all() -> Result<Vec<InstalledFlatpak>, anywho::Error> {
+        let lists = [
+            (Installation::User, RefKind::App),
+            (Installation::User, RefKind::Runtime),
+            (Installation::System, RefKind::App),
+            (Installation::System, RefKind::Runtime),
+        ];
+
+        let results = iced::futures::future::join_all(
+            lists
+                .iter()
+                .map(|(installation, kind)| Self::get_installed(*installation, *kind)),
+        )
+        .await;
+
+        let mut flatpaks = Vec::new();
+        let mut errors = Vec::new();
+
+        for result in results {
+            match result {
+                Ok(installed) => flatpaks.extend(installed),
+                Err(err) => errors.push(err.to_string()),
+            }
+        }
+
+        // only fail if we couldn't list anything
+        if flatpaks.is_empty() && !errors.is_empty() {
+            return Err(anywho!("{}", errors.join(", ")));
+        }
+
+        flatpaks.sort_by_key(|flatpak| flatpak.name.to_lowercase());
+        Ok(flatpaks)
+    }
+
+    /// Returns the installed refs of the given kind of an installation using: flatpak list
+    async fn get_installed(
+        installation: Installation,
+        kind: RefKind,
+    ) -> Result<Vec<InstalledFlatpak>, anywho::Error> {
+        let output = run_command(
+            "flatpak",
+            &[
+                "list",
+                installation.flag(),
+                kind.flag(),
+                "--columns=name,application,version,branch,arch,origin,size",
+            ],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Error listing {} flatpaks: {}",
+                installation,
+                stderr.trim()
+            ));
+        }
+
+        let mut flatpaks =
+            parse_flatpak_list(&String::from_utf8_lossy(&output.stdout), installation, kind);
+
+        if flatpaks.is_empty() {
+            return Ok(flatpaks);
+        }
+
+        // the deploy date of the active checkout is the date the current version was installed
+        let deploy_paths: Vec<String> = flatpaks.iter().map(deploy_path).collect();
+        let mut args = vec!["-c", "%Y %n"];
+        args.extend(deploy_paths.iter().map(String::as_str));
+
+        if let Ok(output) = run_command("stat", &args).await {
+            let dates = parse_stat_dates(&String::from_utf8_lossy(&output.stdout));
+            for (flatpak, path) in flatpaks.iter_mut().zip(&deploy_paths) {
+                if let Some(timestamp) = dates.get(path) {
+                    flatpak.installed_on = format_date(*timestamp);
+                }
+            }
+        }
+
+        Ok(flatpaks)
+    }
+
+    /// Uninstalls the given ref, optionally deleting the application data (~/.var/app/<id>)
+    pub async fn uninstall(
+        installation: Installation,
+        flatpak_ref: String,
+        delete_data: bool,
+    ) -> Result<(), anywho::Error> {
+        let mut args = vec!["uninstall", "-y", "--noninteractive", installation.flag()];
+        if delete_data {
+            args.push("--delete-data");
+        }
+        args.push(&flatpak_ref);
+
+        let output = run_command("flatpak", &args).await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!(
+                "Failed to uninstall {}: {}",
+                flatpak_ref,
+                stderr.trim()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the refs (Ej: runtime/org.gnome.Platform/x86_64/46) of every installation that
+    /// no application uses: the installed runtimes and extensions that aren't pinned
+    pub async fn unused_preview() -> Result<Vec<(Installation, Vec<String>)>, anywho::Error> {
+        let mut apps = Vec::new();
+        let mut app_runtimes = Vec::new();
+
+        // user applications can use system runtimes, so we check the apps of both installations
+        for installation in Installation::ALL {
+            apps.extend(Self::get_installed(installation, RefKind::App).await?);
+            app_runtimes.extend(get_installed_app_runtimes(installation).await?);
+        }
+
+        // extensions are used through the extension points of the runtime or of the application itself
+        let mut refs: Vec<(String, String)> = app_runtimes
+            .iter()
+            .map(|(_, runtime)| {
+                let branch = runtime.rsplit('/').next().unwrap_or_default();
+                (format!("runtime/{}", runtime), branch.to_string())
+            })
+            .chain(
+                apps.iter()
+                    .map(|app| (app.flatpak_ref(), app.branch.clone())),
+            )
+            .collect();
+        refs.sort();
+        refs.dedup();
+
+        let extension_points: HashMap<String, Vec<ExtensionPoint>> =
+            iced::futures::future::join_all(refs.into_iter().map(
+                |(flatpak_ref, branch)| async move {
+                    let points = get_extension_points(&flatpak_ref, &branch)
+                        .await
+                        .unwrap_or_default();
+                    (flatpak_ref, points)
+                },
+            ))
+            .await
+            .into_iter()
+            .collect();
+
+        let mut preview = Vec::new();
+
+        for installation in Installation::ALL {
+            let runtimes = Self::get_installed(installation, RefKind::Runtime).await?;
+            let pins = get_pins(installation).await?;
+
+            let unused = unused_runtimes(&runtimes, &apps, &app_runtimes, &extension_points, &pins);
+            if !unused.is_empty() {
+                preview.push((installation, unused));
+            }
+        }
+
+        Ok(preview)
+    }
+
+    /// Removes exactly the refs of the [`InstalledFlatpak::unused_preview`], flatpak refuses
+    /// to remove a runtime that is still used instead of breaking the application
+    pub async fn uninstall_unused(
+        preview: Vec<(Installation, Vec<String>)>,
+    ) -> Result<(), anywho::Error> {
+        for (installation, refs) in preview {
+            let mut args = vec!["uninstall", "-y", "--noninteractive", installation.flag()];
+            args.extend(refs.iter().map(String::as_str));
+
+            let output = run_command("flatpak", &args).await?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(anywho!(
+                    "Failed to remove the unused {} runtimes: {}",
+                    installation,
+                    stderr.trim()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the patterns of the runtimes pinned on an installation (Ej: runtime/org.gnome.Platform/x86_64/46),
+/// flatpak uninstall --unused never removes them, using: flatpak pin
+async fn get_pins(installation: Installation) -> Result<Vec<String>, anywho::Error> {
+    let output = run_command("flatpak", &["pin", installation.flag()]).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!(
+            "Error listing the pinned {} runtimes: {}",
+            installation,
+            stderr.trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("runtime/"))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Returns the installed runtimes and extensions (Ej: runtime/org.gnome.Platform/x86_64/46) that no application uses
+/// and aren't pinned. Applications use their runtime, the extensions declared by their runtime
+/// or by themselves (Ej: the GL drivers) and their own extensions (Ej: their translations)
+fn unused_runtimes(
+    runtimes: &[InstalledFlatpak],
+    apps: &[InstalledFlatpak],
+    app_runtimes: &[(String, String)],
+    extension_points: &HashMap<String, Vec<ExtensionPoint>>,
+    pins: &[String],
+) -> Vec<String> {
+    let apps: Vec<(&str, &str, Vec<ExtensionPoint>)> = app_runtimes
+        .iter()
+        .map(|(app_id, runtime)| {
+            let app_points = apps
+                .iter()
+                .filter(|app| app.id == *app_id)
+                .filter_map(|app| extension_points.get(&app.flatpak_ref()));
+            let points = extension_points
+                .get(&format!("runtime/{}", runtime))
+                .into_iter()
+                .chain(app_points)
+                .flatten()
+                .cloned()
+                .collect();
+
+            (app_id.as_str(), runtime.as_str(), points)
+        })
+        .collect();
+
+    runtimes
+        .iter()
+        .filter(|runtime| !is_pinned(pins, runtime))
+        .filter(|runtime| {
+            !apps.iter().any(|(app_id, app_runtime, points)| {
+                depends_on(app_id, app_runtime, points, &runtime.id, &runtime.branch)
+            })
+        })
+        .map(|runtime| runtime.flatpak_ref())
+        .collect()
+}
+
+/// Checks if a runtime matches one of the pin patterns, * matches any part of the ref
+fn is_pinned(pins: &[String], runtime: &InstalledFlatpak) -> bool {
+    let parts = ["runtime", &runtime.id, &runtime.arch, &runtime.branch];
+
+    pins.iter().any(|pin| {
+        pin.split('/')
+            .zip(parts)
+            .all(|(pattern, part)| pattern == "*" || pattern.is_empty() || pattern == part)
+    })
+}
+
+/// Path of the active deployment of an installed ref (Ej: /var/lib/flatpak/app/org.gnome.Calculator/x86_64/stable/active)
+fn deploy_path(flatpak: &InstalledFlatpak) -> String {
+    flatpak
+        .installation
+        .path()
+        .join(flatpak.kind.prefix())
+        .join(&flatpak.id)
+        .join(&flatpak.arch)
+        .join(&flatpak.branch)
+        .join("active")
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Parses the output of: flatpak list --columns=name,application,version,branch,arch,origin,size
+fn parse_flatpak_list(
+    output: &str,
+    installation: Installation,
+    kind: RefKind,
+) -> Vec<InstalledFlatpak> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let [name, id, version, branch, arch, origin, size] = columns.as_slice() else {
+                return None;
+            };
+
+            if id.is_empty() || id.contains(char::is_whitespace) {
+                return None;
+            }
+
+            Some(InstalledFlatpak {
+                name: if name.is_empty() { id } else { name }.to_string(),
+                id: id.to_string(),
+                kind,
+                installation,
+                origin: origin.to_string(),
+                version: version.to_string(),
+                branch: branch.to_string(),
+                arch: arch.to_string(),
+                size: size.to_string(),
+                installed_on: String::new(),
+            })
+        })
+        .collect()
+}
+
+/// Parses the output of: stat -c "%Y %n" into a HashMap<path, modification timestamp>
+fn parse_stat_dates(output: &str) -> HashMap<String, u64> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (timestamp, path) = line.split_once(' ')?;
+            Some((path.to_string(), timestamp.parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::core::update_applications::parse_extension_points;
+
+    #[test]
+    fn parses_flatpak_list() {
+        let flatpaks = parse_flatpak_list(
+            include_str!("fixtures/flatpak_list.txt"),
+            Installation::System,
+            RefKind::App,
+        );
+
+        assert_eq!(flatpaks.len(), 2);
+        assert_eq!(flatpaks[0].name, "Calculator");
+        assert_eq!(
+            flatpaks[0].flatpak_ref(),
+            "app/org.gnome.Calculator/x86_64/stable"
+        );
+        assert_eq!(flatpaks[0].size, "6.1 MB");
+        // applications without a name use the ID
+        assert_eq!(flatpaks[1].name, "com.github.tchx84.Flatseal");
+        assert_eq!(flatpaks[1].version, "");
+    }
+
+    #[test]
+    fn finds_unused_runtimes() {
+        let runtimes = parse_flatpak_list(
+            include_str!("fixtures/flatpak_list_runtimes.txt"),
+            Installation::User,
+            RefKind::Runtime,
+        );
+        let apps = parse_flatpak_list(
+            include_str!("fixtures/flatpak_list.txt"),
+            Installation::System,
+            RefKind::App,
+        );
+        let app_runtimes = vec![
+            (
+                "org.gnome.Calculator".to_string(),
+                "org.gnome.Platform/x86_64/48".to_string(),
+            ),
+            (
+                "com.github.tchx84.Flatseal".to_string(),
+                "org.freedesktop.Platform/x86_64/24.08".to_string(),
+            ),
+        ];
+        let extension_points = HashMap::from([
+            (
+                "runtime/org.gnome.Platform/x86_64/48".to_string(),
+                parse_extension_points(
+                    include_str!("fixtures/flatpak_info_gnome_platform.txt"),
+                    "48",
+                ),
+            ),
+            (
+                "runtime/org.freedesktop.Platform/x86_64/24.08".to_string(),
+                parse_extension_points(
+                    include_str!("fixtures/flatpak_info_freedesktop_platform.txt"),
+                    "24.08",
+                ),
+            ),
+        ]);
+
+        assert_eq!(
+            unused_runtimes(&runtimes, &apps, &app_runtimes, &extension_points, &[]),
+            vec![
+                "runtime/org.freedesktop.Platform.GL.default/x86_64/23.08",
+                "runtime/org.gnome.Platform/x86_64/46",
+                "runtime/org.gnome.Platform.Locale/x86_64/46",
+            ]
+        );
+
+        // pinned runtimes are kept even if no application uses them
+        assert_eq!(
+            unused_runtimes(
+                &runtimes,
+                &apps,
+                &app_runtimes,
+                &extension_points,
+                &["runtime/org.gnome.Platform/*/46".to_string()],
+            ),
+            vec![
+                "runtime/org.freedesktop.Platform.GL.default/x86_64/23.08",
+                "runtime/org.gnome.Platform.Locale/x86_64/46",
+            ]
+        );
+    }
+}
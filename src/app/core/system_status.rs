@@ -1,7 +1,9 @@
 use anywho::anywho;
+use serde::Deserialize;
 use std::os::unix::process::ExitStatusExt;
 use std::process::ExitStatus;
 
+use crate::app::core::gpg_signature::{GpgSignature, is_container_image_origin};
 use crate::app::core::run_command;
 
 #[derive(Debug, Clone)]
@@ -9,22 +11,40 @@ pub struct Deployment {
     pub name: String,
     pub version: String,
     pub base_commit: String,
-    pub gpg_signature: String,
+    /// Checksum of the deployment itself, unlike base_commit it changes when packages are layered
+    pub checksum: String,
+    /// Full origin refspec (Ej: fedora:fedora/42/x86_64/silverblue, ostree-image-signed:docker://...)
+    pub origin: String,
+    pub gpg_signature: GpgSignature,
     pub layered_packages: String,
     pub is_pinned: bool,
+    pub is_booted: bool,
     pub index: i32,
 }
 
 impl Deployment {
-    /// Get's all the current deployments using rpm-ostree status
+    /// Get's all the current deployments using rpm-ostree status,
+    /// the checksum and full origin of each deployment are only shown by rpm-ostree status --json.
+    /// If the json output can't be read the deployments are returned without them
     pub async fn get_all() -> Result<Vec<Deployment>, anywho::Error> {
-        let output = run_command("rpm-ostree", &["status"]).await;
+        let (output, json_output) = iced::futures::future::join(
+            run_command("rpm-ostree", &["status"]),
+            run_command("rpm-ostree", &["status", "--json"]),
+        )
+        .await;
 
         let output = match output {
             Ok(output) => output,
             Err(err) => return Err(anywho!("Error fetching System Status: {}", err)),
         };
 
+        let mut details = json_output
+            .ok()
+            .and_then(|json_output| {
+                parse_details(&String::from_utf8_lossy(&json_output.stdout)).ok()
+            })
+            .unwrap_or_default();
+
         let stdout = String::from_utf8_lossy(&output.stdout);
         let mut deployments = Vec::new();
         let mut current_deployment: Option<Deployment> = None;
@@ -49,6 +69,9 @@ impl Deployment {
                     deployments.push(deployment);
                 }
 
+                // the booted deployment is marked with ●
+                let is_booted = line.starts_with('●');
+
                 // extract deployment name
                 let deployment_line = if line.starts_with('●') || line.starts_with('○') {
                     line.chars().skip(1).collect::<String>().trim().to_string()
@@ -63,9 +86,12 @@ impl Deployment {
                         name: String::new(),
                         version: String::new(),
                         base_commit: String::new(),
-                        gpg_signature: String::new(),
+                        checksum: String::new(),
+                        origin: String::new(),
+                        gpg_signature: GpgSignature::default(),
                         layered_packages: String::new(),
                         is_pinned: false,
+                        is_booted,
                         index: deployment_index,
                     });
                     deployment_index += 1;
@@ -78,9 +104,12 @@ impl Deployment {
                     name,
                     version,
                     base_commit: String::new(),
-                    gpg_signature: String::new(),
+                    checksum: String::new(),
+                    origin: String::new(),
+                    gpg_signature: GpgSignature::default(),
                     layered_packages: String::new(),
                     is_pinned: false,
+                    is_booted,
                     index: deployment_index,
                 });
 
@@ -92,6 +121,7 @@ impl Deployment {
                 && !line.contains(':')
                 && !line.starts_with("Version:")
                 && !line.starts_with("BaseCommit:")
+                && !line.starts_with("Commit:")
                 && !line.starts_with("Digest:")
                 && !line.starts_with("GPGSignature:")
                 && !line.starts_with("LayeredPackages:")
@@ -124,9 +154,12 @@ impl Deployment {
                     name,
                     version,
                     base_commit: String::new(),
-                    gpg_signature: String::new(),
+                    checksum: String::new(),
+                    origin: String::new(),
+                    gpg_signature: GpgSignature::default(),
                     layered_packages: String::new(),
                     is_pinned: false,
+                    is_booted: false,
                     index: deployment_index,
                 });
 
@@ -154,12 +187,15 @@ impl Deployment {
                 } else if line.starts_with("BaseCommit:") {
                     deployment.base_commit =
                         line.trim_start_matches("BaseCommit:").trim().to_string();
+                } else if line.starts_with("Commit:") && deployment.base_commit.is_empty() {
+                    // deployments without layered packages only show their Commit
+                    deployment.base_commit = line.trim_start_matches("Commit:").trim().to_string();
                 } else if line.starts_with("Digest:") {
                     // Bazzite uses Digest instead of BaseCommit
                     deployment.base_commit = line.trim_start_matches("Digest:").trim().to_string();
                 } else if line.starts_with("GPGSignature:") {
                     deployment.gpg_signature =
-                        line.trim_start_matches("GPGSignature:").trim().to_string();
+                        GpgSignature::parse(line.trim_start_matches("GPGSignature:"));
                 } else if line.starts_with("LayeredPackages:") {
                     deployment.layered_packages = line
                         .trim_start_matches("LayeredPackages:")
@@ -185,9 +221,41 @@ impl Deployment {
             return Err(anywho!("No deployments found in rpm-ostree status output"));
         }
 
+        // match both outputs by the base commit, deployments that share it are taken in order
+        for deployment in deployments.iter_mut() {
+            let Some(position) = details
+                .iter()
+                .position(|details| details.base_commit() == deployment.base_commit)
+            else {
+                continue;
+            };
+
+            let details = details.remove(position);
+            deployment.checksum = details.checksum;
+            deployment.origin = details
+                .origin
+                .or(details.container_image_reference)
+                .unwrap_or_default();
+        }
+
         Ok(deployments)
     }
 
+    /// Returns the version number without the origin ref (Ej: 42.20250601.0)
+    pub fn display_version(&self) -> String {
+        self.version
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Checks if this deployment should be flagged for not having a valid GPG signature,
+    /// signed container images are verified by the container policy instead of GPG
+    pub fn has_untrusted_signature(&self) -> bool {
+        self.gpg_signature.is_untrusted() && !is_container_image_origin(&self.name)
+    }
+
     /// Helper function to detect if a line looks like a deployment name
     fn is_deployment_line(line: &str) -> bool {
         let trimmed = line.trim();
@@ -195,6 +263,7 @@ impl Deployment {
         // skip obvious field lines
         if trimmed.starts_with("Version:")
             || trimmed.starts_with("BaseCommit:")
+            || trimmed.starts_with("Commit:")
             || trimmed.starts_with("Digest:")
             || trimmed.starts_with("GPGSignature:")
             || trimmed.starts_with("LayeredPackages:")
@@ -308,4 +377,118 @@ impl Deployment {
             Err(err) => Err(anywho!("{}", err)),
         }
     }
+
+    /// Deletes the given deployment using: pkexec ostree admin undeploy <index>
+    pub async fn undeploy_deployment(deployment_index: i32) -> Result<(), anywho::Error> {
+        let output = run_command(
+            "pkexec",
+            &["ostree", "admin", "undeploy", &deployment_index.to_string()],
+        )
+        .await;
+
+        match output {
+            Ok(output) => {
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    if output.status == ExitStatus::from_raw(32256) {
+                        return Err(anywho!("Permision denied"));
+                    }
+
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+
+                    Err(anywho!(
+                        "{}",
+                        if !stderr.is_empty() {
+                            stderr.trim()
+                        } else {
+                            stdout.trim()
+                        }
+                    ))
+                }
+            }
+            Err(err) => Err(anywho!("{}", err)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StatusJson {
+    deployments: Vec<DeploymentJson>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct DeploymentJson {
+    checksum: String,
+    /// Missing for deployments of container images
+    #[serde(default)]
+    origin: Option<String>,
+    #[serde(rename = "container-image-reference", default)]
+    container_image_reference: Option<String>,
+    /// Only set for deployments with layered packages
+    #[serde(rename = "base-checksum", default)]
+    base_checksum: Option<String>,
+    #[serde(rename = "container-image-reference-digest", default)]
+    container_image_reference_digest: Option<String>,
+}
+
+impl DeploymentJson {
+    /// The commit shown by rpm-ostree status as BaseCommit, Commit or Digest
+    fn base_commit(&self) -> &str {
+        self.container_image_reference_digest
+            .as_deref()
+            .or(self.base_checksum.as_deref())
+            .unwrap_or(&self.checksum)
+    }
+}
+
+/// Parses the deployments of: rpm-ostree status --json
+fn parse_details(output: &str) -> Result<Vec<DeploymentJson>, anywho::Error> {
+    let status: StatusJson = serde_json::from_str(output)
+        .map_err(|e| anywho!("Error parsing rpm-ostree status: {}", e))?;
+
+    Ok(status.deployments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_deployment_details() {
+        let details = parse_details(include_str!("fixtures/rpm_ostree_status.json")).unwrap();
+
+        assert_eq!(details.len(), 3);
+        assert_eq!(
+            details[0],
+            DeploymentJson {
+                checksum: "e7a4a1b0f6b5cd8b1e0c3e9a8e3e4fb6b8d1f1d0c2a6e6b4b7a2e9c1d3f5a7b9"
+                    .to_string(),
+                origin: Some("fedora:fedora/42/x86_64/kinoite".to_string()),
+                container_image_reference: None,
+                base_checksum: Some(
+                    "5c1f0e7d9b3a2c4e6f8a0b1d3c5e7f9a1b2c4d6e8f0a1b3c5d7e9f1a2b4c6d8e".to_string()
+                ),
+                container_image_reference_digest: None,
+            }
+        );
+        assert_eq!(
+            details[0].base_commit(),
+            "5c1f0e7d9b3a2c4e6f8a0b1d3c5e7f9a1b2c4d6e8f0a1b3c5d7e9f1a2b4c6d8e"
+        );
+        assert_eq!(
+            details[1].base_commit(),
+            "3b9d2e1f0a8c7b6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d"
+        );
+        assert_eq!(details[2].origin, None);
+        assert_eq!(
+            details[2].container_image_reference.as_deref(),
+            Some("ostree-image-signed:docker://ghcr.io/ublue-os/bazzite:stable")
+        );
+        assert_eq!(
+            details[2].base_commit(),
+            "sha256:4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c"
+        );
+    }
 }
@@ -1,27 +1,79 @@
-// TODO: Explore using tokio::Command instead of the std:: one
-
 use anywho::anywho;
+use iced::futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::process::Command;
+use std::path::PathBuf;
 
-use crate::app::core::run_command;
+use crate::app::core::appstream::{self, AppMetadata, MetadataCache, Release};
+use crate::app::core::flatpak_permissions::{Permission, PermissionKind};
+use crate::app::core::{read_output_segments, run_command, spawn_command};
 
 #[derive(Debug, Clone)]
 pub struct Application {
     pub name: String,
     pub app_id: String,
     pub icon: Option<AppIcon>,
-    //pub current_version: String,
+    pub current_version: String,
     pub latest_version: String,
+    /// Releases newer than the installed version from the local appstream cache, newest first
+    pub release_notes: Vec<Release>,
+    pub installation: Installation,
+    /// Remote the application is installed from (Ej: flathub)
+    pub origin: String,
+    pub branch: String,
+    pub arch: String,
+    /// Set if the update of the application is being held back
+    pub hold: Option<Hold>,
+    /// Permissions the update grants that the installed version doesn't have,
+    /// only compared once the application is about to be updated
+    pub new_permissions: Vec<(PermissionKind, String)>,
     pub application_status: ApplicationStatus,
 }
 
+/// Runtime or extension with an available update (Ej: org.freedesktop.Platform.GL.default)
+#[derive(Debug, Clone)]
+pub struct Runtime {
+    pub runtime_id: String,
+    pub latest_version: String,
+    pub installation: Installation,
+    pub origin: String,
+    pub branch: String,
+    pub arch: String,
+    /// IDs of the installed applications that use this runtime or extension
+    pub dependent_apps: Vec<String>,
+    pub status: ApplicationStatus,
+}
+
 #[derive(Debug, Clone)]
 pub enum AppIcon {
     Svg { path: String },
     Image { path: String },
 }
 
+impl AppIcon {
+    /// Returns the icon at the given path depending on its extension
+    pub fn from_path(path: &std::path::Path) -> Option<AppIcon> {
+        let path_string = path.to_string_lossy().into_owned();
+
+        match path.extension().and_then(|ext| ext.to_str())? {
+            "svg" => Some(AppIcon::Svg { path: path_string }),
+            "png" | "jpg" | "jpeg" | "gif" | "bmp" | "ico" | "tiff" => {
+                Some(AppIcon::Image { path: path_string })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why an application with an available update is not being updated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hold {
+    /// Masked with flatpak mask, flatpak update skips it
+    Masked,
+    /// The user chose to skip this version, newer ones are offered again
+    IgnoredVersion,
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub enum ApplicationStatus {
     Updating,
@@ -29,12 +81,106 @@ pub enum ApplicationStatus {
     NotUpdating,
 }
 
+/// Flatpak installation an application lives in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Installation {
+    User,
+    /// Flatpak's own default
+    #[default]
+    System,
+}
+
+impl Installation {
+    pub const ALL: [Installation; 2] = [Installation::User, Installation::System];
+
+    /// Path of the installation on the host (Ej: /var/lib/flatpak)
+    pub fn path(&self) -> PathBuf {
+        match self {
+            Installation::User => dirs::home_dir()
+                .unwrap_or_default()
+                .join(".local/share/flatpak"),
+            Installation::System => PathBuf::from("/var/lib/flatpak"),
+        }
+    }
+
+    /// Flag to pass to flatpak to target this installation
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Installation::User => "--user",
+            Installation::System => "--system",
+        }
+    }
+}
+
+impl std::fmt::Display for Installation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Installation::User => write!(f, "user"),
+            Installation::System => write!(f, "system"),
+        }
+    }
+}
+
+/// Kind of flatpak ref
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefKind {
+    App,
+    /// Runtimes and extensions (Ej: org.freedesktop.Platform.GL.default)
+    Runtime,
+}
+
+impl RefKind {
+    pub fn flag(&self) -> &'static str {
+        match self {
+            RefKind::App => "--app",
+            RefKind::Runtime => "--runtime",
+        }
+    }
+
+    /// First part of a ref of this kind (Ej: app/org.gnome.Calculator/x86_64/stable)
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            RefKind::App => "app",
+            RefKind::Runtime => "runtime",
+        }
+    }
+}
+
+/// An available update as listed by flatpak remote-ls --updates
+#[derive(Debug, Clone, PartialEq)]
+struct AvailableUpdate {
+    app_id: String,
+    version: String,
+    branch: String,
+    arch: String,
+    origin: String,
+    flatpak_ref: String,
+    /// Matches one of the flatpak mask patterns of the installation
+    masked: bool,
+}
+
 impl Application {
-    /// Returns a Vector of all [`Application`] that have available updates
-    pub async fn get_all_available_updates() -> Result<Vec<Application>, anywho::Error> {
+    /// Full flatpak ref of the application (Ej: app/org.gnome.Calculator/x86_64/stable)
+    pub fn flatpak_ref(&self) -> String {
+        format!("app/{}/{}/{}", self.app_id, self.arch, self.branch)
+    }
+
+    pub fn update_target(&self) -> UpdateTarget {
+        UpdateTarget {
+            installation: self.installation,
+            flatpak_ref: self.flatpak_ref(),
+            name: self.name.clone(),
+        }
+    }
+
+    /// Returns a Vector of all [`Application`] that have available updates,
+    /// their names, icons and release notes come from the appstream caches and are kept on disk
+    pub async fn get_all_available_updates(
+        app_id: &str,
+    ) -> Result<Vec<Application>, anywho::Error> {
         let mut applications = Vec::new();
 
-        let available_updates = match Self::get_available_updates().await {
+        let available_updates = match get_available_updates(RefKind::App).await {
             Ok(updates) => updates,
             Err(e) => {
                 eprintln!("Failed to get available updates: {}", e);
@@ -42,215 +188,229 @@ impl Application {
             }
         };
 
-        for (app_id, latest_version) in available_updates {
-            // if let Some(current_version) = installed_apps.get(&app_id) {
-            let icon_path = Self::get_app_icon(&app_id);
-            let display_name = Self::get_app_display_name(&app_id)
-                .await
-                .unwrap_or(app_id.clone());
+        let installations: Vec<Installation> = Installation::ALL
+            .into_iter()
+            .filter(|installation| available_updates.iter().any(|(i, _)| i == installation))
+            .collect();
 
-            applications.push(Application {
-                name: display_name,
-                app_id,
-                icon: icon_path,
-                //current_version: current_version.to_string(),
-                latest_version,
-                application_status: ApplicationStatus::default(),
-            });
-            // }
-        }
-
-        Ok(applications)
-    }
+        let installed_versions: HashMap<(Installation, String), String> =
+            iced::futures::future::join_all(
+                installations
+                    .iter()
+                    .map(|installation| get_installed_versions(*installation)),
+            )
+            .await
+            .into_iter()
+            .zip(installations)
+            .filter_map(|(result, installation)| result.ok().map(|v| (installation, v)))
+            .flat_map(|(installation, versions)| {
+                versions
+                    .into_iter()
+                    .map(move |(flatpak_ref, version)| ((installation, flatpak_ref), version))
+            })
+            .collect();
 
-    /// Returns the available updates that can actually be updated as HashMap<app_id, version>
-    async fn get_available_updates() -> Result<HashMap<String, String>, anywho::Error> {
-        use std::sync::Arc;
-        use tokio::sync::Mutex;
-        use tokio::task;
+        // every remote has its own appstream cache, we only read each one once
+        let mut appstream_sources: Vec<(Installation, String, String)> = available_updates
+            .iter()
+            .map(|(installation, update)| {
+                (*installation, update.origin.clone(), update.arch.clone())
+            })
+            .collect();
+        appstream_sources.sort_by(|a, b| (a.0.flag(), &a.1, &a.2).cmp(&(b.0.flag(), &b.1, &b.2)));
+        appstream_sources.dedup();
 
-        println!("Checking for updates...");
+        let source_keys: Vec<String> = appstream_sources
+            .iter()
+            .map(|(installation, origin, arch)| appstream::source_key(*installation, origin, arch))
+            .collect();
+        let revisions: Vec<String> =
+            iced::futures::future::join_all(appstream_sources.iter().map(
+                |(installation, origin, arch)| appstream::revision(*installation, origin, arch),
+            ))
+            .await;
 
-        let updates = Arc::new(Mutex::new(HashMap::new()));
-        let installations = vec!["--user", "--system"];
-        let mut handles = Vec::new();
+        let mut cache = MetadataCache::load(app_id).await;
 
-        for installation_flag in installations {
-            let updates = updates.clone();
-            let installation = installation_flag.to_string();
+        let source_of = |installation: Installation, update: &AvailableUpdate| {
+            appstream_sources
+                .iter()
+                .position(|(i, origin, arch)| {
+                    *i == installation && *origin == update.origin && *arch == update.arch
+                })
+                .unwrap_or_default()
+        };
 
-            let handle = task::spawn(async move {
-                println!(
-                    "Checking {} installation...",
-                    if installation == "--user" {
-                        "user"
-                    } else {
-                        "system"
-                    }
-                );
+        // only the applications missing from the cache need their appstream cache parsed again
+        let missing: Vec<(usize, Installation, &AvailableUpdate)> = available_updates
+            .iter()
+            .map(|(installation, update)| (source_of(*installation, update), *installation, update))
+            .filter(|(source, _, update)| {
+                cache
+                    .get(&source_keys[*source], &revisions[*source], &update.app_id)
+                    .is_none()
+            })
+            .collect();
 
-                // get all available updates with versions in one call
-                let all_updates_task = task::spawn_blocking({
-                    let installation = installation.clone();
-                    move || {
-                        if super::is_flatpak() {
-                            Command::new("flatpak-spawn")
-                                .args([
-                                    "--host",
-                                    "flatpak",
-                                    "remote-ls",
-                                    &installation,
-                                    "--updates",
-                                    "--app",
-                                    "--columns=application,version",
-                                ])
-                                .output()
-                        } else {
-                            Command::new("flatpak")
-                                .args([
-                                    "remote-ls",
-                                    &installation,
-                                    "--updates",
-                                    "--app",
-                                    "--columns=application,version",
-                                ])
-                                .output()
+        // each appstream cache is parsed once, on a blocking thread, for all its missing applications
+        let appstreams: Vec<HashMap<String, (AppMetadata, Option<String>)>> =
+            iced::futures::future::join_all(appstream_sources.iter().enumerate().map(
+                |(index, (installation, origin, arch))| {
+                    let app_ids: Vec<String> = missing
+                        .iter()
+                        .filter(|(source, _, _)| *source == index)
+                        .map(|(_, _, update)| update.app_id.clone())
+                        .collect();
+                    async move {
+                        if app_ids.is_empty() {
+                            return HashMap::new();
                         }
-                    }
-                });
-
-                // get the list of actually updatable apps
-                let updatable_apps_task = task::spawn_blocking({
-                    let installation = installation.clone();
-                    move || {
-                        if super::is_flatpak() {
-                            Command::new("flatpak-spawn")
-                                .args(["--host", "flatpak", "update", &installation])
-                                .stdin(std::process::Stdio::piped())
-                                .stdout(std::process::Stdio::piped())
-                                .stderr(std::process::Stdio::piped())
-                                .spawn()
-                                .and_then(|mut child| {
-                                    // send 'n' to decline the update, so we just get the list
-                                    if let Some(mut stdin) = child.stdin.take() {
-                                        use std::io::Write;
-                                        let _ = stdin.write_all(b"n\n");
-                                    }
-                                    child.wait_with_output()
-                                })
-                        } else {
-                            Command::new("flatpak")
-                                .args(["update", &installation])
-                                .stdin(std::process::Stdio::piped())
-                                .stdout(std::process::Stdio::piped())
-                                .stderr(std::process::Stdio::piped())
-                                .spawn()
-                                .and_then(|mut child| {
-                                    // send 'n' to decline the update, so we just get the list
-                                    if let Some(mut stdin) = child.stdin.take() {
-                                        use std::io::Write;
-                                        let _ = stdin.write_all(b"n\n");
-                                    }
-                                    child.wait_with_output()
-                                })
+
+                        match appstream::load(*installation, origin, arch).await {
+                            Ok(xml) => appstream::read_components(xml, app_ids).await,
+                            Err(_) => HashMap::new(),
                         }
                     }
-                });
-
-                // Wait for both tasks to complete
-                let (all_updates_result, updatable_apps_result) =
-                    tokio::join!(all_updates_task, updatable_apps_task);
-
-                // Parse available updates into a HashMap
-                let mut available_versions = HashMap::new();
-
-                if let Ok(Ok(cmd_output)) = all_updates_result {
-                    if cmd_output.status.success() {
-                        let output_str = String::from_utf8_lossy(&cmd_output.stdout);
-                        for line in output_str.lines() {
-                            let parts: Vec<&str> = line.split_whitespace().collect();
-                            if parts.len() >= 2 {
-                                available_versions
-                                    .insert(parts[0].to_string(), parts[1].to_string());
-                            }
-                        }
+                },
+            ))
+            .await;
+
+        let loaded: Vec<AppMetadata> = iced::futures::future::join_all(missing.iter().map(
+            |(source, installation, update)| {
+                let (mut metadata, cached_icon) = appstreams[*source]
+                    .get(&update.app_id)
+                    .cloned()
+                    .unwrap_or_default();
+                async move {
+                    metadata.icon = appstream::cache_icon(
+                        app_id,
+                        *installation,
+                        &update.origin,
+                        &update.arch,
+                        &update.app_id,
+                        cached_icon,
+                    )
+                    .await;
+
+                    // not every application is on the appstream data of its remote
+                    if metadata.name.is_empty() {
+                        metadata.name =
+                            Self::get_app_display_name(*installation, &update.flatpak_ref)
+                                .await
+                                .unwrap_or(update.app_id.clone());
                     }
-                }
 
-                // parse updatable apps
-                if let Ok(Ok(cmd_output)) = updatable_apps_result {
-                    let output_str = String::from_utf8_lossy(&cmd_output.stdout);
-                    let mut local_updates = HashMap::new();
-                    let mut found_list = false;
+                    metadata
+                }
+            },
+        ))
+        .await;
 
-                    for line in output_str.lines() {
-                        let trimmed = line.trim();
+        for ((source, _, update), metadata) in missing.iter().zip(loaded) {
+            cache.insert(
+                &source_keys[*source],
+                &revisions[*source],
+                update.app_id.clone(),
+                metadata,
+            );
+        }
 
-                        // look for the start of the numbered list
-                        if !found_list && trimmed.starts_with("1.") {
-                            found_list = true;
-                        }
+        for (installation, update) in &available_updates {
+            let source = source_of(*installation, update);
+            let metadata = cache
+                .get(&source_keys[source], &revisions[source], &update.app_id)
+                .cloned()
+                .unwrap_or_default();
 
-                        // if we found the list, parse numbered entries
-                        if found_list {
-                            // parse lines like: "1.   org.gnome.Calculator stable  u   fedora  <   2,5 MB"
-                            if let Some(number_end) = trimmed.find('.') {
-                                if trimmed[..number_end]
-                                    .chars()
-                                    .all(|c| c.is_ascii_digit() || c.is_whitespace())
-                                {
-                                    let after_number = &trimmed[number_end + 1..].trim();
-                                    let parts: Vec<&str> =
-                                        after_number.split_whitespace().collect();
-
-                                    if !parts.is_empty() {
-                                        let app_id = parts[0];
-
-                                        // look up version from pre-fetched map
-                                        if let Some(version) = available_versions.get(app_id) {
-                                            println!(
-                                                "Found updatable app: {} -> {}",
-                                                app_id, version
-                                            );
-                                            local_updates
-                                                .insert(app_id.to_string(), version.clone());
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+            let current_version = installed_versions
+                .get(&(*installation, update.flatpak_ref.clone()))
+                .cloned()
+                .unwrap_or_default();
 
-                    let mut global_updates = updates.lock().await;
-                    global_updates.extend(local_updates);
-                }
+            applications.push(Application {
+                name: if metadata.name.is_empty() {
+                    update.app_id.clone()
+                } else {
+                    metadata.name
+                },
+                app_id: update.app_id.clone(),
+                icon: metadata.icon.as_deref().and_then(AppIcon::from_path),
+                release_notes: appstream::newer_releases(&metadata.releases, &current_version),
+                current_version,
+                latest_version: update.version.clone(),
+                installation: *installation,
+                origin: update.origin.clone(),
+                branch: update.branch.clone(),
+                arch: update.arch.clone(),
+                hold: update.masked.then_some(Hold::Masked),
+                new_permissions: Vec::new(),
+                application_status: ApplicationStatus::default(),
             });
-
-            handles.push(handle);
         }
 
-        // wait for all installations to be checked
-        for handle in handles {
-            if let Err(e) = handle.await {
-                eprintln!("Installation check task panicked: {:?}", e);
+        // the cache only speeds up the next check, the updates are still valid without it
+        let _ = cache.save(app_id).await;
+
+        Ok(applications)
+    }
+
+    /// Returns the given applications with the permissions their update grants that the installed
+    /// version doesn't have. Applications that couldn't be compared are left without new permissions
+    pub async fn with_new_permissions(mut applications: Vec<Application>) -> Vec<Application> {
+        let new_permissions = iced::futures::future::join_all(applications.iter().map(|app| {
+            Permission::new_permissions(app.installation, app.origin.clone(), app.flatpak_ref())
+        }))
+        .await;
+
+        for (app, new_permissions) in applications.iter_mut().zip(new_permissions) {
+            if let Ok(new_permissions) = new_permissions {
+                app.new_permissions = new_permissions;
             }
         }
 
-        let updates = Arc::try_unwrap(updates)
-            .map_err(|_| anywho!("Failed to unwrap Arc"))?
-            .into_inner();
+        applications
+    }
+
+    /// Holds back the updates of an application using: flatpak mask <app_id>
+    pub async fn mask(installation: Installation, app_id: String) -> Result<(), anywho::Error> {
+        let output = run_command("flatpak", &["mask", installation.flag(), &app_id]).await?;
 
-        if !updates.is_empty() {
-            println!("Found {} total updatable apps", updates.len());
-            Ok(updates)
-        } else {
-            Err(anywho!("No updates found"))
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!("Failed to hold {}: {}", app_id, stderr.trim()));
         }
+
+        Ok(())
+    }
+
+    /// Releases an application held back with flatpak mask: flatpak mask --remove <app_id>
+    pub async fn unmask(installation: Installation, app_id: String) -> Result<(), anywho::Error> {
+        let output = run_command(
+            "flatpak",
+            &["mask", "--remove", installation.flag(), &app_id],
+        )
+        .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anywho!("Failed to release {}: {}", app_id, stderr.trim()));
+        }
+
+        Ok(())
     }
 
     /// Get the display name for an application
-    async fn get_app_display_name(app_id: &str) -> Result<String, anywho::Error> {
-        let output = run_command("flatpak", &["info", "--show-metadata", app_id]).await?;
+    async fn get_app_display_name(
+        installation: Installation,
+        flatpak_ref: &str,
+    ) -> Result<String, anywho::Error> {
+        let output = run_command(
+            "flatpak",
+            &["info", installation.flag(), "--show-metadata", flatpak_ref],
+        )
+        .await?;
+
+        let app_id = flatpak_ref.split('/').nth(1).unwrap_or(flatpak_ref);
 
         if !output.status.success() {
             return Ok(app_id.to_string()); // fallback to app ID
@@ -269,112 +429,872 @@ impl Application {
 
         Ok(app_id.to_string())
     }
+}
 
-    fn get_app_icon(app_id: &str) -> Option<AppIcon> {
-        freedesktop_icons::lookup(app_id)
-            .force_svg()
-            .with_cache()
-            .with_size(256)
-            .find()
-            .and_then(|path| {
-                if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
-                    match ext {
-                        "svg" => Some(AppIcon::Svg {
-                            path: path.to_string_lossy().into_owned(),
-                        }),
-                        "png" | "jpg" | "jpeg" | "gif" | "bmp" | "ico" | "tiff" => {
-                            Some(AppIcon::Image {
-                                path: path.to_string_lossy().into_owned(),
-                            })
-                        }
-                        _ => None,
-                    }
-                } else {
-                    None
-                }
-            })
+impl Runtime {
+    /// Full flatpak ref of the runtime (Ej: runtime/org.gnome.Platform/x86_64/48)
+    pub fn flatpak_ref(&self) -> String {
+        format!("runtime/{}/{}/{}", self.runtime_id, self.arch, self.branch)
     }
 
-    /// Update a specific application
-    pub async fn update(app_id: String) -> Result<(), UpdateError> {
-        let output = run_command("flatpak", &["update", "-y", &app_id])
+    pub fn update_target(&self) -> UpdateTarget {
+        UpdateTarget {
+            installation: self.installation,
+            flatpak_ref: self.flatpak_ref(),
+            name: self.runtime_id.clone(),
+        }
+    }
+
+    /// Returns a Vector of all [`Runtime`] (runtimes and extensions) that have available updates
+    pub async fn get_all_available_updates() -> Result<Vec<Runtime>, anywho::Error> {
+        let mut available_updates = get_available_updates(RefKind::Runtime)
             .await
-            .map_err(|e| UpdateError::CommandFailed(anywho!("{}", e)))?;
+            .map_err(|e| anywho!("Failed to get available runtime updates: {}", e))?;
 
-        if !output.status.success() {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            return Err(UpdateError::UpdateFailed(format!(
-                "Failed to update {}: {}",
-                app_id, error_msg
-            )));
+        // masked runtimes are skipped by flatpak update, so they are not really updatable
+        available_updates.retain(|(_, update)| !update.masked);
+
+        if available_updates.is_empty() {
+            return Ok(Vec::new());
         }
 
-        println!("Successfully updated: {}", app_id);
-        Ok(())
+        // user applications can use system runtimes, so we check the apps of both installations
+        let installed_apps: Vec<(String, String)> = iced::futures::future::join_all(
+            Installation::ALL
+                .into_iter()
+                .map(get_installed_app_runtimes),
+        )
+        .await
+        .into_iter()
+        .filter_map(Result::ok)
+        .flatten()
+        .collect();
+
+        // extensions are resolved through the extension points of the runtime each app uses
+        let mut runtimes: Vec<String> = installed_apps
+            .iter()
+            .map(|(_, runtime)| runtime.clone())
+            .collect();
+        runtimes.sort();
+        runtimes.dedup();
+
+        let extension_points: HashMap<String, Vec<ExtensionPoint>> =
+            iced::futures::future::join_all(runtimes.into_iter().map(|runtime| async move {
+                let branch = runtime.rsplit('/').next().unwrap_or_default();
+                let points = get_extension_points(&format!("runtime/{}", runtime), branch)
+                    .await
+                    .unwrap_or_default();
+                (runtime, points)
+            }))
+            .await
+            .into_iter()
+            .collect();
+
+        Ok(available_updates
+            .into_iter()
+            .map(|(installation, update)| {
+                let mut dependent_apps: Vec<String> = installed_apps
+                    .iter()
+                    .filter(|(app_id, runtime)| {
+                        depends_on(
+                            app_id,
+                            runtime,
+                            extension_points.get(runtime).map_or(&[], Vec::as_slice),
+                            &update.app_id,
+                            &update.branch,
+                        )
+                    })
+                    .map(|(app_id, _)| app_id.clone())
+                    .collect();
+                dependent_apps.sort();
+                dependent_apps.dedup();
+
+                Runtime {
+                    runtime_id: update.app_id,
+                    latest_version: update.version,
+                    installation,
+                    origin: update.origin,
+                    branch: update.branch,
+                    arch: update.arch,
+                    dependent_apps,
+                    status: ApplicationStatus::default(),
+                }
+            })
+            .collect())
     }
+}
+
+/// Ref to update on a flatpak transaction
+#[derive(Debug, Clone)]
+pub struct UpdateTarget {
+    pub installation: Installation,
+    pub flatpak_ref: String,
+    /// Name shown on the results (Ej: Calculator)
+    pub name: String,
+}
 
-    /// Update all applications with available updates
-    pub async fn update_all(
-        apps_to_update: Vec<Application>,
-    ) -> Result<Vec<UpdateResult>, UpdateError> {
+/// Progress of a flatpak update, refs are identified by their installation and full ref
+#[derive(Debug, Clone)]
+pub enum UpdateEvent {
+    /// Flatpak started updating the ref
+    Started(Installation, String),
+    /// Completion percentage (0-100) of the ref that is being updated
+    Progress(Installation, String, f32),
+    /// The ref has been updated
+    Finished(Installation, String),
+    /// The ref failed to update, with the error
+    Failed(Installation, String, String),
+    /// Every transaction has finished, with the result of each ref
+    Done(Vec<UpdateResult>),
+}
+
+/// Updates the given refs with one transaction per installation, streaming the progress of every ref.
+/// A failing ref doesn't stop the others, so the refs that were already updated stay updated
+pub fn update_with_progress(
+    targets: Vec<UpdateTarget>,
+) -> impl iced::futures::Stream<Item = UpdateEvent> + Send + 'static {
+    iced::stream::channel(100, async move |mut sender| {
         let mut results = Vec::new();
 
-        let output = run_command("flatpak", &["update", "-y"])
-            .await
-            .map_err(|e| UpdateError::CommandFailed(anywho!("{}", e)))?;
-
-        if output.status.success() {
-            for app in apps_to_update {
-                results.push(UpdateResult {
-                    app_name: app.name,
-                    success: true,
-                    error_message: None,
-                });
+        for installation in Installation::ALL {
+            let installation_targets: Vec<&UpdateTarget> = targets
+                .iter()
+                .filter(|target| target.installation == installation)
+                .collect();
+
+            if !installation_targets.is_empty() {
+                results.extend(
+                    run_transaction(installation, &installation_targets, &mut sender).await,
+                );
             }
-            println!("Successfully updated all applications");
-        } else {
-            // if bulk update fails, try individual updates
-            for app in apps_to_update {
-                match Self::update(app.app_id).await {
-                    Ok(()) => results.push(UpdateResult {
-                        app_name: app.name,
-                        success: true,
-                        error_message: None,
-                    }),
-                    Err(e) => results.push(UpdateResult {
-                        app_name: app.name,
-                        success: false,
-                        error_message: Some(e.to_string()),
-                    }),
+        }
+
+        let _ = sender.send(UpdateEvent::Done(results)).await;
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RefState {
+    Pending,
+    Running,
+    Finished,
+    Failed(String),
+}
+
+/// Runs flatpak update for the given refs of an installation, sending the progress events.
+/// Every ref gets exactly one Finished or Failed event
+async fn run_transaction(
+    installation: Installation,
+    targets: &[&UpdateTarget],
+    sender: &mut iced::futures::channel::mpsc::Sender<UpdateEvent>,
+) -> Vec<UpdateResult> {
+    let failed_results = |error: String| -> Vec<UpdateResult> {
+        targets
+            .iter()
+            .map(|target| UpdateResult {
+                installation,
+                flatpak_ref: target.flatpak_ref.clone(),
+                app_name: target.name.clone(),
+                success: false,
+                error_message: Some(error.clone()),
+            })
+            .collect()
+    };
+
+    let mut args = vec!["update", "-y", "--noninteractive", installation.flag()];
+    args.extend(targets.iter().map(|target| target.flatpak_ref.as_str()));
+
+    let mut child = match spawn_command("flatpak", &args) {
+        Ok(child) => child,
+        Err(e) => return failed_results(format!("Command execution failed: {}", e)),
+    };
+
+    // stdout and stderr are read at the same time so errors arrive next to the progress of their ref
+    let mut segments = std::pin::pin!(read_output_segments(&mut child));
+
+    let mut states = vec![RefState::Pending; targets.len()];
+    let mut current: Option<usize> = None;
+    let mut last_error = String::new();
+
+    while let Some(segment) = segments.next().await {
+        match parse_transaction_segment(&segment) {
+            Some(TransactionLine::Operation(flatpak_ref)) => {
+                if let Some(index) = current
+                    && states[index] == RefState::Running
+                {
+                    states[index] = RefState::Finished;
+                    let _ = sender
+                        .send(UpdateEvent::Finished(
+                            installation,
+                            targets[index].flatpak_ref.clone(),
+                        ))
+                        .await;
+                }
+
+                // other refs (Ej: new dependencies) may be part of the transaction
+                current = targets
+                    .iter()
+                    .position(|target| ref_matches(&target.flatpak_ref, &flatpak_ref));
+
+                if let Some(index) = current {
+                    states[index] = RefState::Running;
+                    let _ = sender
+                        .send(UpdateEvent::Started(
+                            installation,
+                            targets[index].flatpak_ref.clone(),
+                        ))
+                        .await;
+                }
+            }
+            Some(TransactionLine::Progress(progress)) => {
+                if let Some(index) = current {
+                    let _ = sender
+                        .send(UpdateEvent::Progress(
+                            installation,
+                            targets[index].flatpak_ref.clone(),
+                            progress,
+                        ))
+                        .await;
+                }
+            }
+            Some(TransactionLine::Error(error)) => {
+                last_error = error.clone();
+                if let Some(index) = current
+                    && states[index] == RefState::Running
+                {
+                    states[index] = RefState::Failed(error.clone());
+                    let _ = sender
+                        .send(UpdateEvent::Failed(
+                            installation,
+                            targets[index].flatpak_ref.clone(),
+                            error,
+                        ))
+                        .await;
+                }
+            }
+            None => {}
+        }
+    }
+
+    let success = match child.wait().await {
+        Ok(status) => status.success(),
+        Err(e) => {
+            last_error = e.to_string();
+            false
+        }
+    };
+
+    let mut results = Vec::new();
+    for (target, state) in targets.iter().zip(states) {
+        let error = match state {
+            RefState::Finished => None,
+            RefState::Failed(error) => Some(error),
+            RefState::Pending | RefState::Running => {
+                // the refs still without a result get their only event now
+                let event = if success {
+                    UpdateEvent::Finished(installation, target.flatpak_ref.clone())
+                } else {
+                    UpdateEvent::Failed(
+                        installation,
+                        target.flatpak_ref.clone(),
+                        last_error.clone(),
+                    )
+                };
+                let _ = sender.send(event).await;
+
+                (!success).then(|| last_error.clone())
+            }
+        };
+
+        results.push(UpdateResult {
+            installation,
+            flatpak_ref: target.flatpak_ref.clone(),
+            app_name: target.name.clone(),
+            success: error.is_none(),
+            error_message: error,
+        });
+    }
+
+    results
+}
+
+/// Returns the available updates of the given kind that can actually be updated on every installation
+async fn get_available_updates(
+    kind: RefKind,
+) -> Result<Vec<(Installation, AvailableUpdate)>, anywho::Error> {
+    let checks = Installation::ALL
+        .into_iter()
+        .map(|installation| get_installation_updates(installation, kind));
+
+    let mut updates = Vec::new();
+    let mut errors = Vec::new();
+
+    for (installation, result) in Installation::ALL
+        .into_iter()
+        .zip(iced::futures::future::join_all(checks).await)
+    {
+        match result {
+            Ok(installation_updates) => updates.extend(
+                installation_updates
+                    .into_iter()
+                    .map(|update| (installation, update)),
+            ),
+            Err(err) => errors.push(err.to_string()),
+        }
+    }
+
+    // only fail if we couldn't check any installation
+    if updates.is_empty() && errors.len() == Installation::ALL.len() {
+        return Err(anywho!("{}", errors.join(", ")));
+    }
+
+    Ok(updates)
+}
+
+/// Returns the available updates of the given installation, marking the ones
+/// flatpak update would skip, using: flatpak remote-ls --updates and flatpak mask
+async fn get_installation_updates(
+    installation: Installation,
+    kind: RefKind,
+) -> Result<Vec<AvailableUpdate>, anywho::Error> {
+    let output = run_command(
+        "flatpak",
+        &[
+            "remote-ls",
+            installation.flag(),
+            "--updates",
+            kind.flag(),
+            "--columns=application,version,branch,arch,origin,ref",
+        ],
+    )
+    .await
+    .map_err(|e| anywho!("{}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!(
+            "Error checking {} updates: {}",
+            installation,
+            stderr.trim()
+        ));
+    }
+
+    let mut updates = parse_remote_ls_updates(&String::from_utf8_lossy(&output.stdout));
+
+    if let Ok(output) = run_command("flatpak", &["mask", installation.flag()]).await
+        && output.status.success()
+    {
+        let patterns = parse_mask_patterns(&String::from_utf8_lossy(&output.stdout));
+        for update in updates.iter_mut() {
+            update.masked = patterns
+                .iter()
+                .any(|p| mask_matches(p, kind, &update.app_id));
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Returns the installed version of every application of the given installation by ref,
+/// using: flatpak list --app --columns=application,arch,branch,version
+async fn get_installed_versions(
+    installation: Installation,
+) -> Result<HashMap<String, String>, anywho::Error> {
+    let output = run_command(
+        "flatpak",
+        &[
+            "list",
+            installation.flag(),
+            "--app",
+            "--columns=application,arch,branch,version",
+        ],
+    )
+    .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!("{}", stderr.trim()));
+    }
+
+    Ok(parse_installed_versions(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Returns the installed applications of the given installation with the runtime they use,
+/// using: flatpak list --app --columns=application,runtime
+pub async fn get_installed_app_runtimes(
+    installation: Installation,
+) -> Result<Vec<(String, String)>, anywho::Error> {
+    let output = run_command(
+        "flatpak",
+        &[
+            "list",
+            installation.flag(),
+            "--app",
+            "--columns=application,runtime",
+        ],
+    )
+    .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!("{}", stderr.trim()));
+    }
+
+    Ok(parse_app_runtimes(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Returns the extension points of the given ref (Ej: runtime/org.gnome.Platform/x86_64/48),
+/// using: flatpak info -m <ref>
+pub async fn get_extension_points(
+    flatpak_ref: &str,
+    branch: &str,
+) -> Result<Vec<ExtensionPoint>, anywho::Error> {
+    let output = run_command("flatpak", &["info", "-m", flatpak_ref]).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anywho!("{}", stderr.trim()));
+    }
+
+    Ok(parse_extension_points(
+        &String::from_utf8_lossy(&output.stdout),
+        branch,
+    ))
+}
+
+/// Extension point declared on the metadata of a runtime or application (Ej: [Extension org.freedesktop.Platform.GL])
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionPoint {
+    id: String,
+    /// Branches of the extension that can be used
+    versions: Vec<String>,
+    /// Extensions whose id is under this one can be used too (Ej: org.freedesktop.Platform.GL.default)
+    subdirectories: bool,
+}
+
+impl ExtensionPoint {
+    fn provides(&self, runtime_id: &str, branch: &str) -> bool {
+        let id_matches = runtime_id == self.id
+            || (self.subdirectories && runtime_id.starts_with(&format!("{}.", self.id)));
+
+        id_matches && self.versions.iter().any(|version| version == branch)
+    }
+}
+
+/// Meaningful line of the output of flatpak update
+#[derive(Debug, Clone, PartialEq)]
+enum TransactionLine {
+    /// Flatpak started working on a ref, it may not have the kind (Ej: org.gnome.Calculator/x86_64/stable)
+    Operation(String),
+    /// Completion percentage of the current operation
+    Progress(f32),
+    Error(String),
+}
+
+/// Parses a segment (split by \r or \n) of the output of: flatpak update --noninteractive
+fn parse_transaction_segment(segment: &str) -> Option<TransactionLine> {
+    let segment = segment.trim();
+    let mut words = segment.split_whitespace();
+
+    match words.next()? {
+        "Updating" | "Installing" => {
+            // the ref is the first word with slashes, progress lines (Ej: Updating 1/3… 45%) have none
+            if let Some(flatpak_ref) = words.clone().find(|word| word.matches('/').count() >= 2) {
+                return Some(TransactionLine::Operation(flatpak_ref.to_string()));
+            }
+        }
+        "Error:" | "error:" => {
+            let error = segment.split_once(':').map(|(_, error)| error.trim())?;
+            return Some(TransactionLine::Error(error.to_string()));
+        }
+        _ => {}
+    }
+
+    segment
+        .split_whitespace()
+        .rev()
+        .find_map(|word| word.strip_suffix('%')?.parse::<f32>().ok())
+        .map(|progress| TransactionLine::Progress(progress.clamp(0., 100.)))
+}
+
+/// Checks if a ref printed by flatpak (that may not have the kind) is the given full ref
+fn ref_matches(full_ref: &str, printed_ref: &str) -> bool {
+    full_ref == printed_ref || full_ref.ends_with(&format!("/{}", printed_ref))
+}
+
+/// Parses the output of: flatpak remote-ls --updates --columns=application,version,branch,arch,origin,ref
+/// columns are tab separated and the version may be empty
+fn parse_remote_ls_updates(output: &str) -> Vec<AvailableUpdate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let app_id = *columns.first()?;
+
+            // skip headers or anything that isn't an application ID
+            if app_id.is_empty() || app_id.contains(char::is_whitespace) || !app_id.contains('.') {
+                return None;
+            }
+
+            let column = |i: usize| columns.get(i).copied().unwrap_or_default().to_string();
+
+            Some(AvailableUpdate {
+                app_id: app_id.to_string(),
+                version: column(1),
+                branch: column(2),
+                arch: column(3),
+                origin: column(4),
+                flatpak_ref: column(5),
+                masked: false,
+            })
+        })
+        .collect()
+}
+
+/// Parses the output of: flatpak mask, one pattern per line (Ej: org.gnome.Calculator)
+fn parse_mask_patterns(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.ends_with(':') && !line.starts_with("No "))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Checks if a mask pattern (Ej: org.gnome.*, app/org.gnome.Calculator/x86_64/stable) matches the given ID
+fn mask_matches(pattern: &str, kind: RefKind, id: &str) -> bool {
+    // ref patterns are matched against the ID part of the ref
+    let pattern = match pattern.split('/').collect::<Vec<&str>>().as_slice() {
+        [prefix, pattern_id, ..] if *prefix == kind.prefix() => *pattern_id,
+        ["app" | "runtime", ..] => return false,
+        _ => pattern,
+    };
+
+    match pattern.strip_suffix('*') {
+        Some(prefix) => id.starts_with(prefix),
+        None => pattern == id,
+    }
+}
+
+/// Parses the output of: flatpak list --app --columns=application,arch,branch,version
+/// into a HashMap<ref, version> (Ej: app/org.gnome.Calculator/x86_64/stable, 47.1)
+fn parse_installed_versions(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split('\t').map(str::trim).collect();
+
+            match columns.as_slice() {
+                [app_id, arch, branch, version, ..] if !app_id.contains(char::is_whitespace) => {
+                    Some((
+                        format!("app/{}/{}/{}", app_id, arch, branch),
+                        version.to_string(),
+                    ))
+                }
+                // apps without a version have an empty last column
+                [app_id, arch, branch] if !app_id.contains(char::is_whitespace) => {
+                    Some((format!("app/{}/{}/{}", app_id, arch, branch), String::new()))
                 }
+                _ => None,
             }
+        })
+        .collect()
+}
+
+/// Parses the output of: flatpak list --app --columns=application,runtime
+/// into (app_id, runtime) pairs (Ej: org.gnome.Calculator, org.gnome.Platform/x86_64/48)
+fn parse_app_runtimes(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (app_id, runtime) = line.split_once('\t')?;
+            let (app_id, runtime) = (app_id.trim(), runtime.trim());
+
+            if app_id.is_empty() || app_id.contains(char::is_whitespace) || runtime.is_empty() {
+                return None;
+            }
+
+            Some((app_id.to_string(), runtime.to_string()))
+        })
+        .collect()
+}
+
+/// Parses the [Extension ...] groups of the metadata of a runtime, extensions without
+/// a version or versions key use the branch of the runtime
+pub fn parse_extension_points(metadata: &str, runtime_branch: &str) -> Vec<ExtensionPoint> {
+    let mut points: Vec<ExtensionPoint> = Vec::new();
+    let mut in_extension = false;
+
+    for line in metadata.lines().map(str::trim) {
+        if let Some(group) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_extension = match group.strip_prefix("Extension ") {
+                Some(id) => {
+                    points.push(ExtensionPoint {
+                        id: id.trim().to_string(),
+                        versions: Vec::new(),
+                        subdirectories: false,
+                    });
+                    true
+                }
+                None => false,
+            };
+            continue;
+        }
+
+        let (Some(point), Some((key, value))) = (points.last_mut(), line.split_once('=')) else {
+            continue;
+        };
+        if !in_extension {
+            continue;
         }
 
-        Ok(results)
+        match key.trim() {
+            // versions takes precedence over version
+            "versions" => {
+                point.versions = value
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|version| !version.is_empty())
+                    .map(str::to_string)
+                    .collect();
+            }
+            "version" if point.versions.is_empty() => {
+                point.versions = vec![value.trim().to_string()]
+            }
+            "subdirectories" => point.subdirectories = value.trim() == "true",
+            _ => {}
+        }
     }
+
+    for point in points.iter_mut() {
+        if point.versions.is_empty() {
+            point.versions.push(runtime_branch.to_string());
+        }
+    }
+
+    points
 }
 
-/// Represents the result of an update operation
+/// Checks if an application depends on the given runtime or extension, either because it's
+/// the runtime the application uses, an extension that runtime declares (Ej: the GL drivers)
+/// or an extension of the application itself (Ej: its translations)
+pub fn depends_on(
+    app_id: &str,
+    app_runtime: &str,
+    extension_points: &[ExtensionPoint],
+    runtime_id: &str,
+    runtime_branch: &str,
+) -> bool {
+    let mut parts = app_runtime.split('/');
+    let base_id = parts.next().unwrap_or_default();
+    let base_branch = parts.nth(1).unwrap_or_default();
+
+    (runtime_id == base_id && runtime_branch == base_branch)
+        || extension_points
+            .iter()
+            .any(|point| point.provides(runtime_id, runtime_branch))
+        || runtime_id.starts_with(&format!("{}.", app_id))
+}
+
+/// Represents the result of updating a ref
 #[derive(Debug, Clone)]
 pub struct UpdateResult {
+    pub installation: Installation,
+    pub flatpak_ref: String,
     pub app_name: String,
     pub success: bool,
     pub error_message: Option<String>,
 }
 
-#[derive(Debug, Clone)]
-pub enum UpdateError {
-    CommandFailed(anywho::Error),
-    UpdateFailed(String),
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl std::fmt::Display for UpdateError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            UpdateError::CommandFailed(e) => write!(f, "Command execution failed: {}", e),
-            UpdateError::UpdateFailed(msg) => write!(f, "Update failed: {}", msg),
-        }
+    #[test]
+    fn parses_remote_ls_updates() {
+        let updates =
+            parse_remote_ls_updates(include_str!("fixtures/flatpak_remote_ls_updates.txt"));
+
+        assert_eq!(updates.len(), 3);
+        assert_eq!(
+            updates[0],
+            AvailableUpdate {
+                app_id: "org.mozilla.firefox".to_string(),
+                version: "139.0.4".to_string(),
+                branch: "stable".to_string(),
+                arch: "x86_64".to_string(),
+                origin: "flathub".to_string(),
+                flatpak_ref: "app/org.mozilla.firefox/x86_64/stable".to_string(),
+                masked: false,
+            }
+        );
+        assert_eq!(updates[1].origin, "fedora");
+        // applications without a version are still updatable
+        assert_eq!(updates[2].version, "");
+        assert_eq!(
+            updates[2].flatpak_ref,
+            "app/com.github.tchx84.Flatseal/x86_64/stable"
+        );
     }
-}
 
-impl std::error::Error for UpdateError {}
+    #[test]
+    fn parses_empty_remote_ls_updates() {
+        assert!(parse_remote_ls_updates("").is_empty());
+    }
+
+    #[test]
+    fn parses_mask_patterns() {
+        let patterns = parse_mask_patterns(include_str!("fixtures/flatpak_mask.txt"));
+
+        assert_eq!(
+            patterns,
+            vec![
+                "org.gnome.Calculator",
+                "org.kde.*",
+                "app/org.mozilla.firefox/x86_64/stable"
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_mask_patterns() {
+        assert!(mask_matches(
+            "org.gnome.Calculator",
+            RefKind::App,
+            "org.gnome.Calculator"
+        ));
+        assert!(!mask_matches(
+            "org.gnome.Calculator",
+            RefKind::App,
+            "org.gnome.Calculator2"
+        ));
+        assert!(mask_matches("org.kde.*", RefKind::App, "org.kde.kdenlive"));
+        assert!(mask_matches(
+            "app/org.mozilla.firefox/x86_64/stable",
+            RefKind::App,
+            "org.mozilla.firefox"
+        ));
+        assert!(!mask_matches(
+            "runtime/org.gnome.Platform/x86_64/48",
+            RefKind::App,
+            "org.gnome.Platform"
+        ));
+        assert!(mask_matches(
+            "runtime/org.gnome.Platform/x86_64/48",
+            RefKind::Runtime,
+            "org.gnome.Platform"
+        ));
+    }
+
+    #[test]
+    fn matches_runtime_dependents() {
+        let apps = parse_app_runtimes(include_str!("fixtures/flatpak_list_app_runtimes.txt"));
+
+        assert_eq!(apps.len(), 3);
+        assert_eq!(
+            apps[0],
+            (
+                "org.gnome.Calculator".to_string(),
+                "org.gnome.Platform/x86_64/48".to_string()
+            )
+        );
+
+        let extension_points: HashMap<&str, Vec<ExtensionPoint>> = HashMap::from([
+            (
+                "org.gnome.Platform/x86_64/48",
+                parse_extension_points(
+                    include_str!("fixtures/flatpak_info_gnome_platform.txt"),
+                    "48",
+                ),
+            ),
+            (
+                "org.freedesktop.Platform/x86_64/24.08",
+                parse_extension_points(
+                    include_str!("fixtures/flatpak_info_freedesktop_platform.txt"),
+                    "24.08",
+                ),
+            ),
+        ]);
+
+        let dependents = |runtime_id: &str, branch: &str| {
+            apps.iter()
+                .filter(|(app_id, runtime)| {
+                    depends_on(
+                        app_id,
+                        runtime,
+                        &extension_points[runtime.as_str()],
+                        runtime_id,
+                        branch,
+                    )
+                })
+                .map(|(app_id, _)| app_id.as_str())
+                .collect::<Vec<&str>>()
+        };
+
+        assert_eq!(
+            dependents("org.gnome.Platform", "48"),
+            vec!["org.gnome.Calculator"]
+        );
+        // apps on runtimes built on top of the freedesktop one use its GL drivers too
+        assert_eq!(
+            dependents("org.freedesktop.Platform.GL.default", "24.08"),
+            vec![
+                "org.gnome.Calculator",
+                "org.mozilla.firefox",
+                "com.github.tchx84.Flatseal"
+            ]
+        );
+        assert!(dependents("org.freedesktop.Platform.GL.default", "23.08").is_empty());
+        assert_eq!(
+            dependents("org.gnome.Platform.Locale", "48"),
+            vec!["org.gnome.Calculator"]
+        );
+        assert_eq!(
+            dependents("org.freedesktop.Platform.VAAPI.Intel", "24.08"),
+            vec![
+                "org.gnome.Calculator",
+                "org.mozilla.firefox",
+                "com.github.tchx84.Flatseal"
+            ]
+        );
+        assert_eq!(
+            dependents("org.mozilla.firefox.Locale", "stable"),
+            vec!["org.mozilla.firefox"]
+        );
+        assert!(dependents("org.kde.Platform", "6.9").is_empty());
+    }
+
+    #[test]
+    fn parses_transaction_output() {
+        let lines: Vec<TransactionLine> = include_str!("fixtures/flatpak_update_output.txt")
+            .split(['\r', '\n'])
+            .filter_map(parse_transaction_segment)
+            .collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                TransactionLine::Operation("org.gnome.Calculator/x86_64/stable".to_string()),
+                TransactionLine::Progress(12.),
+                TransactionLine::Progress(100.),
+                TransactionLine::Operation("app/org.mozilla.firefox/x86_64/stable".to_string()),
+                TransactionLine::Progress(40.),
+                TransactionLine::Error(
+                    "Failed to update app/org.mozilla.firefox/x86_64/stable: Not enough disk space"
+                        .to_string()
+                ),
+            ]
+        );
+
+        assert!(ref_matches(
+            "app/org.gnome.Calculator/x86_64/stable",
+            "org.gnome.Calculator/x86_64/stable"
+        ));
+        assert!(!ref_matches(
+            "app/org.gnome.Calculator/x86_64/stable",
+            "Calculator/x86_64/stable"
+        ));
+    }
+}
@@ -2,13 +2,17 @@ use anywho::anywho;
 use std::os::unix::process::ExitStatusExt;
 use std::process::ExitStatus;
 
+use crate::app::core::gpg_signature::{GpgSignature, is_container_image_origin};
 use crate::app::core::run_command;
+use crate::app::core::system_status::Deployment;
 
 #[derive(Debug, Clone)]
 pub struct SystemUpdate {
     pub version: String,
     pub commit: String,
-    pub gpg_signature: String,
+    pub gpg_signature: GpgSignature,
+    /// Origin of the booted deployment the update applies to (Ej: fedora, ostree-image-signed)
+    pub origin: String,
     pub sec_advisories: Option<String>,
     pub diff: String,
 }
@@ -16,7 +20,11 @@ pub struct SystemUpdate {
 impl SystemUpdate {
     // Check if a [`SystemUpdate`] is available using: rpm-ostree upgrade --check
     pub async fn check() -> Option<SystemUpdate> {
-        let output = run_command("rpm-ostree", &["upgrade", "--check"]).await;
+        let (output, deployments) = iced::futures::future::join(
+            run_command("rpm-ostree", &["upgrade", "--check"]),
+            Deployment::get_all(),
+        )
+        .await;
 
         let output = match output {
             Ok(output) => output,
@@ -73,7 +81,12 @@ impl SystemUpdate {
             Some(SystemUpdate {
                 version,
                 commit,
-                gpg_signature,
+                gpg_signature: GpgSignature::parse(&gpg_signature),
+                origin: deployments
+                    .ok()
+                    .and_then(|deployments| deployments.into_iter().find(|d| d.is_booted))
+                    .map(|booted| booted.name)
+                    .unwrap_or_default(),
                 sec_advisories,
                 diff,
             })
@@ -82,6 +95,12 @@ impl SystemUpdate {
         }
     }
 
+    /// Checks if this update should be flagged for not having a valid GPG signature,
+    /// signed container images are verified by the container policy instead of GPG
+    pub fn has_untrusted_signature(&self) -> bool {
+        self.gpg_signature.is_untrusted() && !is_container_image_origin(&self.origin)
+    }
+
     /// Update the system using: pkexec rpm-ostree upgrade
     pub async fn update() -> Result<(), anywho::Error> {
         let output = run_command("pkexec", &["rpm-ostree", "upgrade"]).await;
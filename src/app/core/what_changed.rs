@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use anywho::anywho;
+
+use crate::app::core::history::PackageDiff;
+use crate::app::core::run_command;
+use crate::app::core::system_status::Deployment;
+
+/// Packages that are worth highlighting when they change (kernel, graphics, desktop shells)
+const NOTABLE_PACKAGES: [&str; 9] = [
+    "kernel",
+    "mesa-dri-drivers",
+    "mesa-vulkan-drivers",
+    "gnome-shell",
+    "plasma-workspace",
+    "cosmic-session",
+    "xfce4-session",
+    "budgie-desktop",
+    "sway",
+];
+
+/// Summary of the changes between the previously booted deployment and the current one
+#[derive(Debug, Clone)]
+pub struct WhatChanged {
+    pub from_version: String,
+    pub from_checksum: String,
+    pub to_version: String,
+    pub to_checksum: String,
+    pub package_changes: PackageDiff,
+    /// Changes of the packages in [`NOTABLE_PACKAGES`] (Ej: kernel 6.14.4-200.fc42 -> 6.14.5-300.fc42)
+    pub notable_changes: Vec<String>,
+    /// Advisories fixed between both deployments (Ej: FEDORA-2025-1a2b3c4d5e Important/Sec. kernel-6.14.5-300.fc42.x86_64)
+    pub advisories: Vec<String>,
+}
+
+impl WhatChanged {
+    /// Returns the checksum of the booted deployment and, if it differs from the last seen one,
+    /// the summary of what changed between them (rpm-ostree db diff --advisories)
+    pub async fn check(
+        last_seen_checksum: Option<String>,
+    ) -> Result<(String, Option<WhatChanged>), anywho::Error> {
+        let deployments = Deployment::get_all().await?;
+
+        let booted = deployments
+            .iter()
+            .find(|d| d.is_booted)
+            .ok_or_else(|| anywho!("Could not find the booted deployment"))?;
+
+        let checksum = booted.checksum.clone();
+
+        // first run or same deployment as last time, nothing to show
+        let Some(last_seen_checksum) =
+            last_seen_checksum.filter(|c| !c.is_empty() && !checksum.is_empty() && *c != checksum)
+        else {
+            return Ok((checksum, None));
+        };
+
+        let from_version = deployments
+            .iter()
+            .find(|d| d.checksum == last_seen_checksum)
+            .map(|d| d.display_version())
+            .unwrap_or_default();
+
+        // the previous commit may have been pruned already, we still show the version change
+        let diff = match run_command(
+            "rpm-ostree",
+            &["db", "diff", "--advisories", &last_seen_checksum, &checksum],
+        )
+        .await
+        {
+            Ok(output) if output.status.success() => {
+                String::from_utf8_lossy(&output.stdout).to_string()
+            }
+            _ => String::new(),
+        };
+
+        let package_changes = PackageDiff::parse(&diff);
+        let notable_changes = package_changes
+            .upgraded
+            .iter()
+            .chain(&package_changes.downgraded)
+            .chain(&package_changes.added)
+            .chain(&package_changes.removed)
+            .filter(|line| NOTABLE_PACKAGES.contains(&package_name(line)))
+            .cloned()
+            .collect();
+
+        let summary = WhatChanged {
+            from_version,
+            from_checksum: last_seen_checksum,
+            to_version: booted.display_version(),
+            to_checksum: checksum.clone(),
+            package_changes,
+            notable_changes,
+            advisories: parse_advisories(&diff),
+        };
+
+        Ok((checksum, Some(summary)))
+    }
+}
+
+/// Gets the package name from a rpm-ostree db diff line, it can be either
+/// "kernel 6.14.4-200.fc42 -> 6.14.5-300.fc42" or "kernel-6.14.5-300.fc42.x86_64"
+fn package_name(line: &str) -> &str {
+    let first_word = line.split_whitespace().next().unwrap_or_default();
+
+    first_word
+        .char_indices()
+        .find(|(i, c)| {
+            *c == '-'
+                && first_word[i + 1..]
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_ascii_digit())
+        })
+        .map(|(i, _)| &first_word[..i])
+        .unwrap_or(first_word)
+}
+
+/// Parses the advisories section of rpm-ostree db diff --advisories
+fn parse_advisories(output: &str) -> Vec<String> {
+    let mut advisories = Vec::new();
+    let mut in_advisories = false;
+
+    for line in output.lines() {
+        if !line.starts_with(' ') {
+            let header = line.trim();
+            in_advisories =
+                header.starts_with("Advisories:") || header.starts_with("SecAdvisories:");
+
+            // some versions print the first advisory on the same line as the header
+            let inline = header.split_once(':').map(|(_, rest)| rest.trim());
+            if in_advisories && let Some(advisory) = inline.filter(|a| !a.is_empty()) {
+                advisories.push(advisory.split_whitespace().collect::<Vec<_>>().join(" "));
+            }
+        } else if in_advisories && !line.trim().is_empty() && !line.trim().starts_with("CVE-") {
+            // CVE lines are details of the advisory above them
+            advisories.push(line.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+
+    advisories
+}
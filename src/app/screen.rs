@@ -2,24 +2,42 @@
 
 pub mod about;
 pub mod config;
+pub mod containers;
+pub mod firmware;
+pub mod flatpak_remotes;
+pub mod installed_flatpaks;
+pub mod post_update_summary;
 pub mod rollback;
 pub mod system_status;
 pub mod update_applications;
+pub mod update_history;
 pub mod update_system;
 
 pub use about::About;
 pub use config::Config;
+pub use containers::Containers;
+pub use firmware::Firmware;
+pub use flatpak_remotes::FlatpakRemotes;
+pub use installed_flatpaks::InstalledFlatpaks;
+pub use post_update_summary::PostUpdateSummary;
 pub use rollback::Rollback;
 pub use system_status::SystemStatus;
 pub use update_applications::UpdateApplications;
+pub use update_history::UpdateHistory;
 pub use update_system::UpdateSystem;
 
 pub enum Screen {
     Welcome,
     UpdateSystem(UpdateSystem),
     UpdateApplications(UpdateApplications),
+    InstalledFlatpaks(InstalledFlatpaks),
+    FlatpakRemotes(FlatpakRemotes),
+    Containers(Containers),
+    Firmware(Firmware),
     Rollback(Rollback),
     SystemStatus(SystemStatus),
+    UpdateHistory(UpdateHistory),
+    PostUpdateSummary(PostUpdateSummary),
     Config(Config),
     About(About),
 }
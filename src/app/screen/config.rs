@@ -1,11 +1,13 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use iced::time::Instant;
-use iced::widget::{button, column, container, pick_list, text};
+use iced::widget::{button, column, container, pick_list, radio, row, text};
 use iced::{Alignment, Length, Subscription, Task, Theme};
 
 use crate::app::core::config::ApplicationTheme;
+use crate::app::core::update_applications::Installation;
 use crate::app::style::{icon_button_style, icon_svg_style};
+use crate::app::utils::ui::installation_label;
 use crate::{fl, icons};
 
 pub struct Config {
@@ -19,6 +21,8 @@ pub enum Message {
 
     /// Pick List Change Theme Callback
     ChangedTheme(ApplicationTheme),
+    /// Radio Change Default Installation Callback
+    ChangedDefaultInstallation(Installation),
 }
 
 pub struct State {
@@ -28,6 +32,7 @@ pub struct State {
 pub enum Action {
     Back,
     ChangedTheme(ApplicationTheme),
+    ChangedDefaultInstallation(Installation),
 }
 
 impl Config {
@@ -63,6 +68,20 @@ impl Config {
                 )
                 .width(Length::Fill)
             ]
+            .spacing(3.),
+            column![
+                text(fl!("default-installation")),
+                Installation::ALL
+                    .iter()
+                    .fold(row![].spacing(20.), |row, installation| {
+                        row.push(radio(
+                            installation_label(*installation),
+                            *installation,
+                            Some(self.state.config.default_installation),
+                            Message::ChangedDefaultInstallation,
+                        ))
+                    })
+            ]
             .spacing(3.)
         ]
         .width(Length::Fill)
@@ -96,6 +115,10 @@ impl Config {
                 self.state.config.theme = application_theme.clone();
                 Action::ChangedTheme(application_theme)
             }
+            Message::ChangedDefaultInstallation(installation) => {
+                self.state.config.default_installation = installation;
+                Action::ChangedDefaultInstallation(installation)
+            }
         }
     }
 
@@ -0,0 +1,598 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{
+    button, column, container, radio, row, scrollable, space, text, text_input, tooltip,
+};
+use iced::{Alignment, Element, Length, Subscription, Task};
+
+use crate::app::core::containers::{Container, ContainerManager, IMAGES};
+use crate::app::style::{
+    TabButtonPosition, danger_button_style, danger_icon_button_style, icon_button_style,
+    icon_svg_style, primary_button_style, rounded_input_combo_style, rounderer_box_container_style,
+    tab_button_style,
+};
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct Containers {
+    state: State,
+    containers: Vec<Container>,
+    /// Container tools installed on the host
+    managers: Vec<ContainerManager>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Reloads the containers
+    RefreshContainers,
+    /// Callback after loading the containers and the installed container tools
+    ContainersLoaded(Result<(Vec<Container>, Vec<ContainerManager>), anywho::Error>),
+
+    /// Starts the given container
+    StartContainer(String),
+    /// Stops the given container
+    StopContainer(String),
+    /// Upgrades the packages of the given distrobox container
+    UpgradeContainer(String),
+    /// Upgrades the packages of every distrobox container
+    UpgradeAll,
+    /// Asks to delete the given container, opens the confirmation
+    DeleteContainer(Container),
+    /// Confirms the deletion of the container pending confirmation
+    ConfirmDeleteContainer,
+
+    /// Opens the form to create a new container
+    OpenCreateContainer,
+    /// Callback when the name of the new container changes
+    CreateNameChanged(String),
+    /// Callback when selecting the image of the new container
+    CreateImageChanged(&'static str),
+    /// Callback when selecting the tool that creates the new container
+    CreateManagerChanged(ContainerManager),
+    /// Creates the container of the form
+    ConfirmCreateContainer,
+
+    /// Callback after starting, stopping, upgrading, creating or deleting a container
+    ContainerChanged(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    /// Changing a container, upgrading and creating may take a while
+    Working,
+    Ready,
+    CreateContainer {
+        name: String,
+        image: &'static str,
+        manager: ContainerManager,
+    },
+    ConfirmDelete {
+        container: Container,
+    },
+}
+
+impl Containers {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+                containers: Vec::new(),
+                managers: Vec::new(),
+            },
+            load_containers(),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::CreateContainer { .. } | State::ConfirmDelete { .. } => {
+                    self.state = State::Ready;
+                    Action::None
+                }
+                State::Working => Action::None,
+                State::Loading | State::Ready => Action::Back,
+            },
+            Message::RefreshContainers => {
+                self.state = State::Loading;
+                Action::Run(load_containers())
+            }
+            Message::ContainersLoaded(result) => {
+                self.state = State::Ready;
+                match result {
+                    Ok((containers, managers)) => {
+                        self.containers = containers;
+                        self.managers = managers;
+                        Action::None
+                    }
+                    Err(err) => {
+                        self.containers = Vec::new();
+                        Action::AddToast(Toast::error_toast(err))
+                    }
+                }
+            }
+            Message::StartContainer(name) => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Container::start(name),
+                    Message::ContainerChanged,
+                ))
+            }
+            Message::StopContainer(name) => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Container::stop(name),
+                    Message::ContainerChanged,
+                ))
+            }
+            Message::UpgradeContainer(name) => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Container::upgrade(name),
+                    Message::ContainerChanged,
+                ))
+            }
+            Message::UpgradeAll => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Container::upgrade_all(),
+                    Message::ContainerChanged,
+                ))
+            }
+            Message::DeleteContainer(container) => {
+                self.state = State::ConfirmDelete { container };
+                Action::None
+            }
+            Message::ConfirmDeleteContainer => {
+                let State::ConfirmDelete { container } = &self.state else {
+                    return Action::None;
+                };
+
+                let task = Task::perform(container.clone().delete(), Message::ContainerChanged);
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::OpenCreateContainer => {
+                let Some(manager) = self.managers.first() else {
+                    return Action::None;
+                };
+
+                self.state = State::CreateContainer {
+                    name: String::new(),
+                    image: IMAGES[0].1,
+                    manager: *manager,
+                };
+                Action::None
+            }
+            Message::CreateNameChanged(value) => {
+                if let State::CreateContainer { name, .. } = &mut self.state {
+                    *name = value;
+                }
+                Action::None
+            }
+            Message::CreateImageChanged(value) => {
+                if let State::CreateContainer { image, .. } = &mut self.state {
+                    *image = value;
+                }
+                Action::None
+            }
+            Message::CreateManagerChanged(value) => {
+                if let State::CreateContainer { manager, .. } = &mut self.state {
+                    *manager = value;
+                }
+                Action::None
+            }
+            Message::ConfirmCreateContainer => {
+                let State::CreateContainer {
+                    name,
+                    image,
+                    manager,
+                } = &self.state
+                else {
+                    return Action::None;
+                };
+
+                if name.trim().is_empty() {
+                    return Action::None;
+                }
+
+                let task = Task::perform(
+                    Container::create(*manager, name.trim().to_string(), image.to_string()),
+                    Message::ContainerChanged,
+                );
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::ContainerChanged(result) => {
+                self.state = State::Loading;
+                let task = load_containers();
+                match result {
+                    Ok(_) => Action::AddToastAndRun((
+                        Toast::success_toast(fl!("container-changes-applied")),
+                        task,
+                    )),
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading | State::Working => container(
+                column![
+                    text(if matches!(self.state, State::Loading) {
+                        fl!("loading")
+                    } else {
+                        fl!("applying-changes")
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready => self.list_view(),
+            State::CreateContainer {
+                name,
+                image,
+                manager,
+            } => create_container_view(name, image, *manager, &self.managers),
+            State::ConfirmDelete { container } => confirm_delete_view(container),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Working) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshContainers)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match &self.state {
+            State::Ready => iced::widget::stack![main_content, back_button, refresh_button].into(),
+            _ => iced::widget::stack![main_content, back_button].into(),
+        }
+    }
+
+    pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
+        Subscription::none()
+    }
+
+    fn list_view(&self) -> Element<'_, Message> {
+        let can_upgrade = self.managers.contains(&ContainerManager::Distrobox);
+
+        let list: Element<Message> = if self.managers.is_empty() && self.containers.is_empty() {
+            container(text(fl!("no-container-managers")))
+                .style(container::warning)
+                .width(Length::Fill)
+                .padding(10.)
+                .into()
+        } else if self.containers.is_empty() {
+            container(text(fl!("no-containers")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+                .into()
+        } else {
+            scrollable(
+                self.containers
+                    .iter()
+                    .fold(column![].spacing(5.), |col, container| {
+                        col.push(container_card(container, can_upgrade, true))
+                    }),
+            )
+            .into()
+        };
+
+        let has_distroboxes = self
+            .containers
+            .iter()
+            .any(|container| container.manager == ContainerManager::Distrobox);
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            row![
+                text(fl!("containers"))
+                    .width(Length::Fill)
+                    .size(18)
+                    .font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                button(text(fl!("upgrade-all")))
+                    .style(primary_button_style)
+                    .on_press_maybe(
+                        (can_upgrade && has_distroboxes).then_some(Message::UpgradeAll)
+                    ),
+                button(text(fl!("create-container")))
+                    .style(primary_button_style)
+                    .on_press_maybe(
+                        (!self.managers.is_empty()).then_some(Message::OpenCreateContainer)
+                    )
+            ]
+            .spacing(5.),
+            list
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+fn load_containers() -> Task<Message> {
+    Task::perform(
+        async {
+            let (containers, managers) =
+                iced::futures::future::join(Container::get_all(), ContainerManager::available())
+                    .await;
+            Ok((containers?, managers))
+        },
+        Message::ContainersLoaded,
+    )
+}
+
+fn container_card(
+    container: &Container,
+    can_upgrade: bool,
+    show_actions: bool,
+) -> Element<'_, Message> {
+    let details = [
+        container.manager.to_string(),
+        container.status.clone(),
+        container.size.clone(),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<String>>()
+    .join(" · ");
+
+    let actions: Element<Message> = if show_actions {
+        let icon_button = |icon: &'static str, tooltip_text: String, message: Message| {
+            tooltip(
+                button(icons::get_icon(icon, 18).style(icon_svg_style))
+                    .style(icon_button_style)
+                    .on_press(message),
+                iced::widget::container(text(tooltip_text))
+                    .padding(5.)
+                    .style(iced::widget::container::secondary),
+                tooltip::Position::Top,
+            )
+        };
+
+        // only distrobox knows how to upgrade the packages of a container
+        let upgrade_button: Element<Message> =
+            if can_upgrade && container.manager == ContainerManager::Distrobox {
+                button(text(fl!("upgrade")))
+                    .style(primary_button_style)
+                    .on_press(Message::UpgradeContainer(container.name.clone()))
+                    .into()
+            } else {
+                space().into()
+            };
+
+        let toggle_button = if container.running {
+            icon_button(
+                "media-playback-stop-symbolic",
+                fl!("stop"),
+                Message::StopContainer(container.name.clone()),
+            )
+        } else {
+            icon_button(
+                "media-playback-start-symbolic",
+                fl!("start"),
+                Message::StartContainer(container.name.clone()),
+            )
+        };
+
+        row![
+            upgrade_button,
+            toggle_button,
+            tooltip(
+                button(icons::get_icon("user-trash-full-symbolic", 18).style(icon_svg_style))
+                    .style(danger_icon_button_style)
+                    .on_press(Message::DeleteContainer(container.clone())),
+                iced::widget::container(text(fl!("delete-container")))
+                    .padding(5.)
+                    .style(iced::widget::container::secondary),
+                tooltip::Position::Top,
+            )
+        ]
+        .spacing(5.)
+        .align_y(Alignment::Center)
+        .into()
+    } else {
+        space().into()
+    };
+
+    iced::widget::container(
+        row![
+            column![
+                text(&container.name).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(&container.image).style(text::secondary).size(13),
+                text(details)
+                    .style(if container.running {
+                        text::success
+                    } else {
+                        text::secondary
+                    })
+                    .size(13)
+            ]
+            .spacing(2.)
+            .width(Length::Fill),
+            actions
+        ]
+        .align_y(Alignment::Center),
+    )
+    .style(rounderer_box_container_style)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+fn create_container_view<'a>(
+    name: &'a str,
+    image: &'static str,
+    manager: ContainerManager,
+    managers: &[ContainerManager],
+) -> Element<'a, Message> {
+    // the tool can only be chosen when both are installed
+    let managers_row: Element<Message> = if managers.len() > 1 {
+        let manager_button = |value: ContainerManager, position: TabButtonPosition| {
+            button(
+                text(value.to_string())
+                    .align_x(Alignment::Center)
+                    .width(Length::Fill),
+            )
+            .on_press(Message::CreateManagerChanged(value))
+            .style(move |t, s| tab_button_style(t, s, manager == value, position))
+            .width(Length::Fill)
+        };
+
+        row![
+            manager_button(ContainerManager::Distrobox, TabButtonPosition::Left),
+            manager_button(ContainerManager::Toolbox, TabButtonPosition::Right),
+        ]
+        .width(Length::Fill)
+        .into()
+    } else {
+        space().into()
+    };
+
+    let images = IMAGES
+        .iter()
+        .fold(column![].spacing(10.), |col, (label, value)| {
+            col.push(
+                row![
+                    radio(*label, *value, Some(image), Message::CreateImageChanged),
+                    text(*value).style(text::secondary).size(13)
+                ]
+                .spacing(10.)
+                .align_y(Alignment::Center),
+            )
+        });
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("create-container"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(fl!("create-container-description"))
+            .size(16)
+            .align_x(Alignment::Center),
+        managers_row,
+        text_input(fl!("container-name-placeholder").as_str(), name)
+            .on_input(Message::CreateNameChanged)
+            .on_paste(Message::CreateNameChanged)
+            .on_submit(Message::ConfirmCreateContainer)
+            .style(rounded_input_combo_style)
+            .padding(10.),
+        container(scrollable(images).width(Length::Fill))
+            .style(rounderer_box_container_style)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(10.),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("create")))
+                .style(primary_button_style)
+                .on_press_maybe(
+                    (!name.trim().is_empty()).then_some(Message::ConfirmCreateContainer)
+                )
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn confirm_delete_view(container: &Container) -> Element<'_, Message> {
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("delete-container"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(fl!("delete-container-confirmation"))
+            .size(18)
+            .align_x(Alignment::Center),
+        container_card(container, false, false),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("delete")))
+                .style(danger_button_style)
+                .on_press(Message::ConfirmDeleteContainer)
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
@@ -0,0 +1,425 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{
+    button, column, container, progress_bar, responsive, row, scrollable, space, text,
+};
+use iced::{Alignment, Element, Length, Subscription, Task};
+
+use crate::app::core::firmware::{self, FirmwareDevice, FirmwareEvent, FirmwareRelease};
+use crate::app::core::reboot;
+use crate::app::style::{
+    icon_button_style, icon_svg_style, primary_button_style, rounderer_box_container_style,
+};
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::spinners::linear::Linear;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct Firmware {
+    state: State,
+    devices: Vec<FirmwareDevice>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Downloads the latest firmware metadata and reloads the devices
+    RefreshDevices,
+    /// Callback after refreshing the firmware metadata
+    MetadataRefreshed(Result<(), anywho::Error>),
+    /// Callback after loading the devices and their available firmware
+    DevicesLoaded(Result<Vec<FirmwareDevice>, anywho::Error>),
+
+    /// Updates the firmware of the given device
+    Update(FirmwareDevice),
+    /// Progress of the running firmware update
+    UpdateProgress(FirmwareEvent),
+
+    /// Attempts to reboot the computer
+    RebootNow,
+    /// Callback presumably if reboot failed
+    RebootCallback(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    Ready,
+    Updating {
+        device_name: String,
+        /// What fwupd is doing (Ej: Writing…)
+        status: String,
+        progress: Option<f32>,
+    },
+    /// A device only applies its new firmware after rebooting
+    PendingReboot,
+}
+
+impl Firmware {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+                devices: Vec::new(),
+            },
+            Task::perform(FirmwareDevice::get_all(), Message::DevicesLoaded),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::Updating { .. } => Action::None,
+                State::Loading | State::Ready | State::PendingReboot => Action::Back,
+            },
+            Message::RefreshDevices => {
+                self.state = State::Loading;
+                Action::Run(Task::perform(
+                    FirmwareDevice::refresh_metadata(),
+                    Message::MetadataRefreshed,
+                ))
+            }
+            Message::MetadataRefreshed(result) => {
+                let task = Task::perform(FirmwareDevice::get_all(), Message::DevicesLoaded);
+                match result {
+                    Ok(_) => Action::Run(task),
+                    // the devices can still be listed with the metadata we already have
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+            Message::DevicesLoaded(result) => match result {
+                Ok(devices) => {
+                    self.state = if devices.iter().any(|device| device.needs_reboot) {
+                        State::PendingReboot
+                    } else {
+                        State::Ready
+                    };
+                    self.devices = devices;
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready;
+                    self.devices = Vec::new();
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::Update(device) => {
+                if !matches!(self.state, State::Ready) {
+                    return Action::None;
+                }
+
+                self.state = State::Updating {
+                    device_name: device.name,
+                    status: String::new(),
+                    progress: None,
+                };
+                Action::Run(Task::run(
+                    firmware::update(device.id),
+                    Message::UpdateProgress,
+                ))
+            }
+            Message::UpdateProgress(event) => {
+                let State::Updating {
+                    status, progress, ..
+                } = &mut self.state
+                else {
+                    return Action::None;
+                };
+
+                match event {
+                    FirmwareEvent::Status(new_status, new_progress) => {
+                        *status = new_status;
+                        *progress = new_progress;
+                        Action::None
+                    }
+                    FirmwareEvent::Done(result) => {
+                        // reloading tells if the new firmware needs a reboot to be applied
+                        self.state = State::Loading;
+                        let task = Task::perform(FirmwareDevice::get_all(), Message::DevicesLoaded);
+                        match result {
+                            Ok(_) => Action::AddToastAndRun((
+                                Toast::success_toast(fl!("firmware-updated")),
+                                task,
+                            )),
+                            Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                        }
+                    }
+                }
+            }
+            Message::RebootNow => Action::Run(Task::perform(reboot(), Message::RebootCallback)),
+            Message::RebootCallback(result) => match result {
+                Ok(_) => {
+                    self.state = State::Loading;
+                    Action::Run(Task::perform(
+                        FirmwareDevice::get_all(),
+                        Message::DevicesLoaded,
+                    ))
+                }
+                Err(err) => Action::AddToast(Toast::error_toast(err)),
+            },
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading => container(
+                column![
+                    text(fl!("checking-updates")),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready => self.list_view(),
+            State::Updating {
+                device_name,
+                status,
+                progress,
+            } => updating_view(device_name, status, *progress),
+            State::PendingReboot => column![
+                space().width(Length::Fill).height(Length::Fixed(35.)),
+                text(fl!("reboot-required"))
+                    .size(24)
+                    .font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .align_x(Alignment::Center),
+                text(fl!("reboot-message"))
+                    .size(18)
+                    .align_x(Alignment::Center),
+                button(text(fl!("reboot-now")))
+                    .style(primary_button_style)
+                    .on_press(Message::RebootNow)
+            ]
+            .padding(20.)
+            .spacing(10.)
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .align_x(Alignment::Center)
+            .into(),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Updating { .. }) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshDevices)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match &self.state {
+            State::Ready | State::PendingReboot => {
+                iced::widget::stack![main_content, back_button, refresh_button].into()
+            }
+            _ => iced::widget::stack![main_content, back_button].into(),
+        }
+    }
+
+    pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
+        Subscription::none()
+    }
+
+    fn list_view(&self) -> Element<'_, Message> {
+        let list: Element<Message> = if self.devices.is_empty() {
+            container(text(fl!("no-firmware-devices")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+                .into()
+        } else {
+            scrollable(
+                self.devices
+                    .iter()
+                    .fold(column![].spacing(5.), |col, device| {
+                        col.push(device_card(device))
+                    }),
+            )
+            .into()
+        };
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            text(fl!("firmware"))
+                .width(Length::Fill)
+                .size(18)
+                .font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            list
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+fn device_card(device: &FirmwareDevice) -> Element<'_, Message> {
+    let details = [device.vendor.clone(), device.summary.clone()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<String>>()
+        .join(" · ");
+
+    // fwupd lists the newest release first
+    let action: Element<Message> = match device.releases.first() {
+        Some(_) => button(text(fl!("update")))
+            .style(primary_button_style)
+            .on_press(Message::Update(device.clone()))
+            .into(),
+        None => text(fl!("firmware-up-to-date"))
+            .style(text::success)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .into(),
+    };
+
+    let releases = device
+        .releases
+        .iter()
+        .fold(column![].spacing(5.), |col, release| {
+            col.push(release_view(release))
+        });
+
+    container(
+        column![
+            row![
+                column![
+                    text(&device.name).font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                    text(details).style(text::secondary).size(13),
+                    text(format!("{}: {}", fl!("current-version"), device.version))
+                        .style(text::secondary)
+                        .size(13)
+                ]
+                .spacing(2.)
+                .width(Length::Fill),
+                action
+            ]
+            .align_y(Alignment::Center),
+            releases
+        ]
+        .spacing(10.),
+    )
+    .style(rounderer_box_container_style)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+fn release_view(release: &FirmwareRelease) -> Element<'_, Message> {
+    let mut header = vec![format!("{}: {}", fl!("available-version"), release.version)];
+    if !release.date.is_empty() {
+        header.push(release.date.clone());
+    }
+    if !release.urgency.is_empty() {
+        header.push(format!("{}: {}", fl!("urgency"), release.urgency));
+    }
+
+    let urgent = matches!(release.urgency.as_str(), "high" | "critical");
+
+    column![
+        text(header.join(" · "))
+            .style(if urgent { text::warning } else { text::primary })
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .size(13),
+        text(release.notes.join("\n")).size(13)
+    ]
+    .spacing(2.)
+    .into()
+}
+
+fn updating_view<'a>(
+    device_name: &'a str,
+    status: &'a str,
+    progress: Option<f32>,
+) -> Element<'a, Message> {
+    let progress: Element<Message> = match progress {
+        Some(value) => progress_bar(0.0..=100.0, value)
+            .girth(Length::Fixed(6.))
+            .into(),
+        None => responsive(|bounds| {
+            container(
+                Linear::new()
+                    .easing(&easing::STANDARD_DECELERATE)
+                    .cycle_duration(std::time::Duration::from_secs_f32(10.0))
+                    .width(Length::Fixed(bounds.width / 2.0)),
+            )
+            .width(Length::Fill)
+            .align_x(Alignment::Center)
+            .into()
+        })
+        .into(),
+    };
+
+    container(
+        column![
+            text(format!("{} {}", fl!("updating-firmware"), device_name)),
+            text(fl!("updating-warning")),
+            text(status).style(text::secondary),
+            progress
+        ]
+        .spacing(10.)
+        .height(Length::Shrink)
+        .width(Length::Fill)
+        .align_x(Alignment::Center),
+    )
+    .align_x(Alignment::Center)
+    .align_y(Alignment::Center)
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .padding(20.)
+    .into()
+}
@@ -0,0 +1,533 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input, tooltip};
+use iced::{Alignment, Element, Length, Subscription, Task};
+
+use crate::app::core::flatpak_remotes::Remote;
+use crate::app::core::update_applications::Installation;
+use crate::app::style::{
+    TabButtonPosition, danger_button_style, danger_icon_button_style, icon_button_style,
+    icon_svg_style, primary_button_style, rounded_input_combo_style, rounderer_box_container_style,
+    tab_button_style,
+};
+use crate::app::utils::ui::installation_label;
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct FlatpakRemotes {
+    state: State,
+    remotes: Vec<Remote>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Reloads the remotes
+    RefreshRemotes,
+    /// Callback after loading the remotes of both installations
+    RemotesLoaded((Vec<Remote>, Option<anywho::Error>)),
+
+    /// Opens the form to add a new remote
+    OpenAddRemote,
+    /// Callback when the name of the new remote changes
+    AddRemoteNameChanged(String),
+    /// Callback when the .flatpakrepo file or URL of the new remote changes
+    AddRemoteLocationChanged(String),
+    /// Callback when selecting the installation of the new remote
+    AddRemoteInstallationChanged(Installation),
+    /// Adds the remote of the form
+    ConfirmAddRemote,
+
+    /// Enables or disables the given remote
+    ToggleRemote(Remote),
+    /// Changes the priority of the given remote
+    ChangePriority(Remote, i32),
+    /// Asks to remove the given remote, opens the confirmation
+    DeleteRemote(Remote),
+    /// Confirms the removal of the remote pending confirmation
+    ConfirmDeleteRemote,
+
+    /// Callback after adding, modifying or removing a remote
+    RemoteChanged(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    /// Adding, modifying or removing a remote
+    Working,
+    Ready,
+    AddRemote {
+        name: String,
+        location: String,
+        installation: Installation,
+    },
+    ConfirmDelete {
+        remote: Remote,
+    },
+}
+
+impl FlatpakRemotes {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+                remotes: Vec::new(),
+            },
+            Task::perform(Remote::get_all(), Message::RemotesLoaded),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::AddRemote { .. } | State::ConfirmDelete { .. } => {
+                    self.state = State::Ready;
+                    Action::None
+                }
+                State::Working => Action::None,
+                State::Loading | State::Ready => Action::Back,
+            },
+            Message::RefreshRemotes => {
+                self.state = State::Loading;
+                Action::Run(Task::perform(Remote::get_all(), Message::RemotesLoaded))
+            }
+            Message::RemotesLoaded((remotes, error)) => {
+                self.state = State::Ready;
+                self.remotes = remotes;
+                match error {
+                    Some(err) => Action::AddToast(Toast::error_toast(err)),
+                    None => Action::None,
+                }
+            }
+            Message::OpenAddRemote => {
+                self.state = State::AddRemote {
+                    name: String::new(),
+                    location: String::new(),
+                    installation: Installation::User,
+                };
+                Action::None
+            }
+            Message::AddRemoteNameChanged(value) => {
+                if let State::AddRemote { name, .. } = &mut self.state {
+                    *name = value;
+                }
+                Action::None
+            }
+            Message::AddRemoteLocationChanged(value) => {
+                if let State::AddRemote { location, .. } = &mut self.state {
+                    *location = value;
+                }
+                Action::None
+            }
+            Message::AddRemoteInstallationChanged(value) => {
+                if let State::AddRemote { installation, .. } = &mut self.state {
+                    *installation = value;
+                }
+                Action::None
+            }
+            Message::ConfirmAddRemote => {
+                let State::AddRemote {
+                    name,
+                    location,
+                    installation,
+                } = &self.state
+                else {
+                    return Action::None;
+                };
+
+                if location.trim().is_empty() {
+                    return Action::None;
+                }
+
+                let task = Task::perform(
+                    Remote::add(*installation, name.clone(), location.clone()),
+                    Message::RemoteChanged,
+                );
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::ToggleRemote(remote) => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Remote::set_enabled(remote.installation, remote.name, !remote.enabled),
+                    Message::RemoteChanged,
+                ))
+            }
+            Message::ChangePriority(remote, priority) => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Remote::set_priority(remote.installation, remote.name, priority),
+                    Message::RemoteChanged,
+                ))
+            }
+            Message::DeleteRemote(remote) => {
+                self.state = State::ConfirmDelete { remote };
+                Action::None
+            }
+            Message::ConfirmDeleteRemote => {
+                let State::ConfirmDelete { remote } = &self.state else {
+                    return Action::None;
+                };
+
+                let task = Task::perform(
+                    Remote::delete(remote.installation, remote.name.clone()),
+                    Message::RemoteChanged,
+                );
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::RemoteChanged(result) => {
+                self.state = State::Loading;
+                let task = Task::perform(Remote::get_all(), Message::RemotesLoaded);
+                match result {
+                    Ok(_) => Action::AddToastAndRun((
+                        Toast::success_toast(fl!("remote-changes-applied")),
+                        task,
+                    )),
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading | State::Working => container(
+                column![
+                    text(if matches!(self.state, State::Loading) {
+                        fl!("loading")
+                    } else {
+                        fl!("applying-changes")
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready => self.list_view(),
+            State::AddRemote {
+                name,
+                location,
+                installation,
+            } => add_remote_view(name, location, *installation),
+            State::ConfirmDelete { remote } => confirm_delete_view(remote),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Working) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshRemotes)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match &self.state {
+            State::Ready => iced::widget::stack![main_content, back_button, refresh_button].into(),
+            _ => iced::widget::stack![main_content, back_button].into(),
+        }
+    }
+
+    pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
+        Subscription::none()
+    }
+
+    fn list_view(&self) -> Element<'_, Message> {
+        // stock Fedora only allows installing part of Flathub
+        let flathub_warning: Element<Message> =
+            if self.remotes.iter().any(Remote::is_restricted_flathub) {
+                container(text(fl!("restricted-flathub-warning")))
+                    .style(container::warning)
+                    .width(Length::Fill)
+                    .padding(10.)
+                    .into()
+            } else {
+                space().into()
+            };
+
+        let remotes = Installation::ALL
+            .iter()
+            .fold(column![].spacing(10.), |col, installation| {
+                let remotes: Vec<&Remote> = self
+                    .remotes
+                    .iter()
+                    .filter(|remote| remote.installation == *installation)
+                    .collect();
+
+                let section = column![text(installation_label(*installation)).size(16).font(
+                    iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }
+                )]
+                .spacing(5.);
+
+                let section = if remotes.is_empty() {
+                    section.push(
+                        container(text(fl!("no-remotes")))
+                            .align_x(Alignment::Center)
+                            .width(Length::Fill)
+                            .padding(5.),
+                    )
+                } else {
+                    remotes.into_iter().fold(section, |section, remote| {
+                        section.push(remote_card(remote, true))
+                    })
+                };
+
+                col.push(section)
+            });
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            row![
+                text(fl!("flatpak-remotes"))
+                    .width(Length::Fill)
+                    .size(18)
+                    .font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                button(text(fl!("add-remote")))
+                    .style(primary_button_style)
+                    .on_press(Message::OpenAddRemote)
+            ]
+            .spacing(5.),
+            flathub_warning,
+            scrollable(remotes)
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+fn remote_card(remote: &Remote, show_actions: bool) -> Element<'_, Message> {
+    let mut details = vec![format!("{}: {}", fl!("priority"), remote.priority)];
+    if !remote.enabled {
+        details.push(fl!("remote-disabled"));
+    }
+    if let Some(filter) = &remote.filter {
+        details.push(format!("{}: {}", fl!("remote-filter"), filter));
+    }
+    if let Some(subset) = &remote.subset {
+        details.push(format!("{}: {}", fl!("remote-subset"), subset));
+    }
+
+    let actions: Element<Message> = if show_actions {
+        let icon_button = |icon: &'static str, tooltip_text: String, message: Option<Message>| {
+            tooltip(
+                button(icons::get_icon(icon, 18).style(icon_svg_style))
+                    .style(icon_button_style)
+                    .on_press_maybe(message),
+                container(text(tooltip_text))
+                    .padding(5.)
+                    .style(container::secondary),
+                tooltip::Position::Top,
+            )
+        };
+
+        row![
+            icon_button(
+                "pan-up-symbolic",
+                fl!("raise-priority"),
+                Some(Message::ChangePriority(
+                    remote.clone(),
+                    remote.priority.saturating_add(1)
+                ))
+            ),
+            icon_button(
+                "pan-down-symbolic",
+                fl!("lower-priority"),
+                (remote.priority > 0)
+                    .then(|| { Message::ChangePriority(remote.clone(), remote.priority - 1) })
+            ),
+            button(text(if remote.enabled {
+                fl!("disable")
+            } else {
+                fl!("enable")
+            }))
+            .style(primary_button_style)
+            .on_press(Message::ToggleRemote(remote.clone())),
+            tooltip(
+                button(icons::get_icon("user-trash-full-symbolic", 18).style(icon_svg_style))
+                    .style(danger_icon_button_style)
+                    .on_press(Message::DeleteRemote(remote.clone())),
+                container(text(fl!("remove-remote")))
+                    .padding(5.)
+                    .style(container::secondary),
+                tooltip::Position::Top,
+            )
+        ]
+        .spacing(5.)
+        .align_y(Alignment::Center)
+        .into()
+    } else {
+        space().into()
+    };
+
+    container(
+        row![
+            column![
+                text(format!("{} ({})", remote.title, remote.name)).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(&remote.url).style(text::secondary).size(13),
+                text(details.join(" · ")).style(text::secondary).size(13)
+            ]
+            .spacing(2.)
+            .width(Length::Fill),
+            actions
+        ]
+        .align_y(Alignment::Center),
+    )
+    .style(if remote.enabled {
+        rounderer_box_container_style
+    } else {
+        container::bordered_box
+    })
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+fn add_remote_view<'a>(
+    name: &'a str,
+    location: &'a str,
+    installation: Installation,
+) -> Element<'a, Message> {
+    let installation_button = |value: Installation, position: TabButtonPosition| {
+        button(
+            text(installation_label(value))
+                .align_x(Alignment::Center)
+                .width(Length::Fill),
+        )
+        .on_press(Message::AddRemoteInstallationChanged(value))
+        .style(move |t, s| tab_button_style(t, s, installation == value, position))
+        .width(Length::Fill)
+    };
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("add-remote"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(fl!("add-remote-description"))
+            .size(16)
+            .align_x(Alignment::Center),
+        row![
+            installation_button(Installation::User, TabButtonPosition::Left),
+            installation_button(Installation::System, TabButtonPosition::Right),
+        ]
+        .width(Length::Fill),
+        text_input(fl!("remote-location-placeholder").as_str(), location)
+            .on_input(Message::AddRemoteLocationChanged)
+            .on_paste(Message::AddRemoteLocationChanged)
+            .on_submit(Message::ConfirmAddRemote)
+            .style(rounded_input_combo_style)
+            .padding(10.),
+        text_input(fl!("remote-name-placeholder").as_str(), name)
+            .on_input(Message::AddRemoteNameChanged)
+            .on_paste(Message::AddRemoteNameChanged)
+            .on_submit(Message::ConfirmAddRemote)
+            .style(rounded_input_combo_style)
+            .padding(10.),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("add")))
+                .style(primary_button_style)
+                .on_press_maybe((!location.trim().is_empty()).then_some(Message::ConfirmAddRemote))
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn confirm_delete_view(remote: &Remote) -> Element<'_, Message> {
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("remove-remote"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(fl!("remove-remote-confirmation"))
+            .size(18)
+            .align_x(Alignment::Center),
+        remote_card(remote, false),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("remove")))
+                .style(danger_button_style)
+                .on_press(Message::ConfirmDeleteRemote)
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
@@ -0,0 +1,679 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{button, checkbox, column, container, row, scrollable, space, text, tooltip};
+use iced::{Alignment, Element, Length, Subscription, Task};
+
+use crate::app::core::installed_flatpaks::InstalledFlatpak;
+use crate::app::core::update_applications::{Installation, RefKind};
+use crate::app::screen::installed_flatpaks::commit_history::CommitHistory;
+use crate::app::screen::installed_flatpaks::permissions::Permissions;
+use crate::app::screen::installed_flatpaks::repair::Repair;
+use crate::app::style::{
+    TabButtonPosition, danger_button_style, danger_icon_button_style, icon_button_style,
+    icon_svg_style, primary_button_style, rounderer_box_container_style, tab_button_style,
+};
+use crate::app::utils::ui::installation_label;
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub mod commit_history;
+pub mod permissions;
+pub mod repair;
+
+pub struct InstalledFlatpaks {
+    state: State,
+    flatpaks: Vec<InstalledFlatpak>,
+    current_tab: RefKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Reloads the installed applications and runtimes
+    RefreshFlatpaks,
+    /// Callback after loading the installed applications and runtimes
+    FlatpaksLoaded(Result<Vec<InstalledFlatpak>, anywho::Error>),
+
+    /// Shows the installed applications
+    OpenApplicationsTab,
+    /// Shows the installed runtimes and extensions
+    OpenRuntimesTab,
+
+    /// Asks to uninstall an application or runtime, opens the confirmation
+    Uninstall(InstalledFlatpak),
+    /// Callback when toggling the deletion of the application data
+    ToggleDeleteData(bool),
+    /// Confirms the uninstallation pending confirmation
+    ConfirmUninstall,
+    /// Callback after uninstalling an application or runtime
+    Uninstalled(Result<(), anywho::Error>),
+
+    /// Asks to remove the unused runtimes, opens the preview of what would be removed
+    PreviewUnused,
+    /// Callback after checking what would be removed
+    UnusedPreviewLoaded(Result<Vec<(Installation, Vec<String>)>, anywho::Error>),
+    /// Confirms the removal of the unused runtimes
+    ConfirmRemoveUnused,
+    /// Callback after removing the unused runtimes
+    UnusedRemoved(Result<(), anywho::Error>),
+
+    /// Permissions SubScreen Messages
+    Permissions(permissions::Message),
+    /// Asks to Open the Permissions SubScreen of the given application
+    OpenPermissions(InstalledFlatpak),
+
+    /// Commit History SubScreen Messages
+    CommitHistory(commit_history::Message),
+    /// Asks to Open the Commit History SubScreen of the given application
+    OpenCommitHistory(InstalledFlatpak),
+
+    /// Repair SubScreen Messages
+    Repair(repair::Message),
+    /// Asks to Open the Repair SubScreen
+    OpenRepair,
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    /// Uninstalling or checking what can be uninstalled
+    Working,
+    Ready,
+    ConfirmUninstall {
+        flatpak: InstalledFlatpak,
+        delete_data: bool,
+    },
+    UnusedPreview {
+        preview: Vec<(Installation, Vec<String>)>,
+    },
+    Permissions(Permissions),
+    CommitHistory(CommitHistory),
+    Repair(Repair),
+}
+
+impl InstalledFlatpaks {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+                flatpaks: Vec::new(),
+                current_tab: RefKind::App,
+            },
+            Task::perform(InstalledFlatpak::get_all(), Message::FlatpaksLoaded),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::ConfirmUninstall { .. } | State::UnusedPreview { .. } => {
+                    self.state = State::Ready;
+                    Action::None
+                }
+                State::Working
+                | State::Permissions(_)
+                | State::CommitHistory(_)
+                | State::Repair(_) => Action::None,
+                State::Loading | State::Ready => Action::Back,
+            },
+            Message::RefreshFlatpaks => {
+                self.state = State::Loading;
+                Action::Run(Task::perform(
+                    InstalledFlatpak::get_all(),
+                    Message::FlatpaksLoaded,
+                ))
+            }
+            Message::FlatpaksLoaded(result) => {
+                self.state = State::Ready;
+                match result {
+                    Ok(flatpaks) => {
+                        self.flatpaks = flatpaks;
+                        Action::None
+                    }
+                    Err(err) => {
+                        self.flatpaks = Vec::new();
+                        Action::AddToast(Toast::error_toast(err))
+                    }
+                }
+            }
+            Message::OpenApplicationsTab => {
+                self.current_tab = RefKind::App;
+                Action::None
+            }
+            Message::OpenRuntimesTab => {
+                self.current_tab = RefKind::Runtime;
+                Action::None
+            }
+            Message::Uninstall(flatpak) => {
+                self.state = State::ConfirmUninstall {
+                    flatpak,
+                    delete_data: false,
+                };
+                Action::None
+            }
+            Message::ToggleDeleteData(value) => {
+                if let State::ConfirmUninstall { delete_data, .. } = &mut self.state {
+                    *delete_data = value;
+                }
+                Action::None
+            }
+            Message::ConfirmUninstall => {
+                let State::ConfirmUninstall {
+                    flatpak,
+                    delete_data,
+                } = &self.state
+                else {
+                    return Action::None;
+                };
+
+                let task = Task::perform(
+                    InstalledFlatpak::uninstall(
+                        flatpak.installation,
+                        flatpak.flatpak_ref(),
+                        *delete_data && flatpak.kind == RefKind::App,
+                    ),
+                    Message::Uninstalled,
+                );
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::Uninstalled(result) => {
+                self.state = State::Loading;
+                let task = Task::perform(InstalledFlatpak::get_all(), Message::FlatpaksLoaded);
+                match result {
+                    Ok(_) => {
+                        Action::AddToastAndRun((Toast::success_toast(fl!("uninstalled")), task))
+                    }
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+            Message::PreviewUnused => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    InstalledFlatpak::unused_preview(),
+                    Message::UnusedPreviewLoaded,
+                ))
+            }
+            Message::UnusedPreviewLoaded(result) => match result {
+                Ok(preview) => {
+                    self.state = State::UnusedPreview { preview };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready;
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::ConfirmRemoveUnused => {
+                let State::UnusedPreview { preview } = &self.state else {
+                    return Action::None;
+                };
+
+                let preview = preview.clone();
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    InstalledFlatpak::uninstall_unused(preview),
+                    Message::UnusedRemoved,
+                ))
+            }
+            Message::UnusedRemoved(result) => {
+                self.state = State::Loading;
+                let task = Task::perform(InstalledFlatpak::get_all(), Message::FlatpaksLoaded);
+                match result {
+                    Ok(_) => {
+                        Action::AddToastAndRun((Toast::success_toast(fl!("unused-removed")), task))
+                    }
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+            Message::Permissions(message) => {
+                let State::Permissions(permissions) = &mut self.state else {
+                    return Action::None;
+                };
+
+                match permissions.update(message, now) {
+                    permissions::Action::None => Action::None,
+                    permissions::Action::Back => {
+                        self.state = State::Ready;
+                        Action::None
+                    }
+                    permissions::Action::Run(task) => Action::Run(task.map(Message::Permissions)),
+                    permissions::Action::AddToast(toast) => Action::AddToast(toast),
+                    permissions::Action::AddToastAndRun((toast, task)) => {
+                        Action::AddToastAndRun((toast, task.map(Message::Permissions)))
+                    }
+                }
+            }
+            Message::OpenPermissions(flatpak) => {
+                let (permissions, task) = Permissions::new(flatpak);
+                self.state = State::Permissions(permissions);
+                Action::Run(task.map(Message::Permissions))
+            }
+            Message::CommitHistory(message) => {
+                let State::CommitHistory(commit_history) = &mut self.state else {
+                    return Action::None;
+                };
+
+                match commit_history.update(message, now) {
+                    commit_history::Action::None => Action::None,
+                    // the installed version may have changed
+                    commit_history::Action::Back => self.update(Message::RefreshFlatpaks, now),
+                    commit_history::Action::Run(task) => {
+                        Action::Run(task.map(Message::CommitHistory))
+                    }
+                    commit_history::Action::AddToast(toast) => Action::AddToast(toast),
+                    commit_history::Action::AddToastAndRun((toast, task)) => {
+                        Action::AddToastAndRun((toast, task.map(Message::CommitHistory)))
+                    }
+                }
+            }
+            Message::OpenCommitHistory(flatpak) => {
+                let (commit_history, task) = CommitHistory::new(flatpak);
+                self.state = State::CommitHistory(commit_history);
+                Action::Run(task.map(Message::CommitHistory))
+            }
+            Message::Repair(message) => {
+                let State::Repair(repair) = &mut self.state else {
+                    return Action::None;
+                };
+
+                match repair.update(message, now) {
+                    repair::Action::None => Action::None,
+                    // the repair may have removed or reinstalled refs
+                    repair::Action::Back => self.update(Message::RefreshFlatpaks, now),
+                    repair::Action::Run(task) => Action::Run(task.map(Message::Repair)),
+                    repair::Action::AddToast(toast) => Action::AddToast(toast),
+                }
+            }
+            Message::OpenRepair => {
+                let (repair, task) = Repair::new();
+                self.state = State::Repair(repair);
+                Action::Run(task.map(Message::Repair))
+            }
+        }
+    }
+
+    pub fn view(&self, now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading | State::Working => container(
+                column![
+                    text(if matches!(self.state, State::Loading) {
+                        fl!("loading")
+                    } else {
+                        fl!("applying-changes")
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready => self.list_view(),
+            State::ConfirmUninstall {
+                flatpak,
+                delete_data,
+            } => confirm_uninstall_view(flatpak, *delete_data),
+            State::UnusedPreview { preview } => unused_preview_view(preview),
+            State::Permissions(permissions) => permissions.view(now).map(Message::Permissions),
+            State::CommitHistory(commit_history) => {
+                commit_history.view(now).map(Message::CommitHistory)
+            }
+            State::Repair(repair) => repair.view(now).map(Message::Repair),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Working) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshFlatpaks)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match &self.state {
+            State::Ready => iced::widget::stack![main_content, back_button, refresh_button].into(),
+            // the subscreens have their own header
+            State::Permissions(_) | State::CommitHistory(_) | State::Repair(_) => {
+                iced::widget::stack![main_content].into()
+            }
+            _ => iced::widget::stack![main_content, back_button].into(),
+        }
+    }
+
+    pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
+        Subscription::none()
+    }
+
+    fn list_view(&self) -> Element<'_, Message> {
+        let flatpaks: Vec<&InstalledFlatpak> = self
+            .flatpaks
+            .iter()
+            .filter(|flatpak| flatpak.kind == self.current_tab)
+            .collect();
+
+        let list: Element<Message> = if flatpaks.is_empty() {
+            container(text(fl!("no-installed-flatpaks")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+                .into()
+        } else {
+            scrollable(
+                flatpaks
+                    .into_iter()
+                    .fold(column![].spacing(5.), |col, flatpak| {
+                        col.push(flatpak_card(flatpak, true))
+                    }),
+            )
+            .into()
+        };
+
+        let tab_button = |label: String, tab: RefKind, position: TabButtonPosition, message| {
+            button(
+                text(label)
+                    .align_x(Alignment::Center)
+                    .align_y(Alignment::Center)
+                    .size(16)
+                    .font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+            )
+            .on_press(message)
+            .style(move |t, s| tab_button_style(t, s, self.current_tab == tab, position))
+            .width(Length::Fill)
+        };
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            row![
+                text(fl!("installed-flatpaks"))
+                    .width(Length::Fill)
+                    .size(18)
+                    .font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                button(text(fl!("repair")))
+                    .style(primary_button_style)
+                    .on_press(Message::OpenRepair),
+                button(text(fl!("remove-unused")))
+                    .style(primary_button_style)
+                    .on_press(Message::PreviewUnused)
+            ]
+            .spacing(5.),
+            row![
+                tab_button(
+                    fl!("applications"),
+                    RefKind::App,
+                    TabButtonPosition::Left,
+                    Message::OpenApplicationsTab
+                ),
+                tab_button(
+                    fl!("runtimes"),
+                    RefKind::Runtime,
+                    TabButtonPosition::Right,
+                    Message::OpenRuntimesTab
+                ),
+            ]
+            .width(Length::Fill)
+            .align_y(Alignment::Center),
+            list
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+fn flatpak_card<'a>(flatpak: &'a InstalledFlatpak, show_actions: bool) -> Element<'a, Message> {
+    let installed_on = if flatpak.installed_on.is_empty() {
+        String::new()
+    } else {
+        format!("{}: {}", fl!("installed-on"), flatpak.installed_on)
+    };
+
+    let details = [
+        flatpak.version.clone(),
+        installation_label(flatpak.installation),
+        flatpak.origin.clone(),
+        flatpak.size.clone(),
+        installed_on,
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<String>>()
+    .join(" · ");
+
+    let actions: Element<Message> = if show_actions {
+        let icon_button = |icon: &'static str, tooltip_text: String, message: Message| {
+            tooltip(
+                button(icons::get_icon(icon, 18).style(icon_svg_style))
+                    .style(icon_button_style)
+                    .on_press(message),
+                container(text(tooltip_text))
+                    .padding(5.)
+                    .style(container::secondary),
+                tooltip::Position::Top,
+            )
+        };
+
+        // only applications have permissions and can be downgraded from here
+        let app_buttons: Element<Message> = if flatpak.kind == RefKind::App {
+            row![
+                icon_button(
+                    "document-open-recent-symbolic",
+                    fl!("commit-history"),
+                    Message::OpenCommitHistory(flatpak.clone())
+                ),
+                icon_button(
+                    "emblem-system-symbolic",
+                    fl!("permissions"),
+                    Message::OpenPermissions(flatpak.clone())
+                )
+            ]
+            .spacing(5.)
+            .into()
+        } else {
+            space().into()
+        };
+
+        row![
+            app_buttons,
+            tooltip(
+                button(icons::get_icon("user-trash-full-symbolic", 18).style(icon_svg_style))
+                    .style(danger_icon_button_style)
+                    .on_press(Message::Uninstall(flatpak.clone())),
+                container(text(fl!("uninstall")))
+                    .padding(5.)
+                    .style(container::secondary),
+                tooltip::Position::Top,
+            )
+        ]
+        .spacing(5.)
+        .align_y(Alignment::Center)
+        .into()
+    } else {
+        space().into()
+    };
+
+    container(
+        row![
+            column![
+                text(&flatpak.name).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(flatpak.flatpak_ref()).style(text::secondary).size(13),
+                text(details).style(text::secondary).size(13)
+            ]
+            .spacing(2.)
+            .width(Length::Fill),
+            actions
+        ]
+        .align_y(Alignment::Center),
+    )
+    .style(rounderer_box_container_style)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+fn confirm_uninstall_view(flatpak: &InstalledFlatpak, delete_data: bool) -> Element<'_, Message> {
+    // only applications have data on ~/.var/app
+    let delete_data_checkbox: Element<Message> = if flatpak.kind == RefKind::App {
+        let label = format!("{} (~/.var/app/{})", fl!("delete-app-data"), flatpak.id);
+        checkbox(label, delete_data)
+            .on_toggle(Message::ToggleDeleteData)
+            .style(checkbox::danger)
+            .into()
+    } else {
+        space().into()
+    };
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("uninstall"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(fl!("uninstall-confirmation"))
+            .size(18)
+            .align_x(Alignment::Center),
+        flatpak_card(flatpak, false),
+        delete_data_checkbox,
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("uninstall")))
+                .style(danger_button_style)
+                .on_press(Message::ConfirmUninstall)
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn unused_preview_view(preview: &[(Installation, Vec<String>)]) -> Element<'_, Message> {
+    let header = text(fl!("remove-unused"))
+        .size(24)
+        .font(iced::font::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        })
+        .align_x(Alignment::Center);
+
+    if preview.is_empty() {
+        return column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            header,
+            text(fl!("nothing-unused")).size(18)
+        ]
+        .padding(20.)
+        .spacing(10.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into();
+    }
+
+    let refs = preview
+        .iter()
+        .fold(column![].spacing(10.), |col, (installation, refs)| {
+            col.push(
+                container(
+                    refs.iter().fold(
+                        column![
+                            text(installation_label(*installation)).font(iced::font::Font {
+                                weight: iced::font::Weight::Bold,
+                                ..Default::default()
+                            })
+                        ]
+                        .spacing(2.),
+                        |col, flatpak_ref| {
+                            col.push(text(flatpak_ref).font(iced::Font::MONOSPACE).size(13))
+                        },
+                    ),
+                )
+                .style(container::rounded_box)
+                .width(Length::Fill)
+                .padding(10.),
+            )
+        });
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        header,
+        text(fl!("remove-unused-confirmation"))
+            .size(18)
+            .align_x(Alignment::Center),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("remove")))
+                .style(danger_button_style)
+                .on_press(Message::ConfirmRemoveUnused)
+        ]
+        .spacing(10.),
+        scrollable(refs)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
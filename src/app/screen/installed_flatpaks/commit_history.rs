@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{button, checkbox, column, container, row, scrollable, space, text};
+use iced::{Alignment, Element, Length, Task};
+
+use crate::app::core::flatpak_commits::Commit;
+use crate::app::core::installed_flatpaks::InstalledFlatpak;
+use crate::app::style::{
+    danger_button_style, icon_button_style, icon_svg_style, primary_button_style,
+    rounderer_box_container_style,
+};
+use crate::app::utils::ui::installation_label;
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct CommitHistory {
+    flatpak: InstalledFlatpak,
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Reloads the commits of the application
+    RefreshCommits,
+    /// Callback after loading the commits of the remote and the installed commit
+    CommitsLoaded(Result<(Vec<Commit>, String), anywho::Error>),
+
+    /// Asks to downgrade to the given commit, opens the confirmation
+    SelectCommit(Commit),
+    /// Callback when toggling the mask after downgrading
+    ToggleMask(bool),
+    /// Confirms the downgrade pending confirmation
+    ConfirmDowngrade,
+    /// Callback after deploying the selected commit
+    Downgraded(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    /// Deploying the selected commit
+    Working,
+    Ready {
+        commits: Vec<Commit>,
+        installed_commit: String,
+    },
+    ConfirmDowngrade {
+        commits: Vec<Commit>,
+        installed_commit: String,
+        commit: Commit,
+        mask: bool,
+    },
+}
+
+impl CommitHistory {
+    pub fn new(flatpak: InstalledFlatpak) -> (Self, Task<Message>) {
+        let task = load_commits(&flatpak);
+        (
+            Self {
+                flatpak,
+                state: State::Loading,
+            },
+            task,
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match std::mem::replace(&mut self.state, State::Loading) {
+                State::ConfirmDowngrade {
+                    commits,
+                    installed_commit,
+                    ..
+                } => {
+                    self.state = State::Ready {
+                        commits,
+                        installed_commit,
+                    };
+                    Action::None
+                }
+                State::Working => {
+                    self.state = State::Working;
+                    Action::None
+                }
+                state => {
+                    self.state = state;
+                    Action::Back
+                }
+            },
+            Message::RefreshCommits => {
+                self.state = State::Loading;
+                Action::Run(load_commits(&self.flatpak))
+            }
+            Message::CommitsLoaded(result) => match result {
+                Ok((commits, installed_commit)) => {
+                    self.state = State::Ready {
+                        commits,
+                        installed_commit,
+                    };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready {
+                        commits: Vec::new(),
+                        installed_commit: String::new(),
+                    };
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::SelectCommit(commit) => {
+                if !matches!(self.state, State::Ready { .. }) {
+                    return Action::None;
+                }
+
+                if let State::Ready {
+                    commits,
+                    installed_commit,
+                } = std::mem::replace(&mut self.state, State::Loading)
+                {
+                    self.state = State::ConfirmDowngrade {
+                        commits,
+                        installed_commit,
+                        commit,
+                        mask: true,
+                    };
+                }
+                Action::None
+            }
+            Message::ToggleMask(value) => {
+                if let State::ConfirmDowngrade { mask, .. } = &mut self.state {
+                    *mask = value;
+                }
+                Action::None
+            }
+            Message::ConfirmDowngrade => {
+                let State::ConfirmDowngrade { commit, mask, .. } = &self.state else {
+                    return Action::None;
+                };
+
+                let task = Task::perform(
+                    Commit::downgrade(
+                        self.flatpak.installation,
+                        self.flatpak.id.clone(),
+                        self.flatpak.flatpak_ref(),
+                        commit.commit.clone(),
+                        *mask,
+                    ),
+                    Message::Downgraded,
+                );
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::Downgraded(result) => {
+                self.state = State::Loading;
+                let task = load_commits(&self.flatpak);
+                match result {
+                    Ok(_) => {
+                        Action::AddToastAndRun((Toast::success_toast(fl!("version-changed")), task))
+                    }
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading | State::Working => container(
+                column![
+                    text(if matches!(self.state, State::Loading) {
+                        fl!("loading")
+                    } else {
+                        fl!("applying-changes")
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready {
+                commits,
+                installed_commit,
+            } => self.commits_view(commits, installed_commit),
+            State::ConfirmDowngrade { commit, mask, .. } => {
+                confirm_downgrade_view(&self.flatpak, commit, *mask)
+            }
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Working) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshCommits)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match &self.state {
+            State::Ready { .. } => {
+                iced::widget::stack![main_content, back_button, refresh_button].into()
+            }
+            _ => iced::widget::stack![main_content, back_button].into(),
+        }
+    }
+
+    fn commits_view<'a>(
+        &'a self,
+        commits: &'a [Commit],
+        installed_commit: &'a str,
+    ) -> Element<'a, Message> {
+        let list: Element<Message> = if commits.is_empty() {
+            container(text(fl!("no-commits")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+                .into()
+        } else {
+            scrollable(commits.iter().fold(column![].spacing(5.), |col, commit| {
+                col.push(commit_card(commit, commit.commit == installed_commit, true))
+            }))
+            .into()
+        };
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            text(format!("{} · {}", fl!("commit-history"), self.flatpak.name))
+                .width(Length::Fill)
+                .size(18)
+                .font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            text(format!(
+                "{} · {} · {}",
+                self.flatpak.flatpak_ref(),
+                installation_label(self.flatpak.installation),
+                self.flatpak.origin
+            ))
+            .width(Length::Fill)
+            .style(text::secondary)
+            .size(13),
+            list
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+fn load_commits(flatpak: &InstalledFlatpak) -> Task<Message> {
+    Task::perform(
+        Commit::get_all(
+            flatpak.installation,
+            flatpak.origin.clone(),
+            flatpak.flatpak_ref(),
+        ),
+        Message::CommitsLoaded,
+    )
+}
+
+fn commit_card(commit: &Commit, is_installed: bool, show_actions: bool) -> Element<'_, Message> {
+    let action: Element<Message> = if is_installed {
+        text(fl!("installed-commit"))
+            .style(text::success)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .into()
+    } else if show_actions {
+        button(text(fl!("downgrade")))
+            .style(primary_button_style)
+            .on_press(Message::SelectCommit(commit.clone()))
+            .into()
+    } else {
+        space().into()
+    };
+
+    let details = [commit.short().to_string(), commit.date.clone()]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<String>>()
+        .join(" · ");
+
+    container(
+        row![
+            column![
+                text(&commit.subject).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(details)
+                    .font(iced::Font::MONOSPACE)
+                    .style(text::secondary)
+                    .size(13)
+            ]
+            .spacing(2.)
+            .width(Length::Fill),
+            action
+        ]
+        .align_y(Alignment::Center),
+    )
+    .style(rounderer_box_container_style)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+fn confirm_downgrade_view<'a>(
+    flatpak: &'a InstalledFlatpak,
+    commit: &'a Commit,
+    mask: bool,
+) -> Element<'a, Message> {
+    let label = format!("{} ({})", fl!("hold-after-downgrade"), flatpak.id);
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("downgrade"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(format!(
+            "{} {}?",
+            fl!("downgrade-confirmation"),
+            flatpak.name
+        ))
+        .size(18)
+        .align_x(Alignment::Center),
+        commit_card(commit, false, false),
+        checkbox(label, mask).on_toggle(Message::ToggleMask),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::Back),
+            button(text(fl!("downgrade")))
+                .style(danger_button_style)
+                .on_press(Message::ConfirmDowngrade)
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
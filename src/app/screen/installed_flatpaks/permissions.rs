@@ -0,0 +1,358 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::text::LineHeight;
+use iced::widget::{button, checkbox, column, container, row, scrollable, space, text, text_input};
+use iced::{Alignment, Element, Length, Task};
+
+use crate::app::core::flatpak_permissions::{Permission, PermissionKind};
+use crate::app::core::installed_flatpaks::InstalledFlatpak;
+use crate::app::style::{
+    danger_button_style, icon_button_style, icon_svg_style, rounded_button_combo_style,
+    rounded_input_combo_style, rounderer_box_container_style,
+};
+use crate::app::utils::ui::{installation_label, permission_kind_label};
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct Permissions {
+    flatpak: InstalledFlatpak,
+    state: State,
+    new_filesystem: String,
+    new_talk_name: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Reloads the permissions of the application
+    RefreshPermissions,
+    /// Callback after loading the permissions and overrides of the application
+    PermissionsLoaded(Result<Vec<Permission>, anywho::Error>),
+
+    /// Grants or revokes the given permission
+    TogglePermission(Permission, bool),
+    /// Callback when the filesystem to grant changes
+    NewFilesystemChanged(String),
+    /// Grants access to the filesystem of the input
+    AddFilesystem,
+    /// Callback when the bus name to grant changes
+    NewTalkNameChanged(String),
+    /// Allows talking to the bus name of the input
+    AddTalkName,
+    /// Removes every override of the application
+    ResetOverrides,
+
+    /// Callback after writing the overrides
+    PermissionsChanged(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    /// Writing the overrides
+    Working,
+    Ready {
+        permissions: Vec<Permission>,
+    },
+}
+
+impl Permissions {
+    pub fn new(flatpak: InstalledFlatpak) -> (Self, Task<Message>) {
+        let task = load_permissions(&flatpak);
+        (
+            Self {
+                flatpak,
+                state: State::Loading,
+                new_filesystem: String::new(),
+                new_talk_name: String::new(),
+            },
+            task,
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::Working => Action::None,
+                State::Loading | State::Ready { .. } => Action::Back,
+            },
+            Message::RefreshPermissions => {
+                self.state = State::Loading;
+                Action::Run(load_permissions(&self.flatpak))
+            }
+            Message::PermissionsLoaded(result) => match result {
+                Ok(permissions) => {
+                    self.state = State::Ready { permissions };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready {
+                        permissions: Vec::new(),
+                    };
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::TogglePermission(permission, granted) => {
+                self.set_permission(permission.kind, permission.value(), granted)
+            }
+            Message::NewFilesystemChanged(value) => {
+                self.new_filesystem = value;
+                Action::None
+            }
+            Message::AddFilesystem => {
+                let filesystem = std::mem::take(&mut self.new_filesystem);
+                if filesystem.trim().is_empty() {
+                    return Action::None;
+                }
+                self.set_permission(PermissionKind::Filesystem, filesystem.trim().into(), true)
+            }
+            Message::NewTalkNameChanged(value) => {
+                self.new_talk_name = value;
+                Action::None
+            }
+            Message::AddTalkName => {
+                let talk_name = std::mem::take(&mut self.new_talk_name);
+                if talk_name.trim().is_empty() {
+                    return Action::None;
+                }
+                self.set_permission(PermissionKind::TalkName, talk_name.trim().into(), true)
+            }
+            Message::ResetOverrides => {
+                self.state = State::Working;
+                Action::Run(Task::perform(
+                    Permission::reset(self.flatpak.installation, self.flatpak.id.clone()),
+                    Message::PermissionsChanged,
+                ))
+            }
+            Message::PermissionsChanged(result) => {
+                self.state = State::Loading;
+                let task = load_permissions(&self.flatpak);
+                match result {
+                    Ok(_) => Action::Run(task),
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading | State::Working => container(
+                column![
+                    text(if matches!(self.state, State::Loading) {
+                        fl!("loading")
+                    } else {
+                        fl!("applying-changes")
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready { permissions } => self.permissions_view(permissions),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Working) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshPermissions)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match &self.state {
+            State::Ready { .. } => {
+                iced::widget::stack![main_content, back_button, refresh_button].into()
+            }
+            _ => iced::widget::stack![main_content, back_button].into(),
+        }
+    }
+
+    fn set_permission(&mut self, kind: PermissionKind, value: String, granted: bool) -> Action {
+        self.state = State::Working;
+        Action::Run(Task::perform(
+            Permission::set(
+                self.flatpak.installation,
+                self.flatpak.id.clone(),
+                kind,
+                value,
+                granted,
+            ),
+            Message::PermissionsChanged,
+        ))
+    }
+
+    fn permissions_view<'a>(&'a self, permissions: &'a [Permission]) -> Element<'a, Message> {
+        let sections = PermissionKind::ALL
+            .iter()
+            .fold(column![].spacing(10.), |col, kind| {
+                let section = permissions
+                    .iter()
+                    .filter(|permission| permission.kind == *kind)
+                    .fold(
+                        column![text(permission_kind_label(*kind)).size(16).font(
+                            iced::font::Font {
+                                weight: iced::font::Weight::Bold,
+                                ..Default::default()
+                            }
+                        )]
+                        .spacing(5.),
+                        |section, permission| section.push(permission_row(permission)),
+                    );
+
+                let section = match kind {
+                    PermissionKind::Filesystem => section.push(add_input(
+                        fl!("filesystem-placeholder"),
+                        &self.new_filesystem,
+                        Message::NewFilesystemChanged,
+                        Message::AddFilesystem,
+                    )),
+                    PermissionKind::TalkName => section.push(add_input(
+                        fl!("talk-name-placeholder"),
+                        &self.new_talk_name,
+                        Message::NewTalkNameChanged,
+                        Message::AddTalkName,
+                    )),
+                    PermissionKind::Device | PermissionKind::Socket => section,
+                };
+
+                col.push(
+                    container(section)
+                        .style(rounderer_box_container_style)
+                        .width(Length::Fill)
+                        .padding(10.),
+                )
+            });
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            row![
+                column![
+                    text(format!("{} · {}", fl!("permissions"), self.flatpak.name))
+                        .size(18)
+                        .font(iced::font::Font {
+                            weight: iced::font::Weight::Bold,
+                            ..Default::default()
+                        }),
+                    text(format!(
+                        "{} · {}",
+                        self.flatpak.id,
+                        installation_label(self.flatpak.installation)
+                    ))
+                    .style(text::secondary)
+                    .size(13)
+                ]
+                .width(Length::Fill),
+                button(text(fl!("reset-overrides")))
+                    .style(danger_button_style)
+                    .on_press(Message::ResetOverrides)
+            ]
+            .align_y(Alignment::Center)
+            .spacing(5.),
+            scrollable(sections)
+        ]
+        .padding(20.)
+        .spacing(10.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+fn load_permissions(flatpak: &InstalledFlatpak) -> Task<Message> {
+    Task::perform(
+        Permission::get_all(
+            flatpak.installation,
+            flatpak.id.clone(),
+            flatpak.flatpak_ref(),
+        ),
+        Message::PermissionsLoaded,
+    )
+}
+
+fn permission_row(permission: &Permission) -> Element<'_, Message> {
+    // tell the user where the current value comes from
+    let source = if permission.app_override.is_some() {
+        fl!("permission-overridden")
+    } else if permission.global_override.is_some() {
+        fl!("permission-global-override")
+    } else {
+        String::new()
+    };
+
+    let label = permission.value();
+    let granted = permission.granted();
+    let toggled = permission.clone();
+    row![
+        checkbox(label, granted)
+            .on_toggle(move |granted| Message::TogglePermission(toggled.clone(), granted))
+            .width(Length::Fill),
+        text(source).style(text::secondary).size(13)
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+fn add_input<'a>(
+    placeholder: String,
+    value: &'a str,
+    on_input: fn(String) -> Message,
+    on_submit: Message,
+) -> Element<'a, Message> {
+    row![
+        text_input(placeholder.as_str(), value)
+            .on_input(on_input)
+            .on_paste(on_input)
+            .on_submit(on_submit.clone())
+            .width(Length::Fill)
+            .style(rounded_input_combo_style)
+            .line_height(LineHeight::Relative(2.)),
+        button(text(fl!("add")).line_height(LineHeight::Relative(2.)))
+            .on_press(on_submit)
+            .style(rounded_button_combo_style)
+    ]
+    .into()
+}
@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{Alignment, Element, Length, Task};
+
+use crate::app::core::flatpak_repair::{RepairEvent, RepairSummary, repair};
+use crate::app::core::update_applications::Installation;
+use crate::app::style::{
+    icon_button_style, icon_svg_style, primary_button_style, rounderer_box_container_style,
+};
+use crate::app::utils::ui::installation_label;
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct Repair {
+    /// Output of flatpak repair, line by line
+    output: Vec<String>,
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Repairs the given installation
+    Repair(Installation),
+    /// Progress of the running repair
+    RepairProgress(RepairEvent),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+}
+
+enum State {
+    /// Choosing the installation to repair
+    Idle,
+    Running {
+        installation: Installation,
+    },
+    Finished {
+        installation: Installation,
+        /// None if the repair failed
+        summary: Option<RepairSummary>,
+    },
+}
+
+impl Repair {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                output: Vec::new(),
+                state: State::Idle,
+            },
+            Task::none(),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::Running { .. } => Action::None,
+                State::Idle | State::Finished { .. } => Action::Back,
+            },
+            Message::Repair(installation) => {
+                if matches!(self.state, State::Running { .. }) {
+                    return Action::None;
+                }
+
+                self.output.clear();
+                self.state = State::Running { installation };
+                Action::Run(Task::run(repair(installation), Message::RepairProgress))
+            }
+            Message::RepairProgress(event) => {
+                let State::Running { installation } = self.state else {
+                    return Action::None;
+                };
+
+                match event {
+                    RepairEvent::Output(line) => {
+                        self.output.push(line);
+                        Action::None
+                    }
+                    RepairEvent::Done(result) => match result {
+                        Ok(summary) => {
+                            self.state = State::Finished {
+                                installation,
+                                summary: Some(summary),
+                            };
+                            Action::None
+                        }
+                        Err(err) => {
+                            self.state = State::Finished {
+                                installation,
+                                summary: None,
+                            };
+                            Action::AddToast(Toast::error_toast(err))
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Idle => installations_view(),
+            State::Running { installation } => self.output_view(*installation, None),
+            State::Finished {
+                installation,
+                summary,
+            } => self.output_view(*installation, Some(summary.as_ref())),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(if matches!(self.state, State::Running { .. }) {
+                    None
+                } else {
+                    Some(Message::Back)
+                })
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        iced::widget::stack![main_content, back_button].into()
+    }
+
+    /// Output of the repair, with a spinner while it runs and the summary once it has finished
+    fn output_view<'a>(
+        &'a self,
+        installation: Installation,
+        summary: Option<Option<&'a RepairSummary>>,
+    ) -> Element<'a, Message> {
+        let log = container(
+            scrollable(
+                self.output
+                    .iter()
+                    .fold(column![].spacing(2.).width(Length::Fill), |col, line| {
+                        col.push(text(line).font(iced::Font::MONOSPACE).size(13))
+                    }),
+            )
+            .anchor_bottom()
+            .width(Length::Fill)
+            .height(Length::Fill),
+        )
+        .style(rounderer_box_container_style)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let footer: Element<Message> = match summary {
+            None => row![
+                Circular::new()
+                    .easing(&easing::EMPHASIZED)
+                    .cycle_duration(std::time::Duration::from_secs_f32(5.0)),
+                text(fl!("repairing"))
+            ]
+            .spacing(10.)
+            .align_y(Alignment::Center)
+            .into(),
+            Some(Some(summary)) => summary_view(summary),
+            Some(None) => space().into(),
+        };
+
+        column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            text(format!(
+                "{} · {}",
+                fl!("repair"),
+                installation_label(installation)
+            ))
+            .width(Length::Fill)
+            .size(18)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+            log,
+            footer
+        ]
+        .padding(20.)
+        .spacing(10.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into()
+    }
+}
+
+/// One card per installation with its path and the button to repair it
+fn installations_view<'a>() -> Element<'a, Message> {
+    let cards = Installation::ALL
+        .iter()
+        .fold(column![].spacing(5.), |col, installation| {
+            col.push(
+                container(
+                    row![
+                        column![
+                            text(installation_label(*installation)).font(iced::font::Font {
+                                weight: iced::font::Weight::Bold,
+                                ..Default::default()
+                            }),
+                            text(installation.path().to_string_lossy().into_owned())
+                                .font(iced::Font::MONOSPACE)
+                                .style(text::secondary)
+                                .size(13)
+                        ]
+                        .spacing(2.)
+                        .width(Length::Fill),
+                        button(text(fl!("repair")))
+                            .style(primary_button_style)
+                            .on_press(Message::Repair(*installation))
+                    ]
+                    .align_y(Alignment::Center),
+                )
+                .style(rounderer_box_container_style)
+                .width(Length::Fill)
+                .padding(10.),
+            )
+        });
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("repair-installations"))
+            .width(Length::Fill)
+            .size(18)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+        text(fl!("repair-description")).width(Length::Fill),
+        cards
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn summary_view(summary: &RepairSummary) -> Element<'_, Message> {
+    let title = text(fl!("repair-summary")).font(iced::font::Font {
+        weight: iced::font::Weight::Bold,
+        ..Default::default()
+    });
+
+    if summary == &RepairSummary::default() {
+        return column![title, text(fl!("nothing-to-repair")).style(text::success)]
+            .spacing(5.)
+            .width(Length::Fill)
+            .into();
+    }
+
+    let refs_row = |label: String, refs: &[String]| -> Element<'_, Message> {
+        if refs.is_empty() {
+            return space().into();
+        }
+
+        column![
+            text(format!("{} ({})", label, refs.len())),
+            text(refs.join("\n"))
+                .font(iced::Font::MONOSPACE)
+                .style(text::secondary)
+                .size(13)
+        ]
+        .spacing(2.)
+        .into()
+    };
+
+    let problems: Element<Message> = if summary.problems.is_empty() {
+        space().into()
+    } else {
+        column![
+            text(fl!("repair-problems")).style(text::warning),
+            text(summary.problems.join("\n"))
+                .font(iced::Font::MONOSPACE)
+                .style(text::warning)
+                .size(13)
+        ]
+        .spacing(2.)
+        .into()
+    };
+
+    column![
+        title,
+        text(format!(
+            "{}: {}",
+            fl!("removed-objects"),
+            summary.removed_objects
+        )),
+        refs_row(fl!("redownloaded-refs"), &summary.redownloaded_refs),
+        refs_row(fl!("removed-refs"), &summary.removed_refs),
+        problems
+    ]
+    .spacing(5.)
+    .width(Length::Fill)
+    .into()
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{button, column, container, scrollable, space, text};
+use iced::{Alignment, Element, Length, Subscription, Task};
+
+use crate::app::core::what_changed::WhatChanged;
+use crate::app::style::{icon_button_style, icon_svg_style, primary_button_style};
+use crate::{fl, icons};
+
+pub struct PostUpdateSummary {
+    summary: WhatChanged,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+}
+
+pub enum Action {
+    Back,
+}
+
+impl PostUpdateSummary {
+    pub fn new(summary: WhatChanged) -> (Self, Task<Message>) {
+        (Self { summary }, Task::none())
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => Action::Back,
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let summary = &self.summary;
+
+        let version_change = text(format!(
+            "{} → {}",
+            version_or_checksum(&summary.from_version, &summary.from_checksum),
+            version_or_checksum(&summary.to_version, &summary.to_checksum)
+        ))
+        .size(18);
+
+        let changes = &summary.package_changes;
+        let counts = if changes.is_empty() {
+            fl!("no-package-changes")
+        } else {
+            [
+                (changes.upgraded.len(), fl!("upgraded")),
+                (changes.downgraded.len(), fl!("downgraded")),
+                (changes.removed.len(), fl!("removed")),
+                (changes.added.len(), fl!("added")),
+            ]
+            .into_iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, label)| format!("{} {}", count, label.to_lowercase()))
+            .collect::<Vec<String>>()
+            .join(", ")
+        };
+
+        let content = column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            text(fl!("what-changed"))
+                .size(24)
+                .font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                })
+                .align_x(Alignment::Center),
+            version_change,
+            text(counts).style(text::secondary),
+            scrollable(
+                column![
+                    section(
+                        fl!("notable-changes"),
+                        &summary.notable_changes,
+                        fl!("no-notable-changes")
+                    ),
+                    section(
+                        fl!("fixed-advisories"),
+                        &summary.advisories,
+                        fl!("no-fixed-advisories")
+                    ),
+                ]
+                .spacing(10.)
+            ),
+            button(text(fl!("continue")))
+                .style(primary_button_style)
+                .on_press(Message::Back)
+        ]
+        .padding(20.)
+        .spacing(10.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center);
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::Back)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        iced::widget::stack![main_content, back_button].into()
+    }
+
+    pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
+        Subscription::none()
+    }
+}
+
+/// Returns the version, or the short checksum if we don't know the version
+fn version_or_checksum<'a>(version: &'a str, checksum: &'a str) -> &'a str {
+    if version.is_empty() {
+        checksum.get(..10).unwrap_or(checksum)
+    } else {
+        version
+    }
+}
+
+fn section<'a>(title: String, lines: &'a [String], empty_text: String) -> Element<'a, Message> {
+    let lines: Element<'a, Message> = if lines.is_empty() {
+        text(empty_text).style(text::secondary).into()
+    } else {
+        lines
+            .iter()
+            .fold(column![].spacing(2.), |col, line| {
+                col.push(text(line).font(iced::Font::MONOSPACE).size(13))
+            })
+            .into()
+    };
+
+    container(
+        column![
+            text(title).font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+            lines
+        ]
+        .spacing(5.),
+    )
+    .style(container::rounded_box)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
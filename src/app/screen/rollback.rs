@@ -4,6 +4,8 @@ use iced::time::Instant;
 use iced::widget::{button, column, container, responsive, space, text};
 use iced::{Alignment, Length, Subscription, Task};
 
+use crate::app::APP_ID;
+use crate::app::core::history::{HistoryEntry, HistoryKind};
 use crate::app::core::rollback::rollback;
 use crate::app::core::{reboot, reboot_pending};
 use crate::app::style::{icon_button_style, icon_svg_style, primary_button_style};
@@ -31,6 +33,8 @@ pub enum Message {
     Rollback,
     /// Callback after attempting to apply a rollback
     RollbackCompleted(Result<(), anywho::Error>),
+    /// Callback after recording the change on the update history
+    HistoryRecorded(Result<(), anywho::Error>),
 
     /// Attempts to reboot the computer
     RebootNow,
@@ -207,12 +211,25 @@ impl Rollback {
                 Action::Run(Task::perform(rollback(), Message::RollbackCompleted))
             }
             Message::RollbackCompleted(result) => match result {
-                Ok(_) => self.update(Message::CheckReboot, now),
+                Ok(_) => Action::Run(Task::perform(
+                    HistoryEntry::record(APP_ID, HistoryKind::Rollback),
+                    Message::HistoryRecorded,
+                )),
                 Err(err) => Action::AddToastAndRun((
                     Toast::error_toast(err),
                     Task::perform(reboot_pending(), Message::RebootChecked),
                 )),
             },
+            Message::HistoryRecorded(result) => match result {
+                Ok(_) => self.update(Message::CheckReboot, now),
+                Err(err) => {
+                    self.state = State::Loading;
+                    Action::AddToastAndRun((
+                        Toast::warning_toast(format!("{}: {}", fl!("history-not-recorded"), err)),
+                        Task::perform(reboot_pending(), Message::RebootChecked),
+                    ))
+                }
+            },
             Message::RebootNow => Action::Run(Task::perform(reboot(), Message::RebootCallback)),
             Message::RebootCallback(result) => match result {
                 Ok(_) => self.update(Message::CheckReboot, now),
@@ -1,22 +1,37 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::collections::HashMap;
+
 use iced::time::Instant;
-use iced::widget::{button, column, container, row, rule, scrollable, space, text, tooltip};
+use iced::widget::{
+    button, column, container, row, rule, scrollable, space, text, text_editor, text_input, tooltip,
+};
 use iced::{Alignment, Element, Length, Subscription, Task};
 
+use crate::app::core::config::DeploymentNote;
 use crate::app::core::system_status::Deployment;
 use crate::app::core::{reboot, reboot_pending};
+use crate::app::screen::system_status::config_drift::ConfigDrift;
+use crate::app::screen::system_status::disk_usage::DiskUsageBreakdown;
 use crate::app::screen::system_status::layered_packages::LayeredPackages;
-use crate::app::style::{icon_button_style, icon_svg_style, primary_button_style};
+use crate::app::style::{
+    danger_button_style, danger_icon_button_style, icon_button_style, icon_svg_style,
+    primary_button_style,
+};
+use crate::app::utils::ui::{gpg_signature_details, gpg_signature_warning};
 use crate::app::widgets::spinners::circular::Circular;
 use crate::app::widgets::spinners::easing;
 use crate::app::widgets::toast::Toast;
 use crate::{fl, icons};
 
+pub mod config_drift;
+pub mod disk_usage;
 pub mod layered_packages;
 
 pub struct SystemStatus {
     state: State,
+    /// User notes attached to deployments, keyed by the deployment checksum
+    deployment_notes: HashMap<String, DeploymentNote>,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +56,26 @@ pub enum Message {
     /// Callback after pinning unpinning a deployment
     DeploymentPinChanged(Result<(), anywho::Error>),
 
+    /// Asks to delete (undeploy) a deployment, opens the confirmation
+    UndeployDeployment(Deployment),
+    /// Cancels the deletion of a deployment
+    CancelUndeployDeployment,
+    /// Confirms the deletion of the deployment pending confirmation
+    ConfirmUndeployDeployment,
+    /// Callback after deleting a deployment
+    DeploymentUndeployed(Result<(), anywho::Error>),
+
+    /// Opens the note editor for the given deployment
+    EditDeploymentNote(Deployment),
+    /// Callback when inputting text on the note label input
+    NoteLabelInputUpdated(String),
+    /// Callback when editing the note text
+    NoteEditorAction(text_editor::Action),
+    /// Saves the note that is being edited
+    SaveDeploymentNote,
+    /// Closes the note editor discarding the changes
+    CancelDeploymentNote,
+
     /// Attempts to reboot the computer
     RebootNow,
     /// Callback presumably if reboot failed
@@ -50,6 +85,16 @@ pub enum Message {
     LayeredPackages(layered_packages::Message),
     /// Asks to Open the Layered Packages SubScreen
     OpenLayeredPackages,
+
+    /// /etc Configuration Drift SubScreen Messages
+    ConfigDrift(config_drift::Message),
+    /// Asks to Open the /etc Configuration Drift SubScreen
+    OpenConfigDrift,
+
+    /// Disk Usage SubScreen Messages
+    DiskUsage(disk_usage::Message),
+    /// Asks to Open the Disk Usage SubScreen
+    OpenDiskUsage,
 }
 
 pub enum State {
@@ -59,8 +104,23 @@ pub enum State {
 }
 
 pub enum SubScreen {
-    Main { deployments: Vec<Deployment> },
+    Main {
+        deployments: Vec<Deployment>,
+        note_editor: Option<NoteEditor>,
+    },
+    ConfirmUndeploy {
+        deployment: Box<Deployment>,
+    },
     LayeredPackages(LayeredPackages),
+    ConfigDrift(ConfigDrift),
+    DiskUsage(DiskUsageBreakdown),
+}
+
+/// Holds the state of the note that is being edited
+pub struct NoteEditor {
+    checksum: String,
+    label: String,
+    note: text_editor::Content,
 }
 
 pub enum Action {
@@ -69,13 +129,15 @@ pub enum Action {
     Run(Task<Message>),
     AddToast(Toast),
     AddToastAndRun((Toast, Task<Message>)),
+    UpdateDeploymentNotes(HashMap<String, DeploymentNote>),
 }
 
 impl SystemStatus {
-    pub fn new() -> (Self, Task<Message>) {
+    pub fn new(deployment_notes: HashMap<String, DeploymentNote>) -> (Self, Task<Message>) {
         (
             Self {
                 state: State::Loading,
+                deployment_notes,
             },
             Task::perform(reboot_pending(), Message::RebootChecked),
         )
@@ -102,15 +164,34 @@ impl SystemStatus {
             )),
             Message::DeploymentsLoaded(deployments) => match deployments {
                 Ok(deployments) => {
+                    // garbage-collect the notes of deployments that no longer exist,
+                    // unless some checksums couldn't be read
+                    let notes_count = self.deployment_notes.len();
+                    if deployments.iter().all(|d| !d.checksum.is_empty()) {
+                        self.deployment_notes.retain(|checksum, _| {
+                            deployments.iter().any(|d| &d.checksum == checksum)
+                        });
+                    }
+                    let notes_changed = notes_count != self.deployment_notes.len();
+
                     self.state = State::Ready {
-                        sub_screen: SubScreen::Main { deployments },
+                        sub_screen: SubScreen::Main {
+                            deployments,
+                            note_editor: None,
+                        },
                     };
-                    Action::None
+
+                    if notes_changed {
+                        Action::UpdateDeploymentNotes(self.deployment_notes.clone())
+                    } else {
+                        Action::None
+                    }
                 }
                 Err(err) => {
                     self.state = State::Ready {
                         sub_screen: SubScreen::Main {
                             deployments: Vec::new(),
+                            note_editor: None,
                         },
                     };
                     Action::AddToast(Toast::error_toast(err))
@@ -141,6 +222,134 @@ impl SystemStatus {
                     Task::perform(Deployment::get_all(), Message::DeploymentsLoaded),
                 )),
             },
+            Message::UndeployDeployment(deployment) => {
+                if deployment.is_booted {
+                    return Action::AddToast(Toast::warning_toast(
+                        "The booted deployment can't be deleted",
+                    ));
+                }
+
+                if deployment.is_pinned {
+                    return Action::AddToast(Toast::warning_toast(
+                        "This deployment is pinned, unpin it before deleting it",
+                    ));
+                }
+
+                self.state = State::Ready {
+                    sub_screen: SubScreen::ConfirmUndeploy {
+                        deployment: Box::new(deployment),
+                    },
+                };
+                Action::None
+            }
+            Message::CancelUndeployDeployment => self.update(Message::LoadDeployments, now),
+            Message::ConfirmUndeployDeployment => {
+                let State::Ready {
+                    sub_screen: SubScreen::ConfirmUndeploy { deployment },
+                } = &self.state
+                else {
+                    return Action::None;
+                };
+
+                let task = Task::perform(
+                    Deployment::undeploy_deployment(deployment.index),
+                    Message::DeploymentUndeployed,
+                );
+                self.state = State::Loading;
+                Action::Run(task)
+            }
+            Message::DeploymentUndeployed(result) => match result {
+                Ok(_) => Action::AddToastAndRun((
+                    Toast::success_toast("Deployment deleted"),
+                    Task::perform(Deployment::get_all(), Message::DeploymentsLoaded),
+                )),
+                Err(err) => Action::AddToastAndRun((
+                    Toast::error_toast(err),
+                    Task::perform(Deployment::get_all(), Message::DeploymentsLoaded),
+                )),
+            },
+            Message::EditDeploymentNote(deployment) => {
+                let State::Ready {
+                    sub_screen: SubScreen::Main { note_editor, .. },
+                } = &mut self.state
+                else {
+                    return Action::None;
+                };
+
+                let current_note = self
+                    .deployment_notes
+                    .get(&deployment.checksum)
+                    .cloned()
+                    .unwrap_or_default();
+
+                *note_editor = Some(NoteEditor {
+                    checksum: deployment.checksum,
+                    label: current_note.label,
+                    note: text_editor::Content::with_text(&current_note.note),
+                });
+                Action::None
+            }
+            Message::NoteLabelInputUpdated(new_value) => {
+                if let State::Ready {
+                    sub_screen:
+                        SubScreen::Main {
+                            note_editor: Some(note_editor),
+                            ..
+                        },
+                } = &mut self.state
+                {
+                    note_editor.label = new_value;
+                }
+                Action::None
+            }
+            Message::NoteEditorAction(action) => {
+                if let State::Ready {
+                    sub_screen:
+                        SubScreen::Main {
+                            note_editor: Some(note_editor),
+                            ..
+                        },
+                } = &mut self.state
+                {
+                    note_editor.note.perform(action);
+                }
+                Action::None
+            }
+            Message::SaveDeploymentNote => {
+                let State::Ready {
+                    sub_screen: SubScreen::Main { note_editor, .. },
+                } = &mut self.state
+                else {
+                    return Action::None;
+                };
+
+                let Some(editor) = note_editor.take() else {
+                    return Action::None;
+                };
+
+                let note = DeploymentNote {
+                    label: editor.label.trim().to_string(),
+                    note: editor.note.text().trim().to_string(),
+                };
+
+                // saving an empty note removes it
+                if note.is_empty() {
+                    self.deployment_notes.remove(&editor.checksum);
+                } else {
+                    self.deployment_notes.insert(editor.checksum, note);
+                }
+
+                Action::UpdateDeploymentNotes(self.deployment_notes.clone())
+            }
+            Message::CancelDeploymentNote => {
+                if let State::Ready {
+                    sub_screen: SubScreen::Main { note_editor, .. },
+                } = &mut self.state
+                {
+                    *note_editor = None;
+                }
+                Action::None
+            }
             Message::RebootNow => Action::Run(Task::perform(reboot(), Message::RebootCallback)),
             Message::RebootCallback(result) => match result {
                 Ok(_) => self.update(Message::CheckReboot, now),
@@ -162,9 +371,16 @@ impl SystemStatus {
                         Action::Run(task.map(Message::LayeredPackages))
                     }
                     layered_packages::Action::AddToast(toast) => Action::AddToast(toast),
-                    layered_packages::Action::BackAndCheckReboot => {
+                    layered_packages::Action::BackAndCheckReboot(None) => {
                         self.update(Message::CheckReboot, now)
                     }
+                    layered_packages::Action::BackAndCheckReboot(Some(toast)) => {
+                        self.state = State::Loading;
+                        Action::AddToastAndRun((
+                            toast,
+                            Task::perform(reboot_pending(), Message::RebootChecked),
+                        ))
+                    }
                 }
             }
             Message::OpenLayeredPackages => {
@@ -185,6 +401,59 @@ impl SystemStatus {
 
                 Action::None
             }
+            Message::ConfigDrift(message) => {
+                let State::Ready { sub_screen, .. } = &mut self.state else {
+                    return Action::None;
+                };
+
+                let SubScreen::ConfigDrift(config_drift) = sub_screen else {
+                    return Action::None;
+                };
+
+                match config_drift.update(message, now) {
+                    config_drift::Action::None => Action::None,
+                    config_drift::Action::Back => self.update(Message::LoadDeployments, now),
+                    config_drift::Action::Run(task) => Action::Run(task.map(Message::ConfigDrift)),
+                    config_drift::Action::AddToast(toast) => Action::AddToast(toast),
+                }
+            }
+            Message::OpenConfigDrift => {
+                let State::Ready { sub_screen, .. } = &mut self.state else {
+                    return Action::None;
+                };
+
+                let (config_drift, task) = ConfigDrift::new();
+                *sub_screen = SubScreen::ConfigDrift(config_drift);
+                Action::Run(task.map(Message::ConfigDrift))
+            }
+            Message::DiskUsage(message) => {
+                let State::Ready { sub_screen, .. } = &mut self.state else {
+                    return Action::None;
+                };
+
+                let SubScreen::DiskUsage(disk_usage) = sub_screen else {
+                    return Action::None;
+                };
+
+                match disk_usage.update(message, now) {
+                    disk_usage::Action::None => Action::None,
+                    disk_usage::Action::Back => self.update(Message::LoadDeployments, now),
+                    disk_usage::Action::Run(task) => Action::Run(task.map(Message::DiskUsage)),
+                    disk_usage::Action::AddToast(toast) => Action::AddToast(toast),
+                    disk_usage::Action::AddToastAndRun((toast, task)) => {
+                        Action::AddToastAndRun((toast, task.map(Message::DiskUsage)))
+                    }
+                }
+            }
+            Message::OpenDiskUsage => {
+                let State::Ready { sub_screen, .. } = &mut self.state else {
+                    return Action::None;
+                };
+
+                let (disk_usage, task) = DiskUsageBreakdown::new();
+                *sub_screen = SubScreen::DiskUsage(disk_usage);
+                Action::Run(task.map(Message::DiskUsage))
+            }
         }
     }
 
@@ -228,7 +497,10 @@ impl SystemStatus {
             .align_x(Alignment::Center)
             .into(),
             State::Ready { sub_screen } => match sub_screen {
-                SubScreen::Main { deployments } => {
+                SubScreen::Main {
+                    deployments,
+                    note_editor,
+                } => {
                     if deployments.is_empty() {
                         column![
                             space().width(Length::Fill).height(Length::Fixed(35.)),
@@ -256,7 +528,15 @@ impl SystemStatus {
                             .spacing(10.);
 
                         for deployment in deployments {
-                            deployment_cards = deployment_cards.push(deployment_card(deployment));
+                            let editor = note_editor
+                                .as_ref()
+                                .filter(|e| e.checksum == deployment.checksum);
+                            deployment_cards = deployment_cards.push(deployment_card(
+                                deployment,
+                                self.deployment_notes.get(&deployment.checksum),
+                                editor,
+                                true,
+                            ));
                         }
 
                         column![
@@ -269,10 +549,17 @@ impl SystemStatus {
                                         weight: iced::font::Weight::Bold,
                                         ..Default::default()
                                     }),
+                                button(text(fl!("disk-usage")))
+                                    .style(primary_button_style)
+                                    .on_press(Message::OpenDiskUsage),
+                                button(text(fl!("etc-changes")))
+                                    .style(primary_button_style)
+                                    .on_press(Message::OpenConfigDrift),
                                 button(text(fl!("manage-layered-packages")))
                                     .style(primary_button_style)
                                     .on_press(Message::OpenLayeredPackages)
-                            ],
+                            ]
+                            .spacing(5.),
                             scrollable(deployment_cards),
                         ]
                         .padding(20.)
@@ -283,9 +570,47 @@ impl SystemStatus {
                         .into()
                     }
                 }
+                SubScreen::ConfirmUndeploy { deployment } => column![
+                    space().width(Length::Fill).height(Length::Fixed(35.)),
+                    text(fl!("delete-deployment"))
+                        .size(24)
+                        .font(iced::font::Font {
+                            weight: iced::font::Weight::Bold,
+                            ..Default::default()
+                        })
+                        .align_x(Alignment::Center),
+                    text(fl!("delete-deployment-confirmation"))
+                        .size(18)
+                        .align_x(Alignment::Center),
+                    row![
+                        button(text(fl!("cancel")))
+                            .style(primary_button_style)
+                            .on_press(Message::CancelUndeployDeployment),
+                        button(text(fl!("delete")))
+                            .style(danger_button_style)
+                            .on_press(Message::ConfirmUndeployDeployment)
+                    ]
+                    .spacing(10.),
+                    scrollable(deployment_card(
+                        deployment,
+                        self.deployment_notes.get(&deployment.checksum),
+                        None,
+                        false
+                    ))
+                ]
+                .padding(20.)
+                .spacing(10.)
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .align_x(Alignment::Center)
+                .into(),
                 SubScreen::LayeredPackages(layered_packages) => {
                     layered_packages.view(now).map(Message::LayeredPackages)
                 }
+                SubScreen::ConfigDrift(config_drift) => {
+                    config_drift.view(now).map(Message::ConfigDrift)
+                }
+                SubScreen::DiskUsage(disk_usage) => disk_usage.view(now).map(Message::DiskUsage),
             },
         };
 
@@ -320,7 +645,11 @@ impl SystemStatus {
         // there's probably a much better way to do this.
         match &self.state {
             State::Ready {
-                sub_screen: SubScreen::LayeredPackages { .. },
+                sub_screen:
+                    SubScreen::LayeredPackages { .. }
+                    | SubScreen::ConfirmUndeploy { .. }
+                    | SubScreen::ConfigDrift { .. }
+                    | SubScreen::DiskUsage { .. },
                 ..
             } => iced::widget::stack![main_content].into(),
             _ => iced::widget::stack![main_content, back_button, refresh_button].into(),
@@ -332,8 +661,13 @@ impl SystemStatus {
     }
 }
 
-fn deployment_card<'a>(deployment: &'a Deployment) -> Element<'a, Message> {
-    let pin_button = match deployment.is_pinned {
+fn deployment_card<'a>(
+    deployment: &'a Deployment,
+    note: Option<&'a DeploymentNote>,
+    note_editor: Option<&'a NoteEditor>,
+    show_actions: bool,
+) -> Element<'a, Message> {
+    let pin_button: Element<'a, Message> = match deployment.is_pinned {
         true => tooltip(
             button(icons::get_icon("unpin-symbolic", 18).style(icon_svg_style))
                 .style(icon_button_style)
@@ -343,7 +677,8 @@ fn deployment_card<'a>(deployment: &'a Deployment) -> Element<'a, Message> {
                 .padding(5.)
                 .style(container::secondary),
             tooltip::Position::Top,
-        ),
+        )
+        .into(),
         false => tooltip(
             button(icons::get_icon("pin-symbolic", 18).style(icon_svg_style))
                 .style(icon_button_style)
@@ -353,7 +688,51 @@ fn deployment_card<'a>(deployment: &'a Deployment) -> Element<'a, Message> {
                 .padding(5)
                 .style(container::secondary),
             tooltip::Position::Top,
-        ),
+        )
+        .into(),
+    };
+
+    // the booted deployment can't be deleted, so we don't offer the option
+    let delete_button: Element<'a, Message> = if deployment.is_booted || !show_actions {
+        space().into()
+    } else {
+        tooltip(
+            button(icons::get_icon("user-trash-full-symbolic", 18).style(icon_svg_style))
+                .style(danger_icon_button_style)
+                .width(Length::Shrink)
+                .on_press(Message::UndeployDeployment(deployment.clone())),
+            container(text(fl!("delete-deployment-tooltip")))
+                .padding(5.)
+                .style(container::secondary),
+            tooltip::Position::Top,
+        )
+        .into()
+    };
+
+    // notes are stored by checksum
+    let edit_note_button: Element<'a, Message> = if show_actions && !deployment.checksum.is_empty()
+    {
+        tooltip(
+            button(icons::get_icon("document-edit-symbolic", 18).style(icon_svg_style))
+                .style(icon_button_style)
+                .width(Length::Shrink)
+                .on_press(Message::EditDeploymentNote(deployment.clone())),
+            container(text(fl!("edit-deployment-note")))
+                .padding(5.)
+                .style(container::secondary),
+            tooltip::Position::Top,
+        )
+        .into()
+    } else {
+        space().into()
+    };
+
+    let note_label: Element<'a, Message> = match note.filter(|n| !n.label.is_empty()) {
+        Some(note) => container(text(&note.label).size(13))
+            .padding([2., 8.])
+            .style(container::primary)
+            .into(),
+        None => space().into(),
     };
 
     let card_header = row![
@@ -367,8 +746,16 @@ fn deployment_card<'a>(deployment: &'a Deployment) -> Element<'a, Message> {
             weight: iced::font::Weight::Bold,
             ..Default::default()
         }),
-        pin_button
+        note_label,
+        edit_note_button,
+        if show_actions {
+            pin_button
+        } else {
+            space().into()
+        },
+        delete_button
     ]
+    .spacing(5.)
     .width(Length::Fill)
     .align_y(Alignment::Center)
     .padding(10.);
@@ -395,7 +782,7 @@ fn deployment_card<'a>(deployment: &'a Deployment) -> Element<'a, Message> {
                 weight: iced::font::Weight::Bold,
                 ..Default::default()
             }),
-            text(&deployment.gpg_signature)
+            gpg_signature_details(&deployment.gpg_signature)
         ]
         .spacing(2.),
         row![
@@ -410,10 +797,63 @@ fn deployment_card<'a>(deployment: &'a Deployment) -> Element<'a, Message> {
     .spacing(3.)
     .padding(10.);
 
+    let deployment_content = match note.filter(|n| !n.note.is_empty()) {
+        Some(note) => deployment_content.push(
+            row![
+                text(fl!("note")).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(&note.note)
+            ]
+            .spacing(2.),
+        ),
+        None => deployment_content,
+    };
+
+    let note_editor_content: Element<'a, Message> = match note_editor {
+        Some(editor) => column![
+            rule::horizontal(1),
+            column![
+                text_input(fl!("note-label-placeholder").as_str(), &editor.label)
+                    .on_input(Message::NoteLabelInputUpdated)
+                    .on_paste(Message::NoteLabelInputUpdated)
+                    .width(Length::Fill),
+                text_editor(&editor.note)
+                    .placeholder(fl!("note-placeholder"))
+                    .on_action(Message::NoteEditorAction)
+                    .height(Length::Fixed(80.)),
+                row![
+                    button(text(fl!("cancel")))
+                        .style(button::secondary)
+                        .on_press(Message::CancelDeploymentNote),
+                    button(text(fl!("save")))
+                        .style(primary_button_style)
+                        .on_press(Message::SaveDeploymentNote)
+                ]
+                .spacing(5.)
+            ]
+            .spacing(5.)
+            .padding(10.)
+        ]
+        .into(),
+        None => space().into(),
+    };
+
+    let signature_warning: Element<'a, Message> = if deployment.has_untrusted_signature() {
+        container(gpg_signature_warning(&deployment.gpg_signature))
+            .padding([0., 10.])
+            .into()
+    } else {
+        space().into()
+    };
+
     container(column![
         card_header,
         rule::horizontal(1),
-        deployment_content
+        signature_warning,
+        deployment_content,
+        note_editor_content
     ])
     .style(container::rounded_box)
     .width(Length::Fill)
@@ -0,0 +1,388 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{Alignment, Element, Length, Padding, Task};
+
+use crate::app::core::config_drift::{ConfigChange, ConfigChangeKind, FileDiff};
+use crate::app::style::{icon_button_style, icon_svg_style, rounderer_box_container_style};
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct ConfigDrift {
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Refreshes the list of changed files
+    RefreshChanges,
+    /// Callback after loading the list of changed files (ostree admin config-diff)
+    ChangesLoaded(Result<Vec<ConfigChange>, anywho::Error>),
+
+    /// Asks to open the diff of the given file
+    OpenDiff(ConfigChange),
+    /// Callback after loading the diff of the file with the given path
+    DiffLoaded(String, Result<FileDiff, anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+}
+
+enum State {
+    Loading,
+    Ready {
+        changes: Vec<ConfigChange>,
+        selected: Option<SelectedDiff>,
+    },
+}
+
+struct SelectedDiff {
+    change: ConfigChange,
+    /// None while the diff is being loaded
+    diff: Option<FileDiff>,
+}
+
+impl ConfigDrift {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+            },
+            Task::perform(ConfigChange::get_all(), Message::ChangesLoaded),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => {
+                // the back button closes the open diff before leaving the subscreen
+                if let State::Ready { selected, .. } = &mut self.state
+                    && selected.is_some()
+                {
+                    *selected = None;
+                    return Action::None;
+                }
+                Action::Back
+            }
+            Message::RefreshChanges => {
+                self.state = State::Loading;
+                Action::Run(Task::perform(
+                    ConfigChange::get_all(),
+                    Message::ChangesLoaded,
+                ))
+            }
+            Message::ChangesLoaded(result) => match result {
+                Ok(changes) => {
+                    self.state = State::Ready {
+                        changes,
+                        selected: None,
+                    };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready {
+                        changes: Vec::new(),
+                        selected: None,
+                    };
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::OpenDiff(change) => {
+                let State::Ready { selected, .. } = &mut self.state else {
+                    return Action::None;
+                };
+
+                if !change.has_diff() {
+                    return Action::None;
+                }
+
+                *selected = Some(SelectedDiff {
+                    change: change.clone(),
+                    diff: None,
+                });
+                let path = change.path.clone();
+                Action::Run(Task::perform(change.diff(), move |result| {
+                    Message::DiffLoaded(path.clone(), result)
+                }))
+            }
+            Message::DiffLoaded(path, result) => {
+                let State::Ready { selected, .. } = &mut self.state else {
+                    return Action::None;
+                };
+
+                // ignore diffs of files that were closed before they finished loading
+                if selected
+                    .as_ref()
+                    .is_none_or(|selected| selected.change.path != path)
+                {
+                    return Action::None;
+                }
+
+                match result {
+                    Ok(diff) => {
+                        if let Some(selected) = selected {
+                            selected.diff = Some(diff);
+                        }
+                        Action::None
+                    }
+                    Err(err) => {
+                        *selected = None;
+                        Action::AddToast(Toast::error_toast(err))
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading => container(
+                column![
+                    text(fl!("loading")),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready {
+                selected: Some(selected),
+                ..
+            } => diff_view(selected),
+            State::Ready {
+                changes,
+                selected: None,
+            } => changes_view(changes),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::Back)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshChanges)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        iced::widget::stack![main_content, back_button, refresh_button].into()
+    }
+}
+
+fn changes_view<'a>(changes: &'a [ConfigChange]) -> Element<'a, Message> {
+    let header = text(fl!("etc-changes"))
+        .width(Length::Fill)
+        .size(18)
+        .font(iced::font::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        });
+
+    if changes.is_empty() {
+        return column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            header,
+            container(text(fl!("no-etc-changes")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into();
+    }
+
+    let count = |kind: ConfigChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+    let summary = text(format!(
+        "{} {} · {} {} · {} {}",
+        count(ConfigChangeKind::Modified),
+        fl!("modified"),
+        count(ConfigChangeKind::Added),
+        fl!("added"),
+        count(ConfigChangeKind::Deleted),
+        fl!("deleted")
+    ));
+
+    let leftovers = changes.iter().filter(|c| c.is_rpm_leftover()).count();
+    let leftovers_warning: Element<Message> = if leftovers > 0 {
+        container(text(format!(
+            "{} {}",
+            leftovers,
+            fl!("rpm-leftovers-warning")
+        )))
+        .style(container::warning)
+        .width(Length::Fill)
+        .padding(10.)
+        .into()
+    } else {
+        space().into()
+    };
+
+    let changes_list =
+        changes
+            .iter()
+            .fold(column![].spacing(5.).width(Length::Fill), |col, change| {
+                let (kind_label, kind_style): (&str, fn(&iced::Theme) -> text::Style) =
+                    match change.kind {
+                        ConfigChangeKind::Modified => ("M", text::warning),
+                        ConfigChangeKind::Added => ("A", text::success),
+                        ConfigChangeKind::Deleted => ("D", text::danger),
+                    };
+
+                let leftover_label: Element<Message> = if change.is_rpm_leftover() {
+                    text(fl!("rpm-leftover")).style(text::danger).into()
+                } else {
+                    space().into()
+                };
+
+                let row_content = row![
+                    text(kind_label)
+                        .font(iced::Font::MONOSPACE)
+                        .style(kind_style)
+                        .width(Length::Fixed(20.)),
+                    text(format!("/etc/{}", change.path))
+                        .font(iced::Font::MONOSPACE)
+                        .width(Length::Fill),
+                    leftover_label
+                ]
+                .spacing(10.)
+                .align_y(Alignment::Center)
+                .width(Length::Fill);
+
+                let entry: Element<Message> = if change.has_diff() {
+                    button(row_content)
+                        .on_press(Message::OpenDiff(change.clone()))
+                        .style(button::text)
+                        .width(Length::Fill)
+                        .into()
+                } else {
+                    container(row_content)
+                        .padding(Padding::new(5.).left(10.).right(10.))
+                        .width(Length::Fill)
+                        .into()
+                };
+
+                col.push(
+                    container(entry)
+                        .style(rounderer_box_container_style)
+                        .width(Length::Fill)
+                        .padding(5.),
+                )
+            });
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        header,
+        summary,
+        leftovers_warning,
+        scrollable(changes_list)
+    ]
+    .padding(20.)
+    .spacing(5.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn diff_view<'a>(selected: &'a SelectedDiff) -> Element<'a, Message> {
+    let header = text(format!("/etc/{}", selected.change.path))
+        .width(Length::Fill)
+        .size(18)
+        .font(iced::font::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        });
+
+    let diff_content: Element<Message> = match &selected.diff {
+        None => container(
+            Circular::new()
+                .easing(&easing::EMPHASIZED)
+                .cycle_duration(std::time::Duration::from_secs_f32(5.0)),
+        )
+        .align_x(Alignment::Center)
+        .width(Length::Fill)
+        .into(),
+        Some(FileDiff::PermissionDenied) => container(text(fl!("etc-diff-permission-denied")))
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .padding(5.)
+            .into(),
+        Some(FileDiff::Text(diff)) if diff.is_empty() => container(text(fl!("no-content-changes")))
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .padding(5.)
+            .into(),
+        Some(FileDiff::Text(diff)) => scrollable(
+            container(
+                diff.lines()
+                    .fold(column![].width(Length::Fill), |col, line| {
+                        let line_text = text(line).font(iced::Font::MONOSPACE).size(13);
+                        col.push(if line.starts_with("+++") || line.starts_with("---") {
+                            line_text.style(text::secondary)
+                        } else if line.starts_with('+') {
+                            line_text.style(text::success)
+                        } else if line.starts_with('-') {
+                            line_text.style(text::danger)
+                        } else if line.starts_with("@@") {
+                            line_text.style(text::primary)
+                        } else {
+                            line_text
+                        })
+                    }),
+            )
+            .style(container::rounded_box)
+            .width(Length::Fill)
+            .padding(10.),
+        )
+        .into(),
+    };
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        header,
+        diff_content
+    ]
+    .padding(20.)
+    .spacing(5.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
@@ -0,0 +1,630 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::f32::consts::PI;
+
+use iced::time::Instant;
+use iced::widget::canvas::{self, Frame, Geometry, Path, path};
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{
+    Alignment, Color, Element, Length, Point, Radians, Rectangle, Renderer, Task, Theme, mouse,
+};
+
+use crate::app::core::disk_usage::{self, CleanupAction, DiskUsage};
+use crate::app::style::{
+    danger_button_style, icon_button_style, icon_svg_style, primary_button_style,
+    rounderer_box_container_style,
+};
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct DiskUsageBreakdown {
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Recomputes the disk usage
+    RefreshUsage,
+    /// Callback after computing the disk usage
+    UsageLoaded(Result<DiskUsage, anywho::Error>),
+
+    /// Selects a slice of the chart (by its position)
+    SelectSlice(usize),
+    /// Asks to run the cleanup action of the selected slice, opens the confirmation
+    RequestCleanup,
+    /// Cancels the pending cleanup action
+    CancelCleanup,
+    /// Confirms and runs the pending cleanup action
+    ConfirmCleanup,
+    /// Callback after running a cleanup action
+    CleanupFinished(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    Loading,
+    Cleaning,
+    Ready {
+        slices: Vec<Slice>,
+        selected: Option<usize>,
+        confirm_cleanup: bool,
+    },
+}
+
+/// A part of the disk usage shown on the chart
+#[derive(Debug, Clone)]
+struct Slice {
+    label: String,
+    size: u64,
+    description: String,
+    action: Option<CleanupAction>,
+}
+
+impl DiskUsageBreakdown {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+            },
+            Task::perform(DiskUsage::get(), Message::UsageLoaded),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => {
+                // the back button closes the open confirmation before leaving the subscreen
+                if let State::Ready {
+                    confirm_cleanup, ..
+                } = &mut self.state
+                    && *confirm_cleanup
+                {
+                    *confirm_cleanup = false;
+                    return Action::None;
+                }
+                Action::Back
+            }
+            Message::RefreshUsage => {
+                self.state = State::Loading;
+                Action::Run(Task::perform(DiskUsage::get(), Message::UsageLoaded))
+            }
+            Message::UsageLoaded(result) => match result {
+                Ok(usage) => {
+                    self.state = State::Ready {
+                        slices: slices(&usage),
+                        selected: None,
+                        confirm_cleanup: false,
+                    };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready {
+                        slices: Vec::new(),
+                        selected: None,
+                        confirm_cleanup: false,
+                    };
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::SelectSlice(position) => {
+                if let State::Ready {
+                    slices,
+                    selected,
+                    confirm_cleanup,
+                } = &mut self.state
+                    && position < slices.len()
+                {
+                    *selected = Some(position);
+                    *confirm_cleanup = false;
+                }
+                Action::None
+            }
+            Message::RequestCleanup => {
+                if let State::Ready {
+                    slices,
+                    selected: Some(selected),
+                    confirm_cleanup,
+                } = &mut self.state
+                    && slices[*selected].action.is_some()
+                {
+                    *confirm_cleanup = true;
+                }
+                Action::None
+            }
+            Message::CancelCleanup => {
+                if let State::Ready {
+                    confirm_cleanup, ..
+                } = &mut self.state
+                {
+                    *confirm_cleanup = false;
+                }
+                Action::None
+            }
+            Message::ConfirmCleanup => {
+                let State::Ready {
+                    slices,
+                    selected: Some(selected),
+                    confirm_cleanup: true,
+                } = &self.state
+                else {
+                    return Action::None;
+                };
+
+                let Some(action) = slices[*selected].action.clone() else {
+                    return Action::None;
+                };
+
+                self.state = State::Cleaning;
+                Action::Run(Task::perform(
+                    disk_usage::cleanup(action),
+                    Message::CleanupFinished,
+                ))
+            }
+            Message::CleanupFinished(result) => match result {
+                Ok(_) => {
+                    self.state = State::Loading;
+                    Action::AddToastAndRun((
+                        Toast::success_toast("Cleanup finished"),
+                        Task::perform(DiskUsage::get(), Message::UsageLoaded),
+                    ))
+                }
+                Err(err) => {
+                    self.state = State::Loading;
+                    Action::AddToastAndRun((
+                        Toast::error_toast(err),
+                        Task::perform(DiskUsage::get(), Message::UsageLoaded),
+                    ))
+                }
+            },
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading | State::Cleaning => container(
+                column![
+                    text(match self.state {
+                        State::Cleaning => fl!("cleaning-up"),
+                        _ => fl!("calculating-disk-usage"),
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready {
+                slices,
+                selected,
+                confirm_cleanup,
+            } => usage_view(slices, *selected, *confirm_cleanup),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::Back)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe(
+                    (!matches!(self.state, State::Cleaning)).then_some(Message::RefreshUsage),
+                )
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        match self.state {
+            State::Cleaning => iced::widget::stack![main_content].into(),
+            _ => iced::widget::stack![main_content, back_button, refresh_button].into(),
+        }
+    }
+}
+
+/// Splits the [`DiskUsage`] into the slices shown on the chart
+fn slices(usage: &DiskUsage) -> Vec<Slice> {
+    let mut slices = vec![Slice {
+        label: fl!("unreferenced-objects"),
+        size: usage.unreferenced_size,
+        description: fl!("unreferenced-objects-description"),
+        action: Some(CleanupAction::Base),
+    }];
+
+    for deployment in &usage.deployments {
+        let (description, action) = if deployment.is_booted {
+            (fl!("booted-deployment-description"), None)
+        } else if deployment.is_pinned {
+            (fl!("pinned-deployment-description"), None)
+        } else {
+            (
+                fl!("deployment-usage-description"),
+                Some(CleanupAction::Undeploy(deployment.index)),
+            )
+        };
+
+        let version = if deployment.version.is_empty() {
+            deployment.checksum.chars().take(10).collect()
+        } else {
+            deployment.version.clone()
+        };
+
+        slices.push(Slice {
+            label: format!("{} {}", fl!("deployment"), version),
+            size: deployment.unique_size,
+            description,
+            action,
+        });
+    }
+
+    slices.push(Slice {
+        label: fl!("cached-metadata"),
+        size: usage.metadata_size,
+        description: fl!("cached-metadata-description"),
+        action: Some(CleanupAction::Metadata),
+    });
+
+    slices
+}
+
+fn usage_view<'a>(
+    slices: &'a [Slice],
+    selected: Option<usize>,
+    confirm_cleanup: bool,
+) -> Element<'a, Message> {
+    let header = text(fl!("disk-usage"))
+        .width(Length::Fill)
+        .size(18)
+        .font(iced::font::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        });
+
+    if slices.is_empty() {
+        return column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            header,
+            container(text(fl!("no-disk-usage")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into();
+    }
+
+    let total: u64 = slices.iter().map(|s| s.size).sum();
+
+    let chart = iced::widget::canvas(UsageChart {
+        slices,
+        selected,
+        total,
+    })
+    .width(Length::Fixed(240.))
+    .height(Length::Fixed(240.));
+
+    let legend = slices.iter().enumerate().fold(
+        column![].spacing(5.).width(Length::Fill),
+        |col, (position, slice)| {
+            let color_box = container(space())
+                .width(Length::Fixed(14.))
+                .height(Length::Fixed(14.))
+                .style(move |theme: &Theme| {
+                    container::Style::default().background(slice_color(theme, position))
+                });
+
+            let percentage = if total > 0 {
+                slice.size as f64 / total as f64 * 100.
+            } else {
+                0.
+            };
+
+            col.push(
+                button(
+                    row![
+                        color_box,
+                        text(&slice.label).width(Length::Fill),
+                        text(format!(
+                            "{} ({:.0}%)",
+                            disk_usage::format_size(slice.size),
+                            percentage
+                        ))
+                    ]
+                    .spacing(10.)
+                    .align_y(Alignment::Center),
+                )
+                .on_press(Message::SelectSlice(position))
+                .style(if selected == Some(position) {
+                    button::secondary
+                } else {
+                    button::text
+                })
+                .width(Length::Fill),
+            )
+        },
+    );
+
+    let details: Element<Message> = match selected.and_then(|s| slices.get(s)) {
+        Some(slice) => {
+            let cleanup_controls: Element<Message> = match (&slice.action, confirm_cleanup) {
+                (None, _) => space().into(),
+                (Some(action), false) => button(text(cleanup_label(action)))
+                    .style(primary_button_style)
+                    .on_press(Message::RequestCleanup)
+                    .into(),
+                (Some(action), true) => column![
+                    text(cleanup_confirmation(action)),
+                    row![
+                        button(text(fl!("cancel")))
+                            .style(primary_button_style)
+                            .on_press(Message::CancelCleanup),
+                        button(text(cleanup_label(action)))
+                            .style(danger_button_style)
+                            .on_press(Message::ConfirmCleanup)
+                    ]
+                    .spacing(10.)
+                ]
+                .spacing(10.)
+                .into(),
+            };
+
+            container(
+                column![
+                    text(&slice.label).size(16).font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                    text(disk_usage::format_size(slice.size)),
+                    text(&slice.description),
+                    cleanup_controls
+                ]
+                .spacing(10.),
+            )
+            .style(rounderer_box_container_style)
+            .width(Length::Fill)
+            .padding(15.)
+            .into()
+        }
+        None => container(text(fl!("select-disk-usage-slice")))
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .padding(5.)
+            .into(),
+    };
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        header,
+        scrollable(
+            column![
+                row![chart, legend].spacing(20.).align_y(Alignment::Center),
+                details
+            ]
+            .spacing(20.)
+        )
+    ]
+    .padding(20.)
+    .spacing(5.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn cleanup_label(action: &CleanupAction) -> String {
+    match action {
+        CleanupAction::Base => fl!("cleanup-base"),
+        CleanupAction::Metadata => fl!("cleanup-metadata"),
+        CleanupAction::Undeploy(_) => fl!("delete-deployment"),
+    }
+}
+
+fn cleanup_confirmation(action: &CleanupAction) -> String {
+    match action {
+        CleanupAction::Base | CleanupAction::Metadata => fl!("cleanup-confirmation"),
+        CleanupAction::Undeploy(_) => fl!("delete-deployment-confirmation"),
+    }
+}
+
+/// Color of the slice on the given position
+fn slice_color(theme: &Theme, position: usize) -> Color {
+    let palette = theme.extended_palette();
+    let colors = [
+        palette.primary.base.color,
+        palette.success.base.color,
+        palette.warning.base.color,
+        palette.danger.base.color,
+        palette.secondary.strong.color,
+        palette.primary.strong.color,
+        palette.success.strong.color,
+    ];
+    colors[position % colors.len()]
+}
+
+/// Donut chart of the disk usage, each slice can be clicked to select it
+struct UsageChart<'a> {
+    slices: &'a [Slice],
+    selected: Option<usize>,
+    total: u64,
+}
+
+impl UsageChart<'_> {
+    const INNER_RADIUS_RATIO: f32 = 0.55;
+
+    /// Start and end angles of each slice (starting from the top, clockwise)
+    fn angles(&self) -> Vec<(f32, f32)> {
+        let mut start = -PI / 2.;
+        self.slices
+            .iter()
+            .map(|slice| {
+                let sweep = if self.total > 0 {
+                    slice.size as f32 / self.total as f32 * 2. * PI
+                } else {
+                    0.
+                };
+                let angles = (start, start + sweep);
+                start += sweep;
+                angles
+            })
+            .collect()
+    }
+
+    /// Returns the position of the slice under the given point (relative to the bounds)
+    fn slice_at(&self, bounds: Rectangle, point: Point) -> Option<usize> {
+        let center = Point::new(bounds.width / 2., bounds.height / 2.);
+        let radius = bounds.width.min(bounds.height) / 2.;
+        let distance = point.distance(center);
+
+        if distance > radius || distance < radius * Self::INNER_RADIUS_RATIO {
+            return None;
+        }
+
+        let mut angle = (point.y - center.y).atan2(point.x - center.x);
+        if angle < -PI / 2. {
+            angle += 2. * PI;
+        }
+
+        self.angles()
+            .iter()
+            .position(|(start, end)| angle >= *start && angle < *end)
+    }
+}
+
+impl canvas::Program<Message> for UsageChart<'_> {
+    /// Slice under the cursor
+    type State = Option<usize>;
+
+    fn update(
+        &self,
+        hovered: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Message>> {
+        let slice = cursor
+            .position_in(bounds)
+            .and_then(|point| self.slice_at(bounds, point));
+
+        match event {
+            canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => slice
+                .map(|slice| canvas::Action::publish(Message::SelectSlice(slice)).and_capture()),
+            canvas::Event::Mouse(mouse::Event::CursorMoved { .. }) if *hovered != slice => {
+                *hovered = slice;
+                Some(canvas::Action::request_redraw())
+            }
+            _ => None,
+        }
+    }
+
+    fn draw(
+        &self,
+        hovered: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let center = frame.center();
+        let radius = frame.width().min(frame.height()) / 2.;
+        let inner_radius = radius * Self::INNER_RADIUS_RATIO;
+
+        for (position, (start, end)) in self.angles().into_iter().enumerate() {
+            if end <= start {
+                continue;
+            }
+
+            // highlighted slices are drawn slightly bigger
+            let outer_radius = if Some(position) == self.selected || Some(position) == *hovered {
+                radius
+            } else {
+                radius * 0.94
+            };
+
+            let slice = Path::new(|builder| {
+                builder.arc(path::Arc {
+                    center,
+                    radius: outer_radius,
+                    start_angle: Radians(start),
+                    end_angle: Radians(end),
+                });
+                builder.line_to(Point::new(
+                    center.x + inner_radius * end.cos(),
+                    center.y + inner_radius * end.sin(),
+                ));
+                builder.arc(path::Arc {
+                    center,
+                    radius: inner_radius,
+                    start_angle: Radians(end),
+                    end_angle: Radians(start),
+                });
+                builder.close();
+            });
+
+            frame.fill(&slice, slice_color(theme, position));
+        }
+
+        frame.fill_text(canvas::Text {
+            content: disk_usage::format_size(self.total),
+            position: center,
+            color: theme.extended_palette().background.base.text,
+            size: iced::Pixels(16.),
+            align_x: iced::widget::text::Alignment::Center,
+            align_y: iced::alignment::Vertical::Center,
+            ..canvas::Text::default()
+        });
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _hovered: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        match cursor
+            .position_in(bounds)
+            .and_then(|point| self.slice_at(bounds, point))
+        {
+            Some(_) => mouse::Interaction::Pointer,
+            None => mouse::Interaction::default(),
+        }
+    }
+}
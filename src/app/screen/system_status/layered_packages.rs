@@ -4,6 +4,8 @@ use iced::widget::{button, checkbox, column, container, row, space, text, text_i
 use iced::{Alignment, Element, Length};
 use iced::{Padding, Task};
 
+use crate::app::APP_ID;
+use crate::app::core::history::{HistoryEntry, HistoryKind};
 use crate::app::core::layered_packages::{
     CheckPackageError, add_packages, check_package, remove_packages,
 };
@@ -48,6 +50,8 @@ pub enum Message {
     ApplyChanges,
     /// Callback after attempting to apply the current changes
     ApplyChangesCallback(Result<(), anywho::Error>),
+    /// Callback after recording the change on the update history
+    HistoryRecorded(Result<(), anywho::Error>),
 }
 
 pub enum Action {
@@ -55,7 +59,8 @@ pub enum Action {
     Back,
     Run(Task<Message>),
     AddToast(Toast),
-    BackAndCheckReboot,
+    /// Goes back and checks for a pending reboot, showing the given toast if any
+    BackAndCheckReboot(Option<Toast>),
 }
 
 /// Represents each possible open Tab of the LayeredPackages Subscreen
@@ -280,8 +285,16 @@ impl LayeredPackages {
                     }
                 }
             },
+            Message::HistoryRecorded(result) => {
+                Action::BackAndCheckReboot(result.err().map(|err| {
+                    Toast::warning_toast(format!("{}: {}", fl!("history-not-recorded"), err))
+                }))
+            }
             Message::ApplyChangesCallback(result) => match result {
-                Ok(_) => Action::BackAndCheckReboot,
+                Ok(_) => Action::Run(Task::perform(
+                    HistoryEntry::record(APP_ID, HistoryKind::Layering),
+                    Message::HistoryRecorded,
+                )),
                 Err(err) => {
                     self.applying_changes = false;
                     Action::AddToast(Toast::error_toast(err))
@@ -1,23 +1,44 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::collections::{HashMap, HashSet};
+
 use iced::time::Instant;
-use iced::widget::{button, column, container, row, scrollable, space, text};
-use iced::{Alignment, Length, Subscription, Task};
+use iced::widget::{
+    Column, button, checkbox, column, container, progress_bar, row, scrollable, space, text,
+    tooltip,
+};
+use iced::{Alignment, Element, Length, Subscription, Task};
 
+use crate::app::APP_ID;
+use crate::app::core::appstream::Release;
+use crate::app::core::end_of_life::{DataMigration, EndOfLife};
 use crate::app::core::update_applications::{
-    Application, ApplicationStatus, UpdateError, UpdateResult,
+    Application, ApplicationStatus, Hold, Installation, Runtime, UpdateEvent, UpdateResult,
+    UpdateTarget, update_with_progress,
 };
+use crate::app::screen::update_applications::search::Search;
 use crate::app::style::{
-    AccordionButtonPosition, icon_button_style, icon_svg_style, primary_button_style,
+    AccordionButtonPosition, danger_button_style, icon_button_style, icon_svg_style,
+    primary_button_style,
+};
+use crate::app::utils::ui::{
+    AccordionButtonStatus, AccordionIcon, accordion_button, installation_label,
+    permission_kind_label,
 };
-use crate::app::utils::ui::{AccordionButtonStatus, AccordionIcon, accordion_button};
 use crate::app::widgets::spinners::circular::Circular;
 use crate::app::widgets::spinners::easing;
 use crate::app::widgets::toast::Toast;
 use crate::{fl, icons};
 
+pub mod search;
+
 pub struct UpdateApplications {
     state: State,
+    /// Application updates the user chose to skip, keyed by the installation and application ref
+    /// with the skipped version
+    ignored_updates: HashMap<(Installation, String), String>,
+    /// Installation the search installs new applications into by default
+    default_installation: Installation,
 }
 
 #[derive(Debug, Clone)]
@@ -25,25 +46,102 @@ pub enum Message {
     /// Asks to go back a screen                     
     Back,
 
-    /// Callback after loading the applications list
-    LoadedApplicationsList(Result<Vec<Application>, anywho::Error>),
+    /// Callback after loading the applications and runtimes lists, together with the end-of-life refs
+    LoadedApplicationsList(
+        Result<Vec<Application>, anywho::Error>,
+        Result<Vec<Runtime>, anywho::Error>,
+        Result<Vec<EndOfLife>, anywho::Error>,
+    ),
     /// Refreshes the applications list
     RefreshApplicationsList,
 
     /// Attempt to update all applications
     UpdateAllApplications,
-    /// Callback after updating all applications
-    UpdatedAllApplications(Result<Vec<UpdateResult>, UpdateError>),
+    /// Attempt to update a single application (installation and ref of the application)
+    UpdateSingleApplication(Installation, String),
+
+    /// Expands or collapses the release notes of an application (installation and ref of the application)
+    ToggleReleaseNotes(Installation, String),
+
+    /// Attempt to update all runtimes and extensions together
+    UpdateAllRuntimes,
+    /// Attempt to update a single runtime or extension (installation and ref of the runtime)
+    UpdateSingleRuntime(Installation, String),
+
+    /// Checks or unchecks a ref to update it together with the other checked ones (installation and ref)
+    ToggleSelected(Installation, String, bool),
+    /// Checks every application and runtime
+    SelectAll,
+    /// Unchecks every application and runtime
+    SelectNone,
+    /// Attempt to update the checked applications and runtimes in a single transaction
+    UpdateSelected,
+
+    /// Progress of the running update
+    UpdateProgress(UpdateEvent),
+    /// Hides the results of the last update
+    DismissResults,
+    /// Callback after comparing the permissions of the applications about to be updated,
+    /// with the refs to update and the compared applications
+    NewPermissionsChecked(Vec<UpdateTarget>, Vec<Application>),
+    /// Updates the applications pending confirmation even though they request new permissions
+    ConfirmNewPermissions,
+    /// Cancels the update pending confirmation
+    CancelNewPermissions,
+
+    /// Holds back an application with flatpak mask (installation and ref of the application)
+    HoldApplication(Installation, String),
+    /// Skips the available version of an application (installation and ref of the application)
+    IgnoreVersion(Installation, String),
+    /// Releases a held application (installation and ref of the application)
+    ReleaseApplication(Installation, String),
+    /// Callback after masking or unmasking an application
+    HoldChanged(Result<(), anywho::Error>),
 
-    /// Attempt to update a single application
-    UpdateSingleApplication(String),
-    /// Callback after updating a single application
-    UpdatedSingleApplication(Result<(), UpdateError>),
+    /// Replaces an end-of-life application with the one it was rebased to (installation and ref of the old one)
+    MigrateApplication(Installation, String),
+    /// Callback after migrating an end-of-life application
+    Migrated(Result<DataMigration, anywho::Error>),
+
+    /// Search SubScreen Messages
+    Search(search::Message),
+    /// Asks to Open the Search SubScreen
+    OpenSearch,
+}
+
+/// Updates shown on the screen, with the state of the running update
+pub struct Updates {
+    applications: Vec<Application>,
+    /// Applications with an available update that is being held back
+    held: Vec<Application>,
+    runtimes: Vec<Runtime>,
+    /// Installed refs that won't get any more updates
+    end_of_life: Vec<EndOfLife>,
+    /// Applications (installation and ref) with their release notes expanded
+    expanded: Vec<(Installation, String)>,
+    /// Completion percentage of each ref (installation and ref) of the running update
+    progress: HashMap<(Installation, String), f32>,
+    /// Refs (installation and ref) of the running update that flatpak has finished updating
+    finished: HashSet<(Installation, String)>,
+    /// Error of each ref (installation and ref) that failed to update
+    errors: HashMap<(Installation, String), String>,
+    /// Refs (installation and ref) checked to be updated together
+    selected: Vec<(Installation, String)>,
+    /// Result of each ref of the last finished update
+    results: Vec<UpdateResult>,
+    /// Update waiting for confirmation because some applications request new permissions
+    pending_confirmation: Vec<UpdateTarget>,
+    /// Comparing the permissions of the applications about to be updated
+    checking_permissions: bool,
 }
 
 pub enum State {
     Loading,
-    Ready { applications: Vec<Application> },
+    /// Migrating an end-of-life application
+    Working,
+    Ready(Box<Updates>),
+    /// Searching and installing new applications
+    Search(Search),
 }
 
 pub enum Action {
@@ -52,26 +150,38 @@ pub enum Action {
     Run(Task<Message>),
     AddToast(Toast),
     AddMultipleToasts(Vec<Toast>),
+    AddToastAndRun((Toast, Task<Message>)),
+    UpdateIgnoredUpdates(HashMap<(Installation, String), String>),
 }
 
 impl UpdateApplications {
-    pub fn new() -> (Self, Task<Message>) {
+    pub fn new(
+        ignored_updates: HashMap<(Installation, String), String>,
+        default_installation: Installation,
+    ) -> (Self, Task<Message>) {
         (
             Self {
                 state: State::Loading,
+                ignored_updates,
+                default_installation,
             },
-            Task::perform(
-                Application::get_all_available_updates(),
-                Message::LoadedApplicationsList,
-            ),
+            load_updates(),
         )
     }
 
-    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
-        let State::Ready { applications, .. } = &self.state else {
+    pub fn view(&self, now: Instant) -> iced::Element<'_, Message> {
+        if let State::Search(search) = &self.state {
+            return search.view(now).map(Message::Search);
+        }
+
+        let State::Ready(updates) = &self.state else {
             return container(
                 column![
-                    text(fl!("checking-updates")),
+                    text(if matches!(self.state, State::Working) {
+                        fl!("applying-changes")
+                    } else {
+                        fl!("checking-updates")
+                    }),
                     Circular::new()
                         .easing(&easing::EMPHASIZED)
                         .cycle_duration(std::time::Duration::from_secs_f32(5.0))
@@ -85,12 +195,48 @@ impl UpdateApplications {
             .height(Length::Fill)
             .into();
         };
+        let Updates {
+            applications,
+            held,
+            runtimes,
+            end_of_life,
+            expanded,
+            progress,
+            finished,
+            errors,
+            selected,
+            results,
+            pending_confirmation,
+            checking_permissions,
+        } = &**updates;
 
         let any_app_updating = applications
             .iter()
-            .any(|app| app.application_status == ApplicationStatus::Updating);
+            .any(|app| app.application_status == ApplicationStatus::Updating)
+            || runtimes
+                .iter()
+                .any(|runtime| runtime.status == ApplicationStatus::Updating);
 
-        let content = if applications.is_empty() {
+        let content = if *checking_permissions {
+            column![
+                space().width(Length::Fill).height(Length::Fixed(35.)),
+                text(fl!("checking-permissions")),
+                Circular::new()
+                    .easing(&easing::EMPHASIZED)
+                    .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+            ]
+            .padding(20.)
+            .spacing(10.)
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .align_x(Alignment::Center)
+        } else if !pending_confirmation.is_empty() {
+            new_permissions_view(applications, pending_confirmation)
+        } else if applications.is_empty()
+            && held.is_empty()
+            && runtimes.is_empty()
+            && end_of_life.is_empty()
+        {
             column![
                 space().width(Length::Fill).height(Length::Fixed(35.)),
                 text(fl!("no-updates"))
@@ -140,37 +286,116 @@ impl UpdateApplications {
                 };
 
                 let show_spinner = app.application_status == ApplicationStatus::Updating;
+                let select_checkbox = select_checkbox(
+                    selected,
+                    app.installation,
+                    app.flatpak_ref(),
+                    any_app_updating,
+                );
+                let description = application_description(app);
+
+                let position = if index == 0 {
+                    AccordionButtonPosition::Top
+                } else if index == applications.len() - 1 {
+                    AccordionButtonPosition::Bottom
+                } else {
+                    AccordionButtonPosition::Middle
+                };
+
+                let is_expanded = expanded
+                    .iter()
+                    .any(|(i, r)| *i == app.installation && *r == app.flatpak_ref());
+
+                let release_notes_button: Element<Message> = if app.release_notes.is_empty() {
+                    space().into()
+                } else {
+                    button(
+                        icons::get_icon(
+                            if is_expanded {
+                                "pan-up-symbolic"
+                            } else {
+                                "pan-down-symbolic"
+                            },
+                            18,
+                        )
+                        .style(icon_svg_style),
+                    )
+                    .on_press(Message::ToggleReleaseNotes(
+                        app.installation,
+                        app.flatpak_ref(),
+                    ))
+                    .style(icon_button_style)
+                    .into()
+                };
 
-                if index == 0 {
-                    applications_accordion = applications_accordion.push(accordion_button(
-                        AccordionButtonPosition::Top,
-                        app.name.to_string(),
-                        app.latest_version.clone(),
-                        icon,
-                        Message::UpdateSingleApplication(app.app_id.clone()),
-                        button_status,
-                        show_spinner,
-                    ));
-                } else if index == applications.len() {
-                    applications_accordion = applications_accordion.push(accordion_button(
-                        AccordionButtonPosition::Bottom,
-                        app.name.to_string(),
-                        app.latest_version.clone(),
-                        icon,
-                        Message::UpdateSingleApplication(app.app_id.clone()),
-                        button_status,
-                        show_spinner,
-                    ));
+                let hold_button = held_action_button(
+                    "pin-symbolic",
+                    fl!("hold-application"),
+                    (!any_app_updating)
+                        .then(|| Message::HoldApplication(app.installation, app.flatpak_ref())),
+                );
+
+                // without a version there's nothing to tell the skipped update from the next one
+                let ignore_button: Element<Message> = if app.latest_version.is_empty() {
+                    space().into()
                 } else {
-                    applications_accordion = applications_accordion.push(accordion_button(
-                        AccordionButtonPosition::Middle,
-                        app.name.to_string(),
-                        app.latest_version.clone(),
-                        icon,
-                        Message::UpdateSingleApplication(app.app_id.clone()),
-                        button_status,
-                        show_spinner,
-                    ));
+                    held_action_button(
+                        "action-unavailable-symbolic",
+                        fl!("ignore-version"),
+                        (!any_app_updating)
+                            .then(|| Message::IgnoreVersion(app.installation, app.flatpak_ref())),
+                    )
+                };
+
+                applications_accordion = applications_accordion.push(
+                    row![
+                        select_checkbox,
+                        accordion_button(
+                            position,
+                            app.name.to_string(),
+                            description,
+                            icon,
+                            Message::UpdateSingleApplication(app.installation, app.flatpak_ref()),
+                            button_status,
+                            show_spinner,
+                        )
+                        .width(Length::Fill),
+                        release_notes_button,
+                        hold_button,
+                        ignore_button
+                    ]
+                    .spacing(5.)
+                    .align_y(Alignment::Center),
+                );
+
+                if let Some(status) = ref_status_view(
+                    progress,
+                    errors,
+                    app.installation,
+                    app.flatpak_ref(),
+                    app.application_status == ApplicationStatus::Updating,
+                ) {
+                    applications_accordion = applications_accordion.push(status);
+                }
+
+                if !app.new_permissions.is_empty() {
+                    applications_accordion = applications_accordion.push(
+                        container(
+                            text(format!(
+                                "{}: {}",
+                                fl!("requests-new-permissions"),
+                                new_permissions_description(app)
+                            ))
+                            .style(text::warning)
+                            .size(13),
+                        )
+                        .padding([2., 10.]),
+                    );
+                }
+
+                if is_expanded {
+                    applications_accordion =
+                        applications_accordion.push(release_notes_view(&app.release_notes));
                 }
             }
 
@@ -184,15 +409,30 @@ impl UpdateApplications {
                             weight: iced::font::Weight::Bold,
                             ..Default::default()
                         }),
+                    button(text(fl!("install-applications")))
+                        .style(primary_button_style)
+                        .on_press_maybe((!any_app_updating).then_some(Message::OpenSearch)),
                     button(text(fl!("update-all")))
                         .style(primary_button_style)
-                        .on_press_maybe(if any_app_updating {
+                        .on_press_maybe(if any_app_updating || applications.is_empty() {
                             None
                         } else {
                             Some(Message::UpdateAllApplications)
                         })
-                ],
-                scrollable(applications_accordion),
+                ]
+                .spacing(5.),
+                selection_view(selected, applications, runtimes, any_app_updating),
+                overall_progress_view(progress, finished),
+                scrollable(
+                    column![
+                        update_results_view(results),
+                        end_of_life_section(end_of_life, any_app_updating),
+                        applications_accordion,
+                        held_section(held, any_app_updating),
+                        runtimes_section(runtimes, any_app_updating, progress, errors, selected)
+                    ]
+                    .spacing(10.)
+                ),
             ]
             .padding(20.)
             .spacing(5.)
@@ -239,113 +479,1107 @@ impl UpdateApplications {
         iced::widget::stack![main_content, back_button, refresh_button].into()
     }
 
-    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+    pub fn update(&mut self, message: Message, now: Instant) -> Action {
         match message {
             Message::Back => Action::Back,
-            Message::LoadedApplicationsList(result) => match result {
-                Ok(applications) => {
-                    self.state = State::Ready { applications };
+            Message::LoadedApplicationsList(applications, runtimes, end_of_life) => {
+                let mut errors = Vec::new();
+
+                let applications = applications.unwrap_or_else(|err| {
+                    errors.push(Toast::error_toast(err.to_string()));
+                    Vec::new()
+                });
+                let runtimes = runtimes.unwrap_or_else(|err| {
+                    errors.push(Toast::error_toast(err.to_string()));
+                    Vec::new()
+                });
+                let end_of_life = end_of_life.unwrap_or_else(|err| {
+                    errors.push(Toast::error_toast(err.to_string()));
+                    Vec::new()
+                });
+
+                let (held, applications) = applications
+                    .into_iter()
+                    .map(|mut app| {
+                        if app.hold.is_none()
+                            && !app.latest_version.is_empty()
+                            && self
+                                .ignored_updates
+                                .get(&(app.installation, app.flatpak_ref()))
+                                == Some(&app.latest_version)
+                        {
+                            app.hold = Some(Hold::IgnoredVersion);
+                        }
+                        app
+                    })
+                    .partition(|app| app.hold.is_some());
+
+                self.state = State::Ready(Box::new(Updates {
+                    applications,
+                    held,
+                    runtimes,
+                    end_of_life,
+                    expanded: Vec::new(),
+                    progress: HashMap::new(),
+                    finished: HashSet::new(),
+                    errors: HashMap::new(),
+                    selected: Vec::new(),
+                    results: Vec::new(),
+                    pending_confirmation: Vec::new(),
+                    checking_permissions: false,
+                }));
+
+                if errors.is_empty() {
                     Action::None
+                } else {
+                    Action::AddMultipleToasts(errors)
                 }
-                Err(err) => {
-                    self.state = State::Ready {
-                        applications: Vec::new(),
-                    };
-                    Action::AddToast(Toast::error_toast(err.to_string()))
-                }
-            },
+            }
             Message::RefreshApplicationsList => {
                 self.state = State::Loading;
-                Action::Run(Task::perform(
-                    Application::get_all_available_updates(),
-                    Message::LoadedApplicationsList,
-                ))
+                Action::Run(load_updates())
             }
             Message::UpdateAllApplications => {
-                let State::Ready { applications, .. } = &mut self.state else {
+                let State::Ready(updates) = &self.state else {
                     return Action::None;
                 };
+                let Updates { applications, .. } = &**updates;
 
-                if applications.is_empty() {
-                    Action::None
-                } else {
+                let targets = applications
+                    .iter()
+                    .map(Application::update_target)
+                    .collect();
+                self.request_update(targets)
+            }
+            Message::UpdateSingleApplication(installation, flatpak_ref) => {
+                let State::Ready(updates) = &self.state else {
+                    return Action::None;
+                };
+                let Updates { applications, .. } = &**updates;
+
+                match applications
+                    .iter()
+                    .find(|a| a.installation == installation && a.flatpak_ref() == flatpak_ref)
+                {
+                    Some(application) => self.request_update(vec![application.update_target()]),
+                    None => Action::AddToast(Toast::error_toast("Application not found!")),
+                }
+            }
+            Message::ToggleReleaseNotes(installation, flatpak_ref) => {
+                if let State::Ready(updates) = &mut self.state {
+                    let Updates { expanded, .. } = &mut **updates;
+                    match expanded
+                        .iter()
+                        .position(|(i, r)| *i == installation && *r == flatpak_ref)
+                    {
+                        Some(position) => {
+                            expanded.remove(position);
+                        }
+                        None => expanded.push((installation, flatpak_ref)),
+                    }
+                }
+                Action::None
+            }
+            Message::UpdateAllRuntimes => {
+                let State::Ready(updates) = &self.state else {
+                    return Action::None;
+                };
+                let Updates { runtimes, .. } = &**updates;
+
+                let targets = runtimes.iter().map(Runtime::update_target).collect();
+                self.request_update(targets)
+            }
+            Message::UpdateSingleRuntime(installation, flatpak_ref) => {
+                let State::Ready(updates) = &self.state else {
+                    return Action::None;
+                };
+                let Updates { runtimes, .. } = &**updates;
+
+                match runtimes
+                    .iter()
+                    .find(|r| r.installation == installation && r.flatpak_ref() == flatpak_ref)
+                {
+                    Some(runtime) => self.request_update(vec![runtime.update_target()]),
+                    None => Action::AddToast(Toast::error_toast("Runtime not found!")),
+                }
+            }
+            Message::ToggleSelected(installation, flatpak_ref, checked) => {
+                if let State::Ready(updates) = &mut self.state {
+                    let Updates { selected, .. } = &mut **updates;
+                    selected.retain(|(i, r)| !(*i == installation && *r == flatpak_ref));
+                    if checked {
+                        selected.push((installation, flatpak_ref));
+                    }
+                }
+                Action::None
+            }
+            Message::SelectAll => {
+                if let State::Ready(updates) = &mut self.state {
+                    let Updates {
+                        applications,
+                        runtimes,
+                        selected,
+                        ..
+                    } = &mut **updates;
+                    *selected = applications
+                        .iter()
+                        .map(|a| (a.installation, a.flatpak_ref()))
+                        .chain(runtimes.iter().map(|r| (r.installation, r.flatpak_ref())))
+                        .collect();
+                }
+                Action::None
+            }
+            Message::SelectNone => {
+                if let State::Ready(updates) = &mut self.state {
+                    let Updates { selected, .. } = &mut **updates;
+                    selected.clear();
+                }
+                Action::None
+            }
+            Message::UpdateSelected => {
+                let State::Ready(updates) = &self.state else {
+                    return Action::None;
+                };
+                let Updates {
+                    applications,
+                    runtimes,
+                    selected,
+                    ..
+                } = &**updates;
+
+                let is_selected = |installation: Installation, flatpak_ref: String| {
+                    selected.contains(&(installation, flatpak_ref))
+                };
+                let targets = applications
+                    .iter()
+                    .filter(|a| is_selected(a.installation, a.flatpak_ref()))
+                    .map(Application::update_target)
+                    .chain(
+                        runtimes
+                            .iter()
+                            .filter(|r| is_selected(r.installation, r.flatpak_ref()))
+                            .map(Runtime::update_target),
+                    )
+                    .collect();
+                self.request_update(targets)
+            }
+            Message::NewPermissionsChecked(targets, compared) => {
+                let State::Ready(updates) = &mut self.state else {
+                    return Action::None;
+                };
+                let Updates {
+                    applications,
+                    pending_confirmation,
+                    checking_permissions,
+                    ..
+                } = &mut **updates;
+
+                *checking_permissions = false;
+
+                let mut requests_new_permissions = false;
+                for compared_app in compared {
+                    requests_new_permissions |= !compared_app.new_permissions.is_empty();
                     applications
                         .iter_mut()
-                        .for_each(|a| a.application_status = ApplicationStatus::Updating);
+                        .filter(|a| {
+                            a.installation == compared_app.installation
+                                && a.flatpak_ref() == compared_app.flatpak_ref()
+                        })
+                        .for_each(|a| a.new_permissions = compared_app.new_permissions.clone());
+                }
 
-                    Action::Run(Task::perform(
-                        Application::update_all(applications.to_vec()),
-                        Message::UpdatedAllApplications,
-                    ))
+                if requests_new_permissions {
+                    *pending_confirmation = targets;
+                    return Action::None;
                 }
+
+                self.start_update(targets)
             }
-            Message::UpdatedAllApplications(update_results) => {
-                match update_results {
-                    Ok(results) => {
-                        for update_res in results {
-                            let mut failed_updates = Vec::<Toast>::new();
-                            if !update_res.success {
-                                eprintln!(
-                                    "{} failed with: {}",
-                                    &update_res.app_name,
-                                    &update_res.error_message.clone().unwrap_or_default()
-                                );
-                                failed_updates.push(Toast::error_toast(format!(
-                                    "{} failed with: {}",
-                                    &update_res.app_name,
-                                    update_res.error_message.unwrap_or_default()
-                                )));
-                            }
-                            if !failed_updates.is_empty() {
-                                return Action::AddMultipleToasts(failed_updates);
-                            }
-                        }
+            Message::ConfirmNewPermissions => {
+                let State::Ready(updates) = &mut self.state else {
+                    return Action::None;
+                };
+                let Updates {
+                    pending_confirmation,
+                    ..
+                } = &mut **updates;
+
+                let targets = std::mem::take(pending_confirmation);
+                self.start_update(targets)
+            }
+            Message::CancelNewPermissions => {
+                if let State::Ready(updates) = &mut self.state {
+                    let Updates {
+                        pending_confirmation,
+                        ..
+                    } = &mut **updates;
+                    pending_confirmation.clear();
+                }
+                Action::None
+            }
+            Message::DismissResults => {
+                if let State::Ready(updates) = &mut self.state {
+                    let Updates { results, .. } = &mut **updates;
+                    results.clear();
+                }
+                Action::None
+            }
+            Message::UpdateProgress(event) => {
+                let State::Ready(updates) = &mut self.state else {
+                    return Action::None;
+                };
+                let Updates {
+                    applications,
+                    runtimes,
+                    progress,
+                    finished,
+                    errors,
+                    selected,
+                    results,
+                    ..
+                } = &mut **updates;
+
+                match event {
+                    UpdateEvent::Started(installation, flatpak_ref) => {
+                        progress.insert((installation, flatpak_ref), 0.);
+                    }
+                    UpdateEvent::Progress(installation, flatpak_ref, value) => {
+                        progress.insert((installation, flatpak_ref), value);
+                    }
+                    UpdateEvent::Finished(installation, flatpak_ref) => {
+                        progress.insert((installation, flatpak_ref.clone()), 100.);
+                        finished.insert((installation, flatpak_ref));
+                    }
+                    UpdateEvent::Failed(installation, flatpak_ref, error) => {
+                        // the rest of the transaction keeps going, only this row stops updating
+                        applications
+                            .iter_mut()
+                            .filter(|a| {
+                                a.installation == installation && a.flatpak_ref() == flatpak_ref
+                            })
+                            .for_each(|a| a.application_status = ApplicationStatus::NotUpdating);
+                        runtimes
+                            .iter_mut()
+                            .filter(|r| {
+                                r.installation == installation && r.flatpak_ref() == flatpak_ref
+                            })
+                            .for_each(|r| r.status = ApplicationStatus::NotUpdating);
+
+                        progress.remove(&(installation, flatpak_ref.clone()));
+                        errors.insert((installation, flatpak_ref), error);
+                    }
+                    UpdateEvent::Done(update_results) => {
+                        // updated refs leave the list, failed ones stay with their error.
+                        // the results come from the transaction's exit status, not the progress
+                        let updated: HashSet<(Installation, String)> = update_results
+                            .iter()
+                            .filter(|res| res.success)
+                            .map(|res| (res.installation, res.flatpak_ref.clone()))
+                            .collect();
+                        let is_updated = |installation: Installation, flatpak_ref: String| {
+                            updated.contains(&(installation, flatpak_ref))
+                        };
+                        applications.retain(|a| !is_updated(a.installation, a.flatpak_ref()));
+                        runtimes.retain(|r| !is_updated(r.installation, r.flatpak_ref()));
+                        selected.retain(|(i, r)| !is_updated(*i, r.clone()));
+
+                        applications
+                            .iter_mut()
+                            .for_each(|a| a.application_status = ApplicationStatus::NotUpdating);
+                        runtimes
+                            .iter_mut()
+                            .for_each(|r| r.status = ApplicationStatus::NotUpdating);
+                        progress.clear();
+                        finished.clear();
+
+                        *results = update_results;
                     }
-                    Err(err) => return Action::AddToast(Toast::error_toast(err)),
                 }
 
+                Action::None
+            }
+            Message::HoldApplication(installation, flatpak_ref) => {
+                let State::Ready(updates) = &self.state else {
+                    return Action::None;
+                };
+                let Updates { applications, .. } = &**updates;
+
+                let Some(application) = applications
+                    .iter()
+                    .find(|a| a.installation == installation && a.flatpak_ref() == flatpak_ref)
+                else {
+                    return Action::AddToast(Toast::error_toast("Application not found!"));
+                };
+
+                let task = Task::perform(
+                    Application::mask(installation, application.app_id.clone()),
+                    Message::HoldChanged,
+                );
                 self.state = State::Loading;
-                Action::Run(Task::perform(
-                    Application::get_all_available_updates(),
-                    Message::LoadedApplicationsList,
-                ))
+                Action::Run(task)
             }
-            Message::UpdateSingleApplication(application_id) => {
-                let State::Ready { applications, .. } = &mut self.state else {
+            Message::IgnoreVersion(installation, flatpak_ref) => {
+                let State::Ready(updates) = &mut self.state else {
                     return Action::None;
                 };
+                let Updates {
+                    applications, held, ..
+                } = &mut **updates;
 
-                if applications.is_empty() {
-                    Action::None
-                } else {
-                    if let Some(application) =
-                        applications.iter_mut().find(|a| a.app_id == application_id)
-                    {
-                        application.application_status = ApplicationStatus::Updating;
-                        Action::Run(Task::perform(
-                            Application::update(application.app_id.clone()),
-                            Message::UpdatedSingleApplication,
-                        ))
-                    } else {
-                        Action::AddToast(Toast::error_toast("Application not found!"))
+                let Some(position) = applications
+                    .iter()
+                    .position(|a| a.installation == installation && a.flatpak_ref() == flatpak_ref)
+                else {
+                    return Action::AddToast(Toast::error_toast("Application not found!"));
+                };
+
+                let mut application = applications.remove(position);
+                application.hold = Some(Hold::IgnoredVersion);
+                self.ignored_updates.insert(
+                    (installation, flatpak_ref),
+                    application.latest_version.clone(),
+                );
+                held.push(application);
+
+                Action::UpdateIgnoredUpdates(self.ignored_updates.clone())
+            }
+            Message::ReleaseApplication(installation, flatpak_ref) => {
+                let State::Ready(updates) = &mut self.state else {
+                    return Action::None;
+                };
+                let Updates {
+                    applications, held, ..
+                } = &mut **updates;
+
+                let Some(position) = held
+                    .iter()
+                    .position(|a| a.installation == installation && a.flatpak_ref() == flatpak_ref)
+                else {
+                    return Action::AddToast(Toast::error_toast("Application not found!"));
+                };
+
+                match held[position].hold {
+                    Some(Hold::Masked) => {
+                        let task = Task::perform(
+                            Application::unmask(installation, held[position].app_id.clone()),
+                            Message::HoldChanged,
+                        );
+                        self.state = State::Loading;
+                        Action::Run(task)
+                    }
+                    Some(Hold::IgnoredVersion) | None => {
+                        let mut application = held.remove(position);
+                        application.hold = None;
+                        applications.push(application);
+                        self.ignored_updates.remove(&(installation, flatpak_ref));
+
+                        Action::UpdateIgnoredUpdates(self.ignored_updates.clone())
                     }
                 }
             }
-            Message::UpdatedSingleApplication(result) => {
-                if let Err(err) = result {
-                    return Action::AddToast(Toast::error_toast(err));
+            Message::HoldChanged(result) => {
+                self.state = State::Loading;
+                match result {
+                    Ok(_) => Action::Run(load_updates()),
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), load_updates())),
                 }
+            }
+            Message::MigrateApplication(installation, flatpak_ref) => {
+                let State::Ready(updates) = &self.state else {
+                    return Action::None;
+                };
+                let Updates { end_of_life, .. } = &**updates;
+
+                let Some(eol) = end_of_life
+                    .iter()
+                    .find(|e| e.installation == installation && e.flatpak_ref() == flatpak_ref)
+                else {
+                    return Action::AddToast(Toast::error_toast("Application not found!"));
+                };
 
+                let task = Task::perform(eol.clone().migrate(), Message::Migrated);
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::Migrated(result) => {
                 self.state = State::Loading;
-                Action::Run(Task::perform(
-                    Application::get_all_available_updates(),
-                    Message::LoadedApplicationsList,
-                ))
+                match result {
+                    Ok(data_migration) => {
+                        let message = match data_migration {
+                            DataMigration::Moved => fl!("migrated"),
+                            DataMigration::NoData => fl!("migrated-no-data"),
+                            DataMigration::Kept => fl!("migrated-data-kept"),
+                        };
+                        Action::AddToastAndRun((Toast::success_toast(message), load_updates()))
+                    }
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), load_updates())),
+                }
+            }
+            Message::Search(message) => {
+                let State::Search(search) = &mut self.state else {
+                    return Action::None;
+                };
+
+                match search.update(message, now) {
+                    search::Action::None => Action::None,
+                    search::Action::Back => {
+                        // installed applications may come with their own updates
+                        self.state = State::Loading;
+                        Action::Run(load_updates())
+                    }
+                    search::Action::Run(task) => Action::Run(task.map(Message::Search)),
+                    search::Action::AddToast(toast) => Action::AddToast(toast),
+                    search::Action::AddToastAndRun((toast, task)) => {
+                        Action::AddToastAndRun((toast, task.map(Message::Search)))
+                    }
+                }
             }
+            Message::OpenSearch => {
+                let (search, task) = Search::new(self.default_installation);
+                self.state = State::Search(search);
+                Action::Run(task.map(Message::Search))
+            }
+        }
+    }
+
+    /// Compares the permissions of the applications about to be updated, so the update
+    /// can be confirmed if they request new ones, otherwise starts updating the given refs
+    fn request_update(&mut self, targets: Vec<UpdateTarget>) -> Action {
+        let State::Ready(updates) = &mut self.state else {
+            return Action::None;
+        };
+        let Updates {
+            applications,
+            checking_permissions,
+            ..
+        } = &mut **updates;
+
+        // runtimes don't have permissions of their own
+        let to_compare: Vec<Application> = applications
+            .iter()
+            .filter(|a| {
+                targets.iter().any(|target| {
+                    a.installation == target.installation && a.flatpak_ref() == target.flatpak_ref
+                })
+            })
+            .cloned()
+            .collect();
+
+        if to_compare.is_empty() {
+            return self.start_update(targets);
+        }
+
+        *checking_permissions = true;
+        Action::Run(Task::perform(
+            Application::with_new_permissions(to_compare),
+            move |compared| Message::NewPermissionsChecked(targets, compared),
+        ))
+    }
+
+    /// Marks the given refs as updating and starts updating them, streaming the progress
+    fn start_update(&mut self, targets: Vec<UpdateTarget>) -> Action {
+        let State::Ready(updates) = &mut self.state else {
+            return Action::None;
+        };
+        let Updates {
+            applications,
+            runtimes,
+            progress,
+            errors,
+            results,
+            ..
+        } = &mut **updates;
+
+        if targets.is_empty() {
+            return Action::None;
         }
+
+        results.clear();
+
+        for target in &targets {
+            let key = (target.installation, target.flatpak_ref.clone());
+
+            applications
+                .iter_mut()
+                .filter(|a| a.installation == key.0 && a.flatpak_ref() == key.1)
+                .for_each(|a| a.application_status = ApplicationStatus::Updating);
+            runtimes
+                .iter_mut()
+                .filter(|r| r.installation == key.0 && r.flatpak_ref() == key.1)
+                .for_each(|r| r.status = ApplicationStatus::Updating);
+
+            errors.remove(&key);
+            progress.insert(key, 0.);
+        }
+
+        Action::Run(Task::run(
+            update_with_progress(targets),
+            Message::UpdateProgress,
+        ))
     }
 
     pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
         Subscription::none()
     }
 }
+
+/// Description of an application on the list (Ej: 47.1 → 48.1 · User · flathub · app/org.gnome.Calculator/x86_64/stable)
+fn application_description(app: &Application) -> String {
+    let version = if app.current_version.is_empty() || app.latest_version.is_empty() {
+        app.latest_version.clone()
+    } else {
+        format!("{} → {}", app.current_version, app.latest_version)
+    };
+
+    [
+        version,
+        installation_label(app.installation),
+        app.origin.clone(),
+        app.flatpak_ref(),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<String>>()
+    .join(" · ")
+}
+
+/// New permissions requested by the update of an application (Ej: Filesystems: home, Sockets: x11)
+fn new_permissions_description(app: &Application) -> String {
+    app.new_permissions
+        .iter()
+        .map(|(kind, value)| format!("{}: {}", permission_kind_label(*kind), value))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Confirmation of an update that includes applications requesting new permissions
+fn new_permissions_view<'a>(
+    applications: &'a [Application],
+    pending_confirmation: &[UpdateTarget],
+) -> Column<'a, Message> {
+    let list = applications
+        .iter()
+        .filter(|app| !app.new_permissions.is_empty())
+        .filter(|app| {
+            pending_confirmation.iter().any(|target| {
+                target.installation == app.installation && target.flatpak_ref == app.flatpak_ref()
+            })
+        })
+        .fold(column![].spacing(5.), |col, app| {
+            col.push(
+                container(
+                    column![
+                        text(&app.name).font(iced::font::Font {
+                            weight: iced::font::Weight::Bold,
+                            ..Default::default()
+                        }),
+                        text(application_description(app))
+                            .style(text::secondary)
+                            .size(13),
+                        text(new_permissions_description(app))
+                    ]
+                    .spacing(2.),
+                )
+                .style(container::warning)
+                .width(Length::Fill)
+                .padding(10.),
+            )
+        });
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        text(fl!("new-permissions"))
+            .size(24)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .align_x(Alignment::Center),
+        text(fl!("new-permissions-confirmation"))
+            .size(18)
+            .align_x(Alignment::Center),
+        scrollable(list),
+        row![
+            button(text(fl!("cancel")))
+                .style(primary_button_style)
+                .on_press(Message::CancelNewPermissions),
+            button(text(fl!("update-anyway")))
+                .style(danger_button_style)
+                .on_press(Message::ConfirmNewPermissions)
+        ]
+        .spacing(10.)
+    ]
+    .padding(20.)
+    .spacing(10.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+}
+
+/// Release notes of the new versions of an application
+fn release_notes_view<'a>(release_notes: &'a [Release]) -> Element<'a, Message> {
+    let releases = release_notes
+        .iter()
+        .fold(column![].spacing(8.), |col, release| {
+            let title = if release.date.is_empty() {
+                release.version.clone()
+            } else {
+                format!("{} · {}", release.version, release.date)
+            };
+
+            let notes: Element<Message> = if release.notes.is_empty() {
+                text(fl!("no-release-notes")).style(text::secondary).into()
+            } else {
+                release
+                    .notes
+                    .iter()
+                    .fold(column![].spacing(2.), |col, line| col.push(text(line)))
+                    .into()
+            };
+
+            col.push(
+                column![
+                    text(title).font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    }),
+                    notes
+                ]
+                .spacing(3.),
+            )
+        });
+
+    container(releases)
+        .style(container::rounded_box)
+        .width(Length::Fill)
+        .padding(10.)
+        .into()
+}
+
+/// Overall completion of the running update, nothing if there isn't one
+fn overall_progress_view<'a>(
+    progress: &HashMap<(Installation, String), f32>,
+    finished: &HashSet<(Installation, String)>,
+) -> Element<'a, Message> {
+    if progress.is_empty() {
+        return space().into();
+    }
+
+    let overall = progress.values().sum::<f32>() / progress.len() as f32;
+
+    column![
+        text(format!(
+            "{} {}/{} · {:.0}%",
+            fl!("updated-refs"),
+            finished.len(),
+            progress.len(),
+            overall
+        ))
+        .style(text::secondary),
+        progress_bar(0.0..=100.0, overall).girth(Length::Fixed(6.))
+    ]
+    .spacing(3.)
+    .into()
+}
+
+/// Progress bar of a ref that is being updated, or the error if it failed to update
+fn ref_status_view<'a>(
+    progress: &HashMap<(Installation, String), f32>,
+    errors: &'a HashMap<(Installation, String), String>,
+    installation: Installation,
+    flatpak_ref: String,
+    is_updating: bool,
+) -> Option<Element<'a, Message>> {
+    let key = (installation, flatpak_ref);
+
+    if let Some(error) = errors.get(&key) {
+        return Some(
+            container(text(error).style(text::danger).size(13))
+                .padding([2., 10.])
+                .into(),
+        );
+    }
+
+    if !is_updating {
+        return None;
+    }
+
+    let value = progress.get(&key).copied().unwrap_or_default();
+    Some(
+        container(progress_bar(0.0..=100.0, value).girth(Length::Fixed(4.)))
+            .padding([2., 10.])
+            .into(),
+    )
+}
+
+/// Checkbox to add a ref (installation and ref) to the ones updated together
+fn select_checkbox<'a>(
+    selected: &[(Installation, String)],
+    installation: Installation,
+    flatpak_ref: String,
+    any_updating: bool,
+) -> Element<'a, Message> {
+    let is_selected = selected
+        .iter()
+        .any(|(i, r)| *i == installation && *r == flatpak_ref);
+
+    checkbox("", is_selected)
+        .on_toggle_maybe((!any_updating).then_some(move |checked| {
+            Message::ToggleSelected(installation, flatpak_ref.clone(), checked)
+        }))
+        .into()
+}
+
+/// Selection count with the buttons to check every ref, none of them or update the checked ones
+fn selection_view<'a>(
+    selected: &[(Installation, String)],
+    applications: &[Application],
+    runtimes: &[Runtime],
+    any_updating: bool,
+) -> Element<'a, Message> {
+    if applications.is_empty() && runtimes.is_empty() {
+        return space().into();
+    }
+
+    row![
+        text(format!("{} {}", selected.len(), fl!("selected")))
+            .style(text::secondary)
+            .width(Length::Fill),
+        button(text(fl!("select-all")))
+            .style(primary_button_style)
+            .on_press_maybe((!any_updating).then_some(Message::SelectAll)),
+        button(text(fl!("select-none")))
+            .style(primary_button_style)
+            .on_press_maybe((!any_updating && !selected.is_empty()).then_some(Message::SelectNone)),
+        button(text(fl!("update-selected")))
+            .style(primary_button_style)
+            .on_press_maybe(
+                (!any_updating && !selected.is_empty()).then_some(Message::UpdateSelected)
+            )
+    ]
+    .spacing(5.)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Result of each ref of the last finished update, nothing if there isn't one
+fn update_results_view(results: &[UpdateResult]) -> Element<'_, Message> {
+    if results.is_empty() {
+        return space().into();
+    }
+
+    let updated = results.iter().filter(|result| result.success).count();
+
+    let list = results.iter().fold(column![].spacing(5.), |col, result| {
+        let status: Element<Message> = match &result.error_message {
+            Some(error) if !result.success => text(format!("{}: {}", fl!("update-failed"), error))
+                .style(text::danger)
+                .size(13)
+                .into(),
+            _ => text(fl!("update-succeeded"))
+                .style(text::success)
+                .size(13)
+                .into(),
+        };
+
+        col.push(
+            column![
+                text(&result.app_name).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(format!(
+                    "{} · {}",
+                    installation_label(result.installation),
+                    result.flatpak_ref
+                ))
+                .style(text::secondary)
+                .size(13),
+                status
+            ]
+            .spacing(2.),
+        )
+    });
+
+    container(
+        column![
+            row![
+                text(format!(
+                    "{} {}/{}",
+                    fl!("update-results"),
+                    updated,
+                    results.len()
+                ))
+                .width(Length::Fill)
+                .size(16)
+                .font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                button(text(fl!("dismiss")))
+                    .style(primary_button_style)
+                    .on_press(Message::DismissResults)
+            ]
+            .align_y(Alignment::Center),
+            list
+        ]
+        .spacing(10.),
+    )
+    .style(container::rounded_box)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+/// Icon button with a tooltip to hold back an application
+fn held_action_button<'a>(
+    icon: &'static str,
+    tooltip_text: String,
+    message: Option<Message>,
+) -> Element<'a, Message> {
+    tooltip(
+        button(icons::get_icon(icon, 18).style(icon_svg_style))
+            .on_press_maybe(message)
+            .style(icon_button_style),
+        container(text(tooltip_text))
+            .padding(5.)
+            .style(container::secondary),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Section listing the applications with held back updates, each one can be released
+fn held_section<'a>(held: &'a [Application], any_updating: bool) -> Element<'a, Message> {
+    if held.is_empty() {
+        return space().into();
+    }
+
+    let list = held.iter().fold(column![].spacing(5.), |col, app| {
+        let reason = match app.hold {
+            Some(Hold::Masked) => fl!("held-masked"),
+            Some(Hold::IgnoredVersion) | None => {
+                format!("{} {}", fl!("ignored-version"), app.latest_version)
+            }
+        };
+
+        col.push(
+            container(
+                row![
+                    column![
+                        text(&app.name).font(iced::font::Font {
+                            weight: iced::font::Weight::Bold,
+                            ..Default::default()
+                        }),
+                        text(application_description(app))
+                            .style(text::secondary)
+                            .size(13),
+                        text(reason).style(text::secondary).size(13)
+                    ]
+                    .spacing(2.)
+                    .width(Length::Fill),
+                    button(text(fl!("release")))
+                        .style(primary_button_style)
+                        .on_press_maybe((!any_updating).then(|| {
+                            Message::ReleaseApplication(app.installation, app.flatpak_ref())
+                        }))
+                ]
+                .align_y(Alignment::Center),
+            )
+            .style(container::rounded_box)
+            .width(Length::Fill)
+            .padding(10.),
+        )
+    });
+
+    column![
+        text(fl!("held-updates")).size(18).font(iced::font::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        }),
+        list
+    ]
+    .spacing(5.)
+    .into()
+}
+
+/// Section warning about the installed refs that reached their end-of-life,
+/// the ones with a replacement can be migrated to it
+fn end_of_life_section<'a>(
+    end_of_life: &'a [EndOfLife],
+    any_updating: bool,
+) -> Element<'a, Message> {
+    if end_of_life.is_empty() {
+        return space().into();
+    }
+
+    end_of_life
+        .iter()
+        .fold(column![].spacing(5.), |col, eol| {
+            let mut details = vec![
+                installation_label(eol.installation),
+                eol.origin.clone(),
+                eol.flatpak_ref(),
+            ];
+            if !eol.reason.is_empty() {
+                details.push(eol.reason.clone());
+            }
+
+            let migrate_button: Element<Message> =
+                match eol.rebase_id() {
+                    Some(new_id) => button(text(format!("{} {}", fl!("migrate-to"), new_id)))
+                        .style(primary_button_style)
+                        .on_press_maybe((!any_updating).then(|| {
+                            Message::MigrateApplication(eol.installation, eol.flatpak_ref())
+                        }))
+                        .into(),
+                    None => space().into(),
+                };
+
+            col.push(
+                container(
+                    row![
+                        column![
+                            text(format!("{} · {}", eol.name, fl!("end-of-life"))).font(
+                                iced::font::Font {
+                                    weight: iced::font::Weight::Bold,
+                                    ..Default::default()
+                                }
+                            ),
+                            text(details.join(" · ")).size(13)
+                        ]
+                        .spacing(2.)
+                        .width(Length::Fill),
+                        migrate_button
+                    ]
+                    .spacing(5.)
+                    .align_y(Alignment::Center),
+                )
+                .style(container::warning)
+                .width(Length::Fill)
+                .padding(10.),
+            )
+        })
+        .into()
+}
+
+/// Loads the applications and runtimes with available updates and the end-of-life refs at the same time
+fn load_updates() -> Task<Message> {
+    Task::perform(
+        iced::futures::future::join3(
+            Application::get_all_available_updates(APP_ID),
+            Runtime::get_all_available_updates(),
+            EndOfLife::get_all(),
+        ),
+        |(applications, runtimes, end_of_life)| {
+            Message::LoadedApplicationsList(applications, runtimes, end_of_life)
+        },
+    )
+}
+
+/// Section listing the runtimes and extensions with available updates
+fn runtimes_section<'a>(
+    runtimes: &'a [Runtime],
+    any_updating: bool,
+    progress: &HashMap<(Installation, String), f32>,
+    errors: &'a HashMap<(Installation, String), String>,
+    selected: &[(Installation, String)],
+) -> Element<'a, Message> {
+    if runtimes.is_empty() {
+        return space().into();
+    }
+
+    let header = row![
+        text(fl!("runtime-updates"))
+            .width(Length::Fill)
+            .size(18)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+        button(text(fl!("update-runtimes")))
+            .style(primary_button_style)
+            .on_press_maybe(if any_updating {
+                None
+            } else {
+                Some(Message::UpdateAllRuntimes)
+            })
+    ]
+    .align_y(Alignment::Center);
+
+    let accordion =
+        runtimes
+            .iter()
+            .enumerate()
+            .fold(column![].width(Length::Fill), |col, (index, runtime)| {
+                let position = if index == 0 {
+                    AccordionButtonPosition::Top
+                } else if index == runtimes.len() - 1 {
+                    AccordionButtonPosition::Bottom
+                } else {
+                    AccordionButtonPosition::Middle
+                };
+
+                let button_status = if any_updating {
+                    AccordionButtonStatus::Disabled
+                } else {
+                    AccordionButtonStatus::Enabled
+                };
+
+                let dependents = if runtime.dependent_apps.is_empty() {
+                    fl!("no-dependent-apps")
+                } else {
+                    format!("{}: {}", fl!("used-by"), runtime.dependent_apps.join(", "))
+                };
+
+                let description = [
+                    runtime.latest_version.clone(),
+                    installation_label(runtime.installation),
+                    runtime.origin.clone(),
+                    runtime.flatpak_ref(),
+                ]
+                .into_iter()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<String>>()
+                .join(" · ");
+
+                let col = col.push(
+                    row![
+                        select_checkbox(
+                            selected,
+                            runtime.installation,
+                            runtime.flatpak_ref(),
+                            any_updating
+                        ),
+                        accordion_button(
+                            position,
+                            runtime.runtime_id.clone(),
+                            format!("{}\n{}", description, dependents),
+                            AccordionIcon::None,
+                            Message::UpdateSingleRuntime(
+                                runtime.installation,
+                                runtime.flatpak_ref()
+                            ),
+                            button_status,
+                            runtime.status == ApplicationStatus::Updating,
+                        )
+                        .width(Length::Fill)
+                    ]
+                    .spacing(5.)
+                    .align_y(Alignment::Center),
+                );
+
+                match ref_status_view(
+                    progress,
+                    errors,
+                    runtime.installation,
+                    runtime.flatpak_ref(),
+                    runtime.status == ApplicationStatus::Updating,
+                ) {
+                    Some(status) => col.push(status),
+                    None => col,
+                }
+            });
+
+    column![header, accordion].spacing(5.).into()
+}
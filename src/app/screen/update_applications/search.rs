@@ -0,0 +1,334 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::text::LineHeight;
+use iced::widget::{
+    button, column, container, image, radio, row, scrollable, space, svg, text, text_input,
+};
+use iced::{Alignment, Element, Length, Task};
+
+use crate::app::APP_ID;
+use crate::app::core::flatpak_search::SearchResult;
+use crate::app::core::update_applications::{AppIcon, Installation};
+use crate::app::style::{
+    icon_button_style, icon_svg_style, primary_button_style, rounded_button_combo_style,
+    rounded_input_combo_style, rounderer_box_container_style,
+};
+use crate::app::utils::ui::installation_label;
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+pub struct Search {
+    query: String,
+    /// Installation new applications are installed into, starts with the configured default
+    installation: Installation,
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Callback when the search input changes
+    QueryChanged(String),
+    /// Searches the applications matching the input
+    Search,
+    /// Callback after searching the appstream data of the remotes
+    SearchCompleted(Result<Vec<SearchResult>, anywho::Error>),
+
+    /// Callback when choosing the installation to install into
+    InstallationChanged(Installation),
+    /// Installs the given application from its first remote on the selected installation
+    Install(SearchResult),
+    /// Callback after installing an application
+    Installed(Result<(), anywho::Error>),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+    AddToastAndRun((Toast, Task<Message>)),
+}
+
+enum State {
+    /// Nothing searched yet
+    Idle,
+    Searching,
+    /// Installing an application
+    Working,
+    Ready {
+        results: Vec<SearchResult>,
+    },
+}
+
+impl Search {
+    pub fn new(default_installation: Installation) -> (Self, Task<Message>) {
+        (
+            Self {
+                query: String::new(),
+                installation: default_installation,
+                state: State::Idle,
+            },
+            Task::none(),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => match self.state {
+                State::Working => Action::None,
+                State::Idle | State::Searching | State::Ready { .. } => Action::Back,
+            },
+            Message::QueryChanged(value) => {
+                self.query = value;
+                Action::None
+            }
+            Message::Search => {
+                if self.query.trim().is_empty() {
+                    return Action::None;
+                }
+
+                self.state = State::Searching;
+                Action::Run(self.search())
+            }
+            Message::SearchCompleted(result) => match result {
+                Ok(results) => {
+                    self.state = State::Ready { results };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready {
+                        results: Vec::new(),
+                    };
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::InstallationChanged(installation) => {
+                self.installation = installation;
+                Action::None
+            }
+            Message::Install(result) => {
+                let Some(remote) = result.remote_of(self.installation) else {
+                    return Action::AddToast(Toast::error_toast("Remote not found!"));
+                };
+
+                let task = Task::perform(
+                    SearchResult::install(
+                        self.installation,
+                        remote.to_string(),
+                        result.app_id.clone(),
+                        result.branch.clone(),
+                    ),
+                    Message::Installed,
+                );
+                self.state = State::Working;
+                Action::Run(task)
+            }
+            Message::Installed(result) => {
+                // search again so the installed application is marked as such
+                self.state = State::Searching;
+                let task = self.search();
+                match result {
+                    Ok(_) => Action::AddToastAndRun((
+                        Toast::success_toast(fl!("application-installed")),
+                        task,
+                    )),
+                    Err(err) => Action::AddToastAndRun((Toast::error_toast(err), task)),
+                }
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let results: Element<Message> = match &self.state {
+            State::Idle => space().into(),
+            State::Searching | State::Working => container(
+                column![
+                    text(if matches!(self.state, State::Searching) {
+                        fl!("searching")
+                    } else {
+                        fl!("applying-changes")
+                    }),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready { results } if results.is_empty() => {
+                container(text(fl!("no-search-results")))
+                    .align_x(Alignment::Center)
+                    .width(Length::Fill)
+                    .padding(5.)
+                    .into()
+            }
+            State::Ready { results } => {
+                scrollable(results.iter().fold(column![].spacing(5.), |col, result| {
+                    col.push(result_card(result, self.installation))
+                }))
+                .into()
+            }
+        };
+
+        let is_working = matches!(self.state, State::Working);
+
+        let installations = Installation::ALL.iter().fold(
+            row![text(fl!("install-into"))]
+                .spacing(20.)
+                .align_y(Alignment::Center),
+            |row, installation| {
+                row.push(radio(
+                    installation_label(*installation),
+                    *installation,
+                    Some(self.installation),
+                    Message::InstallationChanged,
+                ))
+            },
+        );
+
+        let content = column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            text(fl!("install-applications"))
+                .width(Length::Fill)
+                .size(18)
+                .font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+            row![
+                text_input(fl!("search-placeholder").as_str(), &self.query)
+                    .on_input(Message::QueryChanged)
+                    .on_paste(Message::QueryChanged)
+                    .on_submit(Message::Search)
+                    .width(Length::Fill)
+                    .style(rounded_input_combo_style)
+                    .line_height(LineHeight::Relative(2.)),
+                button(text(fl!("search")).line_height(LineHeight::Relative(2.)))
+                    .on_press_maybe((!is_working).then_some(Message::Search))
+                    .style(rounded_button_combo_style)
+            ],
+            installations,
+            results
+        ]
+        .padding(20.)
+        .spacing(10.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center);
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press_maybe((!is_working).then_some(Message::Back))
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        iced::widget::stack![main_content, back_button].into()
+    }
+
+    fn search(&self) -> Task<Message> {
+        Task::perform(
+            SearchResult::search(APP_ID, self.query.trim().to_string()),
+            Message::SearchCompleted,
+        )
+    }
+}
+
+fn result_card(result: &SearchResult, installation: Installation) -> Element<'_, Message> {
+    let action: Element<Message> = if result.installed.contains(&installation) {
+        text(fl!("installed"))
+            .style(text::success)
+            .font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            })
+            .into()
+    } else if result.remote_of(installation).is_none() {
+        // none of the remotes that provide it are configured on this installation
+        text(fl!("not-available-in-installation"))
+            .style(text::secondary)
+            .into()
+    } else {
+        button(text(fl!("install")))
+            .style(primary_button_style)
+            .on_press(Message::Install(result.clone()))
+            .into()
+    };
+
+    let details = [
+        result.version.clone(),
+        result.remote_names().join(", "),
+        result.app_id.clone(),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<String>>()
+    .join(" · ");
+
+    container(
+        row![
+            result_icon(result.icon.as_ref()),
+            column![
+                text(&result.name).font(iced::font::Font {
+                    weight: iced::font::Weight::Bold,
+                    ..Default::default()
+                }),
+                text(&result.summary),
+                text(details).style(text::secondary).size(13)
+            ]
+            .spacing(2.)
+            .width(Length::Fill),
+            action
+        ]
+        .spacing(10.)
+        .align_y(Alignment::Center),
+    )
+    .style(rounderer_box_container_style)
+    .width(Length::Fill)
+    .padding(10.)
+    .into()
+}
+
+fn result_icon(icon: Option<&AppIcon>) -> Element<'_, Message> {
+    let icon: Element<Message> = match icon {
+        Some(AppIcon::Svg { path }) => svg(svg::Handle::from_path(path))
+            .width(Length::Fixed(50.))
+            .height(Length::Fixed(50.))
+            .into(),
+        Some(AppIcon::Image { path }) => image(image::Handle::from_path(path))
+            .width(Length::Fixed(50.))
+            .height(Length::Fixed(50.))
+            .content_fit(iced::ContentFit::Contain)
+            .into(),
+        None => icons::get_icon("package-x-generic-symbolic", 18)
+            .style(icon_svg_style)
+            .into(),
+    };
+
+    container(icon)
+        .width(Length::Fixed(50.))
+        .height(Length::Fixed(50.))
+        .align_x(Alignment::Center)
+        .align_y(Alignment::Center)
+        .into()
+}
@@ -0,0 +1,424 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use iced::time::Instant;
+use iced::widget::canvas::{self, Frame, Geometry, Path};
+use iced::widget::{button, column, container, row, scrollable, space, text};
+use iced::{
+    Alignment, Color, Element, Length, Point, Rectangle, Renderer, Subscription, Task, Theme, mouse,
+};
+
+use crate::app::APP_ID;
+use crate::app::core::history::{HistoryEntry, HistoryKind};
+use crate::app::style::{icon_button_style, icon_svg_style, rounderer_box_container_style};
+use crate::app::widgets::spinners::circular::Circular;
+use crate::app::widgets::spinners::easing;
+use crate::app::widgets::toast::Toast;
+use crate::{fl, icons};
+
+/// Height of each entry on the timeline
+const ENTRY_HEIGHT: f32 = 90.;
+
+pub struct UpdateHistory {
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Asks to go back a screen
+    Back,
+
+    /// Reloads the recorded history
+    RefreshHistory,
+    /// Callback after loading the recorded history
+    HistoryLoaded(Result<Vec<HistoryEntry>, anywho::Error>),
+
+    /// Opens the full package diff of the entry on the given position
+    OpenEntry(usize),
+}
+
+pub enum Action {
+    None,
+    Back,
+    Run(Task<Message>),
+    AddToast(Toast),
+}
+
+enum State {
+    Loading,
+    Ready {
+        /// Newest entries first
+        entries: Vec<HistoryEntry>,
+        selected: Option<usize>,
+    },
+}
+
+impl UpdateHistory {
+    pub fn new() -> (Self, Task<Message>) {
+        (
+            Self {
+                state: State::Loading,
+            },
+            Task::perform(HistoryEntry::load_all(APP_ID), Message::HistoryLoaded),
+        )
+    }
+
+    pub fn update(&mut self, message: Message, _now: Instant) -> Action {
+        match message {
+            Message::Back => {
+                // the back button closes the open entry before leaving the screen
+                if let State::Ready { selected, .. } = &mut self.state
+                    && selected.is_some()
+                {
+                    *selected = None;
+                    return Action::None;
+                }
+                Action::Back
+            }
+            Message::RefreshHistory => {
+                self.state = State::Loading;
+                Action::Run(Task::perform(
+                    HistoryEntry::load_all(APP_ID),
+                    Message::HistoryLoaded,
+                ))
+            }
+            Message::HistoryLoaded(result) => match result {
+                Ok(mut entries) => {
+                    entries.reverse();
+                    self.state = State::Ready {
+                        entries,
+                        selected: None,
+                    };
+                    Action::None
+                }
+                Err(err) => {
+                    self.state = State::Ready {
+                        entries: Vec::new(),
+                        selected: None,
+                    };
+                    Action::AddToast(Toast::error_toast(err))
+                }
+            },
+            Message::OpenEntry(position) => {
+                if let State::Ready { entries, selected } = &mut self.state
+                    && position < entries.len()
+                {
+                    *selected = Some(position);
+                }
+                Action::None
+            }
+        }
+    }
+
+    pub fn view(&self, _now: Instant) -> iced::Element<'_, Message> {
+        let content: Element<Message> = match &self.state {
+            State::Loading => container(
+                column![
+                    text(fl!("loading")),
+                    Circular::new()
+                        .easing(&easing::EMPHASIZED)
+                        .cycle_duration(std::time::Duration::from_secs_f32(5.0))
+                ]
+                .spacing(10.)
+                .align_x(Alignment::Center),
+            )
+            .align_x(Alignment::Center)
+            .align_y(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into(),
+            State::Ready {
+                entries,
+                selected: Some(selected),
+            } => entry_view(&entries[*selected]),
+            State::Ready {
+                entries,
+                selected: None,
+            } => timeline_view(entries),
+        };
+
+        let main_content = container(content)
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let back_button = container(
+            button(icons::get_icon("go-previous-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::Back)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::Start)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        let refresh_button = container(
+            button(icons::get_icon("view-refresh-symbolic", 18).style(icon_svg_style))
+                .on_press(Message::RefreshHistory)
+                .style(icon_button_style),
+        )
+        .align_x(Alignment::End)
+        .align_y(Alignment::Start)
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(10.);
+
+        iced::widget::stack![main_content, back_button, refresh_button].into()
+    }
+
+    pub fn subscription(&self, _now: Instant) -> Subscription<Message> {
+        Subscription::none()
+    }
+}
+
+fn kind_label(kind: &HistoryKind) -> String {
+    match kind {
+        HistoryKind::Upgrade => fl!("history-upgrade"),
+        HistoryKind::Rollback => fl!("history-rollback"),
+        HistoryKind::Rebase => fl!("history-rebase"),
+        HistoryKind::Layering => fl!("history-layering"),
+    }
+}
+
+fn kind_color(theme: &Theme, kind: &HistoryKind) -> Color {
+    let palette = theme.extended_palette();
+    match kind {
+        HistoryKind::Upgrade => palette.primary.base.color,
+        HistoryKind::Rollback => palette.warning.base.color,
+        HistoryKind::Rebase => palette.danger.base.color,
+        HistoryKind::Layering => palette.success.base.color,
+    }
+}
+
+/// Summary of the package changes of an entry (Ej: 12 upgraded, 1 added)
+fn diff_summary(entry: &HistoryEntry) -> String {
+    let changes = entry.package_changes();
+
+    if changes.is_empty() {
+        return fl!("no-package-changes");
+    }
+
+    [
+        (changes.upgraded.len(), fl!("upgraded")),
+        (changes.downgraded.len(), fl!("downgraded")),
+        (changes.removed.len(), fl!("removed")),
+        (changes.added.len(), fl!("added")),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{} {}", count, label.to_lowercase()))
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+fn short_checksum(checksum: &str) -> &str {
+    checksum.get(..10).unwrap_or(checksum)
+}
+
+fn timeline_view<'a>(entries: &'a [HistoryEntry]) -> Element<'a, Message> {
+    let header = text(fl!("update-history"))
+        .width(Length::Fill)
+        .size(18)
+        .font(iced::font::Font {
+            weight: iced::font::Weight::Bold,
+            ..Default::default()
+        });
+
+    if entries.is_empty() {
+        return column![
+            space().width(Length::Fill).height(Length::Fixed(35.)),
+            header,
+            container(text(fl!("no-history")))
+                .align_x(Alignment::Center)
+                .width(Length::Fill)
+                .padding(5.)
+        ]
+        .padding(20.)
+        .spacing(5.)
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .align_x(Alignment::Center)
+        .into();
+    }
+
+    let timeline =
+        entries
+            .iter()
+            .enumerate()
+            .fold(column![].width(Length::Fill), |col, (position, entry)| {
+                let node = iced::widget::canvas(TimelineNode {
+                    kind: entry.kind.clone(),
+                    is_first: position == 0,
+                    is_last: position == entries.len() - 1,
+                })
+                .width(Length::Fixed(30.))
+                .height(Length::Fixed(ENTRY_HEIGHT));
+
+                let entry_content = column![
+                    row![
+                        text(kind_label(&entry.kind))
+                            .width(Length::Fill)
+                            .font(iced::font::Font {
+                                weight: iced::font::Weight::Bold,
+                                ..Default::default()
+                            }),
+                        text(entry.formatted_date()).style(text::secondary)
+                    ],
+                    text(format!(
+                        "{} ({}) → {} ({})",
+                        entry.from_version,
+                        short_checksum(&entry.from_checksum),
+                        entry.to_version,
+                        short_checksum(&entry.to_checksum)
+                    )),
+                    text(diff_summary(entry)).style(text::secondary)
+                ]
+                .spacing(3.);
+
+                col.push(
+                    row![
+                        node,
+                        container(
+                            button(entry_content)
+                                .on_press(Message::OpenEntry(position))
+                                .style(button::text)
+                                .width(Length::Fill)
+                        )
+                        .style(rounderer_box_container_style)
+                        .width(Length::Fill)
+                        .height(Length::Fixed(ENTRY_HEIGHT - 10.))
+                        .align_y(Alignment::Center)
+                        .padding(5.)
+                    ]
+                    .spacing(10.)
+                    .align_y(Alignment::Center),
+                )
+            });
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        header,
+        scrollable(timeline)
+    ]
+    .padding(20.)
+    .spacing(5.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+fn entry_view<'a>(entry: &'a HistoryEntry) -> Element<'a, Message> {
+    let header = text(format!(
+        "{} · {}",
+        kind_label(&entry.kind),
+        entry.formatted_date()
+    ))
+    .width(Length::Fill)
+    .size(18)
+    .font(iced::font::Font {
+        weight: iced::font::Weight::Bold,
+        ..Default::default()
+    });
+
+    let versions = column![
+        row![
+            text(fl!("from")).font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+            text(format!("{} ({})", entry.from_version, entry.from_checksum))
+        ]
+        .spacing(2.),
+        row![
+            text(fl!("to")).font(iced::font::Font {
+                weight: iced::font::Weight::Bold,
+                ..Default::default()
+            }),
+            text(format!("{} ({})", entry.to_version, entry.to_checksum))
+        ]
+        .spacing(2.)
+    ]
+    .spacing(3.);
+
+    let diff_content: Element<Message> = if entry.package_diff.is_empty() {
+        container(text(fl!("no-package-changes")))
+            .align_x(Alignment::Center)
+            .width(Length::Fill)
+            .padding(5.)
+            .into()
+    } else {
+        scrollable(
+            container(
+                entry
+                    .package_diff
+                    .lines()
+                    .fold(column![].width(Length::Fill), |col, line| {
+                        col.push(text(line).font(iced::Font::MONOSPACE).size(13))
+                    }),
+            )
+            .style(container::rounded_box)
+            .width(Length::Fill)
+            .padding(10.),
+        )
+        .into()
+    };
+
+    column![
+        space().width(Length::Fill).height(Length::Fixed(35.)),
+        header,
+        versions,
+        diff_content
+    ]
+    .padding(20.)
+    .spacing(5.)
+    .height(Length::Fill)
+    .width(Length::Fill)
+    .align_x(Alignment::Center)
+    .into()
+}
+
+/// Segment of the timeline (line and dot) drawn next to each entry
+struct TimelineNode {
+    kind: HistoryKind,
+    is_first: bool,
+    is_last: bool,
+}
+
+impl canvas::Program<Message> for TimelineNode {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let center = frame.center();
+
+        let line_start = if self.is_first { center.y } else { 0. };
+        let line_end = if self.is_last {
+            center.y
+        } else {
+            frame.height()
+        };
+
+        let line = Path::line(
+            Point::new(center.x, line_start),
+            Point::new(center.x, line_end),
+        );
+        frame.stroke(
+            &line,
+            canvas::Stroke::default()
+                .with_width(2.)
+                .with_color(theme.extended_palette().background.strong.color),
+        );
+
+        frame.fill(&Path::circle(center, 7.), kind_color(theme, &self.kind));
+
+        vec![frame.into_geometry()]
+    }
+}
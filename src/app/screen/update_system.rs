@@ -4,9 +4,14 @@ use iced::time::Instant;
 use iced::widget::{button, column, container, responsive, row, rule, scrollable, space, text};
 use iced::{Alignment, Length, Subscription, Task};
 
+use crate::app::APP_ID;
+use crate::app::core::history::{HistoryEntry, HistoryKind};
 use crate::app::core::update_system::SystemUpdate;
 use crate::app::core::{reboot, reboot_pending};
-use crate::app::style::{icon_button_style, icon_svg_style, primary_button_style};
+use crate::app::style::{
+    danger_button_style, icon_button_style, icon_svg_style, primary_button_style,
+};
+use crate::app::utils::ui::{gpg_signature_details, gpg_signature_warning};
 use crate::app::widgets::spinners::circular::Circular;
 use crate::app::widgets::spinners::easing;
 use crate::app::widgets::spinners::linear::Linear;
@@ -32,10 +37,17 @@ pub enum Message {
     /// Attempts to refresh the available updates
     RefreshAvailableUpdates,
 
-    /// Calls the update function to apply an update
+    /// Calls the update function to apply an update, asks for confirmation first
+    /// if the update doesn't have a valid GPG signature
     Update,
+    /// Applies the update pending confirmation even if it doesn't have a valid GPG signature
+    ProceedAnyway,
+    /// Cancels the update pending confirmation
+    CancelUpdate,
     /// Callback after attempting to apply an update
     Updated(Result<(), anywho::Error>),
+    /// Callback after recording the change on the update history
+    HistoryRecorded(Result<(), anywho::Error>),
 
     /// Attempts to reboot the computer
     RebootNow,
@@ -48,6 +60,7 @@ pub enum State {
     Updating,
     PendingReboot,
     Ready { update: Option<SystemUpdate> },
+    ConfirmUntrusted { update: SystemUpdate },
 }
 
 pub enum Action {
@@ -86,6 +99,11 @@ impl UpdateSystem {
                                 .style(primary_button_style)
                                 .on_press(Message::Update)
                         ],
+                        if system_update.has_untrusted_signature() {
+                            gpg_signature_warning(&system_update.gpg_signature)
+                        } else {
+                            space().into()
+                        },
                         scrollable(system_update_card(system_update))
                     ]
                     .padding(20.)
@@ -113,6 +131,35 @@ impl UpdateSystem {
                     .align_x(Alignment::Center)
                 }
             }
+            State::ConfirmUntrusted { update } => column![
+                space().width(Length::Fill).height(Length::Fixed(35.)),
+                text(fl!("untrusted-update"))
+                    .size(24)
+                    .font(iced::font::Font {
+                        weight: iced::font::Weight::Bold,
+                        ..Default::default()
+                    })
+                    .align_x(Alignment::Center),
+                gpg_signature_warning(&update.gpg_signature),
+                text(fl!("untrusted-update-confirmation"))
+                    .size(18)
+                    .align_x(Alignment::Center),
+                row![
+                    button(text(fl!("cancel")))
+                        .style(primary_button_style)
+                        .on_press(Message::CancelUpdate),
+                    button(text(fl!("proceed-anyway")))
+                        .style(danger_button_style)
+                        .on_press(Message::ProceedAnyway)
+                ]
+                .spacing(10.),
+                scrollable(system_update_card(update))
+            ]
+            .padding(20.)
+            .spacing(10.)
+            .height(Length::Fill)
+            .width(Length::Fill)
+            .align_x(Alignment::Center),
             State::PendingReboot => column![
                 space().width(Length::Fill).height(Length::Fixed(35.)),
                 text(fl!("reboot-required"))
@@ -236,16 +283,59 @@ impl UpdateSystem {
                 Action::Run(Task::perform(SystemUpdate::check(), Message::UpdateLoaded))
             }
             Message::Update => {
+                let State::Ready {
+                    update: Some(system_update),
+                } = &self.state
+                else {
+                    return Action::None;
+                };
+
+                if system_update.has_untrusted_signature() {
+                    self.state = State::ConfirmUntrusted {
+                        update: system_update.clone(),
+                    };
+                    return Action::None;
+                }
+
+                self.state = State::Updating;
+                Action::Run(Task::perform(SystemUpdate::update(), Message::Updated))
+            }
+            Message::ProceedAnyway => {
+                if !matches!(self.state, State::ConfirmUntrusted { .. }) {
+                    return Action::None;
+                }
+
                 self.state = State::Updating;
                 Action::Run(Task::perform(SystemUpdate::update(), Message::Updated))
             }
+            Message::CancelUpdate => {
+                if let State::ConfirmUntrusted { update } = &self.state {
+                    self.state = State::Ready {
+                        update: Some(update.clone()),
+                    };
+                }
+                Action::None
+            }
             Message::Updated(result) => match result {
-                Ok(_) => self.update(Message::CheckReboot, now),
+                Ok(_) => Action::Run(Task::perform(
+                    HistoryEntry::record(APP_ID, HistoryKind::Upgrade),
+                    Message::HistoryRecorded,
+                )),
                 Err(err) => Action::AddToastAndRun((
                     Toast::error_toast(err),
                     Task::perform(reboot_pending(), Message::RebootChecked),
                 )),
             },
+            Message::HistoryRecorded(result) => match result {
+                Ok(_) => self.update(Message::CheckReboot, now),
+                Err(err) => {
+                    self.state = State::Loading;
+                    Action::AddToastAndRun((
+                        Toast::warning_toast(format!("{}: {}", fl!("history-not-recorded"), err)),
+                        Task::perform(reboot_pending(), Message::RebootChecked),
+                    ))
+                }
+            },
             Message::RebootNow => Action::Run(Task::perform(reboot(), Message::RebootCallback)),
             Message::RebootCallback(result) => match result {
                 Ok(_) => self.update(Message::CheckReboot, now),
@@ -287,7 +377,7 @@ fn system_update_card<'a>(system_update: &'a SystemUpdate) -> iced::Element<'a,
                 weight: iced::font::Weight::Bold,
                 ..Default::default()
             }),
-            text(&system_update.gpg_signature)
+            gpg_signature_details(&system_update.gpg_signature)
         ]
         .spacing(2.),
         row![
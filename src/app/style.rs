@@ -47,6 +47,7 @@ pub fn rounded_button_combo_style(theme: &Theme, status: button::Status) -> butt
     style
 }
 
+#[derive(Clone, Copy)]
 pub enum TabButtonPosition {
     Left,
     #[allow(dead_code)]
@@ -189,3 +190,14 @@ pub fn primary_button_style(theme: &Theme, status: button::Status) -> button::St
     };
     style
 }
+
+pub fn danger_button_style(theme: &Theme, status: button::Status) -> button::Style {
+    let mut style = button::danger(theme, status);
+    style.border.radius = iced::border::Radius {
+        top_left: 15.0,
+        top_right: 15.0,
+        bottom_left: 15.0,
+        bottom_right: 15.0,
+    };
+    style
+}
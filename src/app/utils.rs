@@ -1,3 +1,4 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+pub mod date;
 pub mod ui;
@@ -2,13 +2,16 @@
 
 use crate::{
     app::{
+        core::flatpak_permissions::PermissionKind,
+        core::gpg_signature::{GpgSignature, GpgSignatureStatus},
+        core::update_applications::Installation,
         style::{AccordionButtonPosition, accordion_button_style, icon_svg_style},
         widgets::spinners::{circular::Circular, easing},
     },
-    icons,
+    fl, icons,
 };
 use iced::{
-    Alignment, Length, Renderer, Theme,
+    Alignment, Element, Length, Renderer, Theme,
     widget::{Button, button, column, container, image, row, space, svg, text},
 };
 use std::fmt::Debug;
@@ -24,6 +27,11 @@ pub enum PossibleBundledSVGs {
     UpdateApplications,
     SystemStatus,
     Rollback,
+    UpdateHistory,
+    InstalledFlatpaks,
+    FlatpakRemotes,
+    Containers,
+    Firmware,
 }
 
 impl PossibleBundledSVGs {
@@ -39,6 +47,17 @@ impl PossibleBundledSVGs {
                 icons::get_handle("utilities-system-monitor-symbolic", 18)
             }
             PossibleBundledSVGs::Rollback => icons::get_handle("view-refresh-symbolic", 18),
+            PossibleBundledSVGs::UpdateHistory => {
+                icons::get_handle("document-open-recent-symbolic", 18)
+            }
+            PossibleBundledSVGs::InstalledFlatpaks => {
+                icons::get_handle("package-x-generic-symbolic", 18)
+            }
+            PossibleBundledSVGs::FlatpakRemotes => icons::get_handle("network-server-symbolic", 18),
+            PossibleBundledSVGs::Containers => icons::get_handle("utilities-terminal-symbolic", 18),
+            PossibleBundledSVGs::Firmware => {
+                icons::get_handle("application-x-firmware-symbolic", 18)
+            }
         }
     }
 }
@@ -159,3 +178,69 @@ where
     .height(80.)
     .on_press_maybe(on_press)
 }
+
+/// Shows the state of a [`GpgSignature`] and the details we could parse from it
+pub fn gpg_signature_details<'a, Message: 'a>(signature: &'a GpgSignature) -> Element<'a, Message> {
+    let status = match signature.status {
+        GpgSignatureStatus::Valid => text(fl!("gpg-valid")).style(text::success),
+        GpgSignatureStatus::Invalid => text(fl!("gpg-invalid")).style(text::danger),
+        GpgSignatureStatus::Unsigned => text(fl!("gpg-unsigned")).style(text::warning),
+        GpgSignatureStatus::Unknown => text(fl!("gpg-unknown")).style(text::secondary),
+    };
+
+    let details = [
+        (fl!("gpg-key-id"), &signature.key_id),
+        (fl!("gpg-signer"), &signature.signer),
+        (fl!("gpg-date"), &signature.date),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.as_ref().map(|value| format!("{} {}", label, value)))
+    .collect::<Vec<String>>();
+
+    let details: Element<'a, Message> = if !details.is_empty() {
+        text(details.join(" · ")).into()
+    } else if matches!(
+        signature.status,
+        GpgSignatureStatus::Invalid | GpgSignatureStatus::Unknown
+    ) && !signature.raw.is_empty()
+    {
+        text(&signature.raw).into()
+    } else {
+        space().into()
+    };
+
+    row![status, details].spacing(5.).into()
+}
+
+/// Prominent warning for commits that don't have a valid [`GpgSignature`]
+pub fn gpg_signature_warning<'a, Message: 'a>(signature: &'a GpgSignature) -> Element<'a, Message> {
+    let warning = match signature.status {
+        GpgSignatureStatus::Valid | GpgSignatureStatus::Unknown => return space().into(),
+        GpgSignatureStatus::Invalid => fl!("gpg-invalid-warning"),
+        GpgSignatureStatus::Unsigned => fl!("gpg-unsigned-warning"),
+    };
+
+    container(text(warning))
+        .style(container::danger)
+        .width(Length::Fill)
+        .padding(10.)
+        .into()
+}
+
+/// Translated name of a flatpak installation
+pub fn installation_label(installation: Installation) -> String {
+    match installation {
+        Installation::User => fl!("installation-user"),
+        Installation::System => fl!("installation-system"),
+    }
+}
+
+/// Translated name of a kind of flatpak permission
+pub fn permission_kind_label(kind: PermissionKind) -> String {
+    match kind {
+        PermissionKind::Filesystem => fl!("permission-filesystems"),
+        PermissionKind::Device => fl!("permission-devices"),
+        PermissionKind::Socket => fl!("permission-sockets"),
+        PermissionKind::TalkName => fl!("permission-talk-names"),
+    }
+}
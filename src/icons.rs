@@ -45,6 +45,17 @@ impl IconCache {
         bundle!("pin-symbolic", 18);
         bundle!("unpin-symbolic", 18);
         bundle!("user-trash-full-symbolic", 18);
+        bundle!("document-edit-symbolic", 18);
+        bundle!("document-open-recent-symbolic", 18);
+        bundle!("pan-down-symbolic", 18);
+        bundle!("pan-up-symbolic", 18);
+        bundle!("package-x-generic-symbolic", 18);
+        bundle!("network-server-symbolic", 18);
+        bundle!("action-unavailable-symbolic", 18);
+        bundle!("utilities-terminal-symbolic", 18);
+        bundle!("media-playback-start-symbolic", 18);
+        bundle!("media-playback-stop-symbolic", 18);
+        bundle!("application-x-firmware-symbolic", 18);
         Self { cache }
     }
 